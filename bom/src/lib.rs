@@ -2,24 +2,28 @@
 
 use std::fmt::Write;
 
-use config::{Config, KeySize};
+use config::{Config, Controller, KeySize, SwitchType};
 
 /// A bill of materials.
 pub struct Bom {
-    items: [Item; 14],
+    items: [Item; 17],
 }
 
 impl Bom {
     /// Creates a new bill of materials from the given configuration.
     #[must_use]
     pub fn from_config(config: &Config) -> Self {
-        let columns = config.finger_cluster.columns.len();
         #[allow(clippy::cast_sign_loss)]
-        let rows = i8::from(config.finger_cluster.rows) as usize;
+        let keys_per_side: usize = config
+            .finger_cluster
+            .column_rows()
+            .iter()
+            .map(|rows| i8::from(rows.count) as usize)
+            .sum();
         #[allow(clippy::cast_sign_loss)]
         let thumb_keys = i8::from(config.thumb_cluster.keys) as usize;
 
-        let finger_keys = 2 * columns * rows;
+        let finger_keys = 2 * keys_per_side;
         let thumb_keys = 2 * thumb_keys;
         let total_keys = finger_keys + thumb_keys;
 
@@ -28,17 +32,50 @@ impl Bom {
             KeySize::U1_5 => (finger_keys, thumb_keys),
         };
 
+        let switch_type = config.keyboard.switch_type;
+        let (switch_name, keycap_1u_name, keycap_1_5u_name) = switch_and_keycap_names(switch_type);
+        let hot_swap_sockets = if config.keyboard.hot_swap && switch_type.has_hot_swap_socket() {
+            total_keys
+        } else {
+            0
+        };
+        let controller = config.keyboard.controller;
+        let (controller_name, controller_url) = controller_name_and_url(controller);
+
+        // Wireless halves are powered by a battery instead of being connected by a TRRS cable
+        let (wired_halves, wireless_halves) = if controller.is_wireless() {
+            (0, 2)
+        } else {
+            (2, 0)
+        };
+        // Only the RP2040 requires a voltage divider for detecting the USB connection
+        let usb_detection_circuits = match controller {
+            Controller::Kb2040 | Controller::Rp2040Zero => 2,
+            Controller::ProMicro | Controller::NiceNano => 0,
+        };
+
         let items = [
-            Item::new(total_keys, "Key switch Cherry MX compatible", ""),
-            Item::new(keycaps_1u, "Keycap DSA 1U", ""),
-            Item::new(keycaps_1_5u, "Keycap DSA 1.5U", ""),
-            Item::new(1, "3.5mm to 3.5mm audio cable 50cm TRRS 4 pin", ""),
+            Item::new(total_keys, switch_name, ""),
+            Item::new(hot_swap_sockets, hot_swap_socket_name(switch_type), ""),
+            Item::new(keycaps_1u, keycap_1u_name, ""),
+            Item::new(keycaps_1_5u, keycap_1_5u_name, ""),
             Item::new(
-                2,
+                wired_halves / 2,
+                "3.5mm to 3.5mm audio cable 50cm TRRS 4 pin",
+                "",
+            ),
+            Item::new(
+                wired_halves,
                 "3.5mm phone connector TRRS 4 pin",
                 "https://mouser.com/ProductDetail/490-SJ2-35894CSMT-TR",
             ),
-            Item::new(2, "KB2040", "https://mouser.com/ProductDetail/485-5302"),
+            Item::new(2, controller_name, controller_url),
+            Item::new(wireless_halves, "LiPo battery 3.7V 110mAh 301230", ""),
+            Item::new(
+                wireless_halves,
+                "Slide switch SPDT right angle",
+                "https://mouser.com/ProductDetail/611-PCM12SMTR",
+            ),
             Item::new(
                 4,
                 "FFC connector 12 pin 1mm pitch",
@@ -50,12 +87,12 @@ impl Bom {
                 "https://mouser.com/ProductDetail/640-1012A0101C4084T",
             ),
             Item::new(
-                2,
+                usb_detection_circuits,
                 "10kOhm resistor 1206",
                 "https://mouser.com/ProductDetail/71-CRCW120610K0FKEBC",
             ),
             Item::new(
-                2,
+                usb_detection_circuits,
                 "Zener diode 3.6V SOD-80",
                 "https://mouser.com/ProductDetail/637-ZMM3.6",
             ),
@@ -100,6 +137,51 @@ impl Bom {
     }
 }
 
+/// Returns the names of the switch and the 1U and 1.5U keycaps for the given switch type.
+fn switch_and_keycap_names(switch_type: SwitchType) -> (&'static str, &'static str, &'static str) {
+    match switch_type {
+        SwitchType::Mx => (
+            "Key switch Cherry MX compatible",
+            "Keycap DSA 1U",
+            "Keycap DSA 1.5U",
+        ),
+        SwitchType::ChocV1 => (
+            "Key switch Kailh Choc v1",
+            "Keycap Kailh Choc v1 compatible 1U",
+            "Keycap Kailh Choc v1 compatible 1.5U",
+        ),
+        SwitchType::ChocV2 => (
+            "Key switch Kailh Choc v2",
+            "Keycap low profile MX stem 1U",
+            "Keycap low profile MX stem 1.5U",
+        ),
+        SwitchType::GateronLowProfile => (
+            "Key switch Gateron low profile",
+            "Keycap Gateron low profile compatible 1U",
+            "Keycap Gateron low profile compatible 1.5U",
+        ),
+    }
+}
+
+/// Returns the name of the Kailh hot-swap socket for the given switch type.
+fn hot_swap_socket_name(switch_type: SwitchType) -> &'static str {
+    match switch_type {
+        SwitchType::Mx => "Kailh hot-swap socket MX CPG151101S11",
+        SwitchType::ChocV1 | SwitchType::ChocV2 => "Kailh hot-swap socket Choc CPG135001S30",
+        SwitchType::GateronLowProfile => "",
+    }
+}
+
+/// Returns the name and URL of the given controller.
+fn controller_name_and_url(controller: Controller) -> (&'static str, &'static str) {
+    match controller {
+        Controller::Kb2040 => ("KB2040", "https://mouser.com/ProductDetail/485-5302"),
+        Controller::Rp2040Zero => ("RP2040-Zero", "https://www.waveshare.com/rp2040-zero.htm"),
+        Controller::ProMicro => ("Pro Micro ATmega32U4 5V 16MHz", ""),
+        Controller::NiceNano => ("nice!nano v2", "https://nicekeyboards.com/nice-nano"),
+    }
+}
+
 // An item in a BOM.
 struct Item {
     amount: usize,
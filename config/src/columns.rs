@@ -1,3 +1,5 @@
+use std::iter::once;
+
 use glam::DVec2;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use show::{
@@ -6,7 +8,7 @@ use show::{
 };
 use show_derive::Show;
 
-use crate::{ColumnCurvatureAngle, FiniteFloat, SideAngle, Vec2};
+use crate::{ColumnCurvatureAngle, FiniteFloat, Ranged, SideAngle, SplayAngle, Vec2};
 
 const MAXIMUM_NORMAL_COLUMNS: usize = 4;
 
@@ -31,6 +33,54 @@ impl Columns {
             + self.normal_columns.len()
             + usize::from(self.right_side_column.active)
     }
+
+    /// Returns the row overrides of the active columns from left to right.
+    #[must_use]
+    pub fn row_overrides(&self) -> Vec<Option<Rows>> {
+        let left_side_column = self
+            .left_side_column
+            .active
+            .then_some(self.left_side_column.rows);
+        let right_side_column = self
+            .right_side_column
+            .active
+            .then_some(self.right_side_column.rows);
+
+        left_side_column
+            .into_iter()
+            .chain(self.normal_columns.0.iter().map(|column| column.rows))
+            .chain(right_side_column)
+            .collect()
+    }
+
+    /// Returns mutable references to the row overrides of all columns, including inactive ones.
+    pub(crate) fn row_overrides_mut(&mut self) -> impl Iterator<Item = &mut Option<Rows>> {
+        once(&mut self.left_side_column.rows)
+            .chain(
+                self.normal_columns
+                    .0
+                    .iter_mut()
+                    .map(|column| &mut column.rows),
+            )
+            .chain(once(&mut self.right_side_column.rows))
+    }
+
+    /// Sets the row overrides which were activated since `previous` to the given rows.
+    pub(crate) fn initialize_new_row_overrides(&mut self, previous: &Columns, rows: Rows) {
+        if self.normal_columns.len() != previous.normal_columns.len() {
+            return;
+        }
+
+        let previous_overrides = once(&previous.left_side_column.rows)
+            .chain(previous.normal_columns.0.iter().map(|column| &column.rows))
+            .chain(once(&previous.right_side_column.rows));
+
+        for (row_override, previous_override) in self.row_overrides_mut().zip(previous_overrides) {
+            if row_override.is_some() && previous_override.is_none() {
+                *row_override = Some(rows);
+            }
+        }
+    }
 }
 
 impl Show for Columns {
@@ -152,6 +202,11 @@ pub struct NormalColumn {
     pub curvature_angle: ColumnCurvatureAngle,
     /// The offset of the column in Y and Z.
     pub offset: Vec2<FiniteFloat>,
+    /// The splay angle of the column, rotating it counterclockwise around its home key.
+    pub splay_angle: SplayAngle,
+    /// The rows of the column, overriding the ones of the finger cluster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Rows>,
 }
 
 /// A configuration of a side column.
@@ -161,6 +216,35 @@ pub struct SideColumn {
     pub active: bool,
     /// The angle of the side column to the neighboring normal one.
     pub side_angle: SideAngle,
+    /// The rows of the side column, overriding the ones of the finger cluster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Rows>,
+}
+
+/// A configuration of the rows of a column.
+#[derive(Clone, Copy, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
+pub struct Rows {
+    /// The number of rows.
+    pub count: Ranged<i8, 1, 5>,
+    /// The row index of the home row.
+    pub home_row_index: Ranged<i8, 0, 4>,
+}
+
+impl Rows {
+    /// Returns the number of rows above the home row, including the home row itself.
+    #[must_use]
+    pub fn upper_count(&self) -> i8 {
+        i8::from(self.count) - i8::from(self.home_row_index)
+    }
+}
+
+impl Default for Rows {
+    fn default() -> Self {
+        Self {
+            count: 3.try_into().expect("value should be within range"),
+            home_row_index: 1.try_into().expect("value should be within range"),
+        }
+    }
 }
 
 /// A type of a column.
@@ -195,6 +279,8 @@ pub struct ColumnConfig {
     pub offset: DVec2,
     /// The side angle of the column.
     pub side_angle: f64,
+    /// The splay angle of the column.
+    pub splay_angle: f64,
 }
 
 impl From<&Columns> for Vec<ColumnConfig> {
@@ -205,6 +291,8 @@ impl From<&Columns> for Vec<ColumnConfig> {
             let &NormalColumn {
                 curvature_angle,
                 offset,
+                splay_angle,
+                ..
             } = columns.normal_columns.first();
 
             configs.push(ColumnConfig {
@@ -212,6 +300,7 @@ impl From<&Columns> for Vec<ColumnConfig> {
                 curvature_angle: curvature_angle.into(),
                 offset: offset.into(),
                 side_angle: columns.left_side_column.side_angle.into(),
+                splay_angle: splay_angle.into(),
             });
         }
 
@@ -219,6 +308,8 @@ impl From<&Columns> for Vec<ColumnConfig> {
             let &NormalColumn {
                 curvature_angle,
                 offset,
+                splay_angle,
+                ..
             } = normal_column;
 
             ColumnConfig {
@@ -226,6 +317,7 @@ impl From<&Columns> for Vec<ColumnConfig> {
                 curvature_angle: curvature_angle.into(),
                 offset: offset.into(),
                 side_angle: 0.0,
+                splay_angle: splay_angle.into(),
             }
         }));
 
@@ -233,6 +325,8 @@ impl From<&Columns> for Vec<ColumnConfig> {
             let &NormalColumn {
                 curvature_angle,
                 offset,
+                splay_angle,
+                ..
             } = columns.normal_columns.last();
 
             configs.push(ColumnConfig {
@@ -240,6 +334,7 @@ impl From<&Columns> for Vec<ColumnConfig> {
                 curvature_angle: curvature_angle.into(),
                 offset: offset.into(),
                 side_angle: columns.right_side_column.side_angle.into(),
+                splay_angle: splay_angle.into(),
             });
         }
 
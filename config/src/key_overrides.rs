@@ -0,0 +1,158 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    mem::discriminant,
+};
+
+use serde::{Deserialize, Serialize};
+use show::{
+    egui::{CollapsingHeader, ComboBox, DragValue, Ui},
+    Show,
+};
+use show_derive::Show;
+
+use crate::{FiniteFloat, Vec3};
+
+/// The maximum index of a column, row or thumb key which can be selected.
+const MAXIMUM_INDEX: u8 = 5;
+
+/// Overrides of the positions of single keys.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct KeyOverrides(Vec<KeyOverride>);
+
+impl KeyOverrides {
+    /// Returns true if there are no key overrides.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the key overrides.
+    pub fn iter(&self) -> impl Iterator<Item = &KeyOverride> {
+        self.0.iter()
+    }
+}
+
+impl Show for KeyOverrides {
+    const TITLE: &'static str = "Key overrides";
+
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let mut override_to_delete = None;
+
+        for (index, key_override) in self.0.iter_mut().enumerate() {
+            CollapsingHeader::new(format!("Key override {}", index + 1))
+                .default_open(true)
+                .show(ui, |ui| {
+                    changed |= key_override.show(ui);
+
+                    if ui.button("Delete override").clicked() {
+                        override_to_delete = Some(index);
+                    }
+                });
+        }
+
+        if let Some(index) = override_to_delete {
+            self.0.remove(index);
+            return true;
+        }
+
+        if ui.button("Add override").clicked() {
+            self.0.push(KeyOverride::default());
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+/// An override of the position of a single key.
+#[derive(Clone, Default, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
+pub struct KeyOverride {
+    /// The key to override the position of.
+    #[serde(flatten)]
+    pub key: KeyIndex,
+    /// The translation of the key along its own X, Y and Z axes.
+    #[serde(default)]
+    pub translation: Vec3<FiniteFloat>,
+    /// The rotation of the key around its own X, Y and Z axes in degrees.
+    #[serde(default)]
+    pub rotation: Vec3<FiniteFloat>,
+}
+
+/// An index of a single key.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum KeyIndex {
+    /// A finger key given by its column and row index.
+    Finger {
+        /// The index of the column from left to right, including the side columns.
+        column: u8,
+        /// The index of the row from bottom to top.
+        row: u8,
+    },
+    /// A thumb key given by its index.
+    Thumb {
+        /// The index of the thumb key from left to right.
+        thumb: u8,
+    },
+}
+
+impl Default for KeyIndex {
+    fn default() -> Self {
+        KeyIndex::Finger { column: 0, row: 0 }
+    }
+}
+
+impl Display for KeyIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyIndex::Finger { .. } => write!(f, "Finger key"),
+            KeyIndex::Thumb { .. } => write!(f, "Thumb key"),
+        }
+    }
+}
+
+impl Show for KeyIndex {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            // Reverse order since widgets are placed right to left
+            match self {
+                KeyIndex::Finger { column, row } => {
+                    changed |= ui
+                        .add(DragValue::new(row).range(0..=MAXIMUM_INDEX).prefix("row "))
+                        .changed();
+                    changed |= ui
+                        .add(
+                            DragValue::new(column)
+                                .range(0..=MAXIMUM_INDEX)
+                                .prefix("column "),
+                        )
+                        .changed();
+                }
+                KeyIndex::Thumb { thumb } => {
+                    changed |= ui
+                        .add(DragValue::new(thumb).range(0..=MAXIMUM_INDEX))
+                        .changed();
+                }
+            }
+
+            ComboBox::from_label("")
+                .selected_text(self.to_string())
+                .show_ui(ui, |ui| {
+                    for value in [KeyIndex::default(), KeyIndex::Thumb { thumb: 0 }] {
+                        let selected = discriminant(self) == discriminant(&value);
+
+                        if ui.selectable_label(selected, value.to_string()).clicked() && !selected {
+                            *self = value;
+                            changed = true;
+                        }
+                    }
+                });
+        });
+
+        changed
+    }
+}
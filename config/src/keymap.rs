@@ -0,0 +1,373 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use show::egui::{CollapsingHeader, Grid, ScrollArea, TextEdit, Ui};
+
+/// The keycodes of the finger keys of a full size keyboard per row from top to bottom.
+static FULL_SIZE_ROWS: [[&str; 12]; 5] = [
+    [
+        "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+    ],
+    [
+        "ESC", "1", "2", "3", "4", "5", "6", "7", "8", "9", "0", "BSPC",
+    ],
+    [
+        "TAB", "Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "ENT",
+    ],
+    [
+        "CAPS", "A", "S", "D", "F", "G", "H", "J", "K", "L", "SCLN", "QUOT",
+    ],
+    [
+        "LSFT", "Z", "X", "C", "V", "B", "N", "M", "COMM", "DOT", "SLSH", "RSFT",
+    ],
+];
+
+/// The keycodes of the thumb keys of a full size keyboard.
+static FULL_SIZE_THUMB_KEYS: [&str; 12] = [
+    "NO", "NO", "LCTL", "LGUI", "LALT", "SPC", "SPC", "RALT", "RGUI", "RCTL", "NO", "NO",
+];
+
+/// The keycode of a key falling through to the next lower layer.
+const TRANSPARENT: &str = "TRNS";
+
+/// The size of a keymap given by the number of keys in each direction.
+#[derive(Clone, Copy)]
+pub struct KeymapSize {
+    /// The number of finger key rows.
+    pub rows: usize,
+    /// The number of finger key columns per half.
+    pub columns: usize,
+    /// The number of thumb keys per half.
+    pub thumb_keys: usize,
+}
+
+/// A keymap consisting of named layers.
+///
+/// Keycodes are given using the QMK names, with basic keycodes omitting the `KC_` prefix.
+/// Missing or empty keycodes fall back to the default ones in the first layer and to transparent
+/// keys in all other layers.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Keymap(Vec<Layer>);
+
+impl Keymap {
+    /// Returns true if there are no layers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the names of the layers as identifiers, with a single `BASE` layer if there are none.
+    ///
+    /// Identifiers already taken by a previous layer are made unique by appending the layer index.
+    #[must_use]
+    pub fn layer_names(&self) -> Vec<String> {
+        if self.0.is_empty() {
+            return vec!["BASE".to_owned()];
+        }
+
+        let mut taken_identifiers = HashSet::new();
+
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let mut identifier = layer.identifier(index);
+                while !taken_identifiers.insert(identifier.clone()) {
+                    identifier = format!("{identifier}_{index}");
+                }
+
+                identifier
+            })
+            .collect()
+    }
+
+    /// Returns the keycode of the finger key in the given layer, row and column.
+    ///
+    /// The rows are indexed from the top and the columns from the left across both halves.
+    #[must_use]
+    pub fn finger_keycode(
+        &self,
+        layer: usize,
+        size: KeymapSize,
+        row: usize,
+        column: usize,
+    ) -> &str {
+        self.0
+            .get(layer)
+            .and_then(|layer| layer.finger_keycode(row, column))
+            .unwrap_or_else(|| {
+                if layer == 0 {
+                    default_finger_keycode(size, row, column)
+                } else {
+                    TRANSPARENT
+                }
+            })
+    }
+
+    /// Returns the keycode of the thumb key with the given index in the given layer.
+    ///
+    /// The thumb keys are indexed from the left across both halves.
+    #[must_use]
+    pub fn thumb_keycode(&self, layer: usize, size: KeymapSize, index: usize) -> &str {
+        self.0
+            .get(layer)
+            .and_then(|layer| layer.thumb_keycode(index))
+            .unwrap_or_else(|| {
+                if layer == 0 {
+                    default_thumb_keycode(size, index)
+                } else {
+                    TRANSPARENT
+                }
+            })
+    }
+
+    /// Sets the keycode of the finger key in the given layer, row and column.
+    ///
+    /// The rows are indexed from the top and the columns from the left across both halves.
+    /// Missing layers are added as required.
+    pub fn set_finger_keycode(&mut self, layer: usize, row: usize, column: usize, keycode: String) {
+        *entry(entry(&mut self.layer_mut(layer).finger_keys, row), column) = keycode;
+    }
+
+    /// Sets the keycode of the thumb key with the given index in the given layer.
+    ///
+    /// The thumb keys are indexed from the left across both halves. Missing layers are added as
+    /// required.
+    pub fn set_thumb_keycode(&mut self, layer: usize, index: usize, keycode: String) {
+        *entry(&mut self.layer_mut(layer).thumb_keys, index) = keycode;
+    }
+
+    /// Returns a mutable reference to the given layer, adding layers as required.
+    fn layer_mut(&mut self, layer: usize) -> &mut Layer {
+        while self.0.len() <= layer {
+            self.add_layer();
+        }
+
+        &mut self.0[layer]
+    }
+
+    /// Adds an empty layer, naming it if it is the first one.
+    fn add_layer(&mut self) {
+        let name = if self.0.is_empty() { "base" } else { "" };
+
+        self.0.push(Layer {
+            name: name.to_owned(),
+            ..Layer::default()
+        });
+    }
+
+    /// Shows a widget for editing the keymap of the given size. Returns true if it was changed.
+    pub fn show(&mut self, ui: &mut Ui, size: KeymapSize) -> bool {
+        let mut changed = false;
+        let mut layer_to_delete = None;
+
+        for (index, layer) in self.0.iter_mut().enumerate() {
+            CollapsingHeader::new(format!("Layer {}", index + 1))
+                .id_salt(("keymap_layer", index))
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name");
+                        changed |= ui.text_edit_singleline(&mut layer.name).changed();
+                    });
+
+                    changed |= layer.show(ui, index, size);
+
+                    if ui.button("Delete layer").clicked() {
+                        layer_to_delete = Some(index);
+                    }
+                });
+        }
+
+        if let Some(index) = layer_to_delete {
+            self.0.remove(index);
+            return true;
+        }
+
+        if ui.button("Add layer").clicked() {
+            self.add_layer();
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+/// A layer of a keymap.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Layer {
+    /// The name of the layer.
+    pub name: String,
+    /// The keycodes of the finger keys per row from top to bottom.
+    ///
+    /// Each row contains the keycodes of both halves from left to right.
+    #[serde(default)]
+    pub finger_keys: Vec<Vec<String>>,
+    /// The keycodes of the thumb keys of both halves from left to right.
+    #[serde(default)]
+    pub thumb_keys: Vec<String>,
+}
+
+impl Layer {
+    /// Returns the name of the layer as an identifier in upper case.
+    fn identifier(&self, index: usize) -> String {
+        let identifier: String = self
+            .name
+            .trim()
+            .chars()
+            .map(|character| {
+                if character.is_ascii_alphanumeric() {
+                    character.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        match identifier.chars().next() {
+            None => format!("LAYER_{index}"),
+            Some(character) if character.is_ascii_digit() => format!("LAYER_{identifier}"),
+            Some(_) => identifier,
+        }
+    }
+
+    /// Returns the keycode of the finger key in the given row and column if there is one.
+    fn finger_keycode(&self, row: usize, column: usize) -> Option<&str> {
+        self.finger_keys
+            .get(row)
+            .and_then(|row| row.get(column))
+            .map(|keycode| keycode.trim())
+            .filter(|keycode| !keycode.is_empty())
+    }
+
+    /// Returns the keycode of the thumb key with the given index if there is one.
+    fn thumb_keycode(&self, index: usize) -> Option<&str> {
+        self.thumb_keys
+            .get(index)
+            .map(|keycode| keycode.trim())
+            .filter(|keycode| !keycode.is_empty())
+    }
+
+    /// Shows a grid of the keycodes of the given size. Returns true if a keycode was changed.
+    ///
+    /// Missing keycodes are shown as hints using the ones they fall back to.
+    fn show(&mut self, ui: &mut Ui, index: usize, size: KeymapSize) -> bool {
+        const KEY_WIDTH: f32 = 40.0;
+
+        let mut changed = false;
+        let fallback = |keycode: &'static str| if index == 0 { keycode } else { TRANSPARENT };
+        let thumb_key_padding = size.columns.saturating_sub(size.thumb_keys);
+        let finger_key_padding = size.thumb_keys.saturating_sub(size.columns);
+
+        ScrollArea::horizontal()
+            .id_salt(("keymap_grid", index))
+            .show(ui, |ui| {
+                Grid::new(("keymap", index)).show(ui, |ui| {
+                    for row in 0..size.rows {
+                        for _ in 0..finger_key_padding {
+                            ui.label("");
+                        }
+                        for column in 0..2 * size.columns {
+                            if column == size.columns {
+                                ui.label("");
+                            }
+
+                            let hint = fallback(default_finger_keycode(size, row, column));
+                            let mut keycode = self
+                                .finger_keycode(row, column)
+                                .unwrap_or_default()
+                                .to_owned();
+
+                            if ui
+                                .add(
+                                    TextEdit::singleline(&mut keycode)
+                                        .hint_text(hint)
+                                        .desired_width(KEY_WIDTH),
+                                )
+                                .changed()
+                            {
+                                *entry(entry(&mut self.finger_keys, row), column) = keycode;
+                                changed = true;
+                            }
+                        }
+                        ui.end_row();
+                    }
+
+                    for _ in 0..thumb_key_padding {
+                        ui.label("");
+                    }
+                    for thumb_key in 0..2 * size.thumb_keys {
+                        if thumb_key == size.thumb_keys {
+                            ui.label("");
+                        }
+
+                        let hint = fallback(default_thumb_keycode(size, thumb_key));
+                        let mut keycode =
+                            self.thumb_keycode(thumb_key).unwrap_or_default().to_owned();
+
+                        if ui
+                            .add(
+                                TextEdit::singleline(&mut keycode)
+                                    .hint_text(hint)
+                                    .desired_width(KEY_WIDTH),
+                            )
+                            .changed()
+                        {
+                            *entry(&mut self.thumb_keys, thumb_key) = keycode;
+                            changed = true;
+                        }
+                    }
+                    ui.end_row();
+                });
+            });
+
+        changed
+    }
+}
+
+/// Returns the default keycode of the finger key in the given row and column.
+fn default_finger_keycode(size: KeymapSize, row: usize, column: usize) -> &'static str {
+    let full_size_row = FULL_SIZE_ROWS.len() - size.rows + row;
+    let full_size_column = FULL_SIZE_ROWS[0].len() / 2 - size.columns + column;
+
+    FULL_SIZE_ROWS[full_size_row][full_size_column]
+}
+
+/// Returns the default keycode of the thumb key with the given index.
+fn default_thumb_keycode(size: KeymapSize, index: usize) -> &'static str {
+    FULL_SIZE_THUMB_KEYS[FULL_SIZE_THUMB_KEYS.len() / 2 - size.thumb_keys + index]
+}
+
+/// Returns a mutable reference to the entry with the given index, inserting empty entries as
+/// required.
+fn entry<T: Default>(entries: &mut Vec<T>, index: usize) -> &mut T {
+    if entries.len() <= index {
+        entries.resize_with(index + 1, T::default);
+    }
+
+    &mut entries[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colliding_layer_names_are_made_unique() {
+        let keymap = Keymap(
+            ["Nav", "nav ", "a-b", "a b", "", "layer 5"]
+                .into_iter()
+                .map(|name| Layer {
+                    name: name.to_owned(),
+                    ..Layer::default()
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            keymap.layer_names(),
+            ["NAV", "NAV_1", "A_B", "A_B_3", "LAYER_4", "LAYER_5"]
+        );
+    }
+}
@@ -1,7 +1,11 @@
 //! The `config` crate contains everything related to the available configuration options.
 
 mod columns;
+mod key_overrides;
+mod keymap;
+mod migration;
 mod primitives;
+mod stable_hasher;
 
 use std::{
     fmt::{self, Display, Formatter},
@@ -11,33 +15,115 @@ use std::{
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use show::{
-    egui::{ComboBox, DragValue, Frame, Margin, ScrollArea, Ui},
+    egui::{CollapsingHeader, ComboBox, DragValue, Frame, Margin, RichText, ScrollArea, Ui},
     Show,
 };
 use show_derive::Show;
 
-pub use columns::{ColumnConfig, ColumnType, Columns, NormalColumn, SideColumn};
+pub use columns::{ColumnConfig, ColumnType, Columns, NormalColumn, Rows, SideColumn};
+pub use key_overrides::{KeyIndex, KeyOverride, KeyOverrides};
+pub use keymap::{Keymap, KeymapSize, Layer};
+pub use migration::{Migration, CURRENT_VERSION};
 pub use primitives::{
-    ColumnCurvatureAngle, FiniteFloat, PositiveFloat, Ranged, SideAngle, ThumbCurvatureAngle, Vec2,
-    Vec3,
+    ColumnCurvatureAngle, DeviceVersion, FabricationSize, FiniteFloat, PositiveFloat, Ranged,
+    SideAngle, SplayAngle, ThumbCurvatureAngle, UsbId, Vec2, Vec3,
 };
 pub use show::Color;
+pub use stable_hasher::StableHasher;
 
 /// A configuration of a keyboard.
 #[derive(Clone, Serialize, Deserialize, Eq)]
 pub struct Config {
+    /// The version of the configuration schema.
+    pub version: u32,
     /// The preview configuration.
     pub preview: Preview,
     /// The finger cluster configuration.
     pub finger_cluster: FingerClusterWrapper,
     /// The thumb cluster configuration.
     pub thumb_cluster: ThumbCluster,
+    /// The overrides of the positions of single keys.
+    #[serde(default, skip_serializing_if = "KeyOverrides::is_empty")]
+    pub key_overrides: KeyOverrides,
     /// The keyboard configuration.
     pub keyboard: Keyboard,
+    /// The fabrication parameters of the matrix PCB.
+    pub fabrication: Fabrication,
+    /// The metadata of the firmware.
+    pub firmware: Firmware,
+    /// The keymap of the firmware.
+    #[serde(default, skip_serializing_if = "Keymap::is_empty")]
+    pub keymap: Keymap,
     /// The colors of the keyboard.
     pub colors: Colors,
 }
 
+impl Config {
+    /// Parses a configuration from the given TOML string.
+    ///
+    /// Configurations saved with an older schema version are upgraded to the current one first.
+    /// Returns the configuration along with the migrations which were applied to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the TOML string is not a valid configuration.
+    pub fn from_toml(toml: &str) -> Result<(Self, Vec<&'static Migration>), Error> {
+        let mut table: toml::Table = toml::from_str(toml)?;
+        let migrations = migration::migrate(&mut table)?;
+
+        // Deserialize from the original string if possible to keep the error locations intact
+        let config = if migrations.is_empty() {
+            toml::from_str(toml)?
+        } else {
+            table.try_into()?
+        };
+
+        Ok((config, migrations))
+    }
+
+    /// Returns the size of the keymap resulting from the finger and thumb cluster.
+    #[allow(clippy::cast_sign_loss)]
+    #[must_use]
+    pub fn keymap_size(&self) -> KeymapSize {
+        KeymapSize {
+            rows: i8::from(self.finger_cluster.matrix_rows().count) as usize,
+            columns: self.finger_cluster.column_rows().len(),
+            thumb_keys: i8::from(self.thumb_cluster.keys) as usize,
+        }
+    }
+
+    /// Sets the switch type and whether to use hot-swap sockets, adjusting the key distances to
+    /// the ones usual for them.
+    pub fn set_switch_type(&mut self, switch_type: SwitchType, hot_swap: bool) {
+        let previous_switch_type = self.keyboard.switch_type;
+        let previous_hot_swap = self.keyboard.hot_swap;
+
+        self.keyboard.switch_type = switch_type;
+        self.keyboard.hot_swap = hot_swap;
+        self.set_key_distances(previous_switch_type, previous_hot_swap);
+    }
+
+    /// Sets the key distances of the finger and thumb cluster to the ones usual for the switch type
+    /// and sockets.
+    ///
+    /// Only the distances still set to the usual ones of the given previous switch type and
+    /// sockets are changed, keeping customized distances.
+    fn set_key_distances(&mut self, previous_switch_type: SwitchType, previous_hot_swap: bool) {
+        let previous_key_distance = previous_switch_type.key_distance(previous_hot_swap);
+        let key_distance = self
+            .keyboard
+            .switch_type
+            .key_distance(self.keyboard.hot_swap);
+
+        if self.finger_cluster.0.key_distance == previous_key_distance {
+            self.finger_cluster.0.key_distance = key_distance;
+        }
+        if self.thumb_cluster.key_distance == previous_key_distance.x {
+            self.thumb_cluster.key_distance = key_distance.x;
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         let toml_string = include_str!("default.toml");
@@ -50,7 +136,9 @@ impl PartialEq for Config {
     fn eq(&self, other: &Self) -> bool {
         self.finger_cluster == other.finger_cluster
             && self.thumb_cluster == other.thumb_cluster
+            && self.key_overrides == other.key_overrides
             && self.keyboard == other.keyboard
+            && self.fabrication == other.fabrication
     }
 }
 
@@ -58,7 +146,9 @@ impl Hash for Config {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.finger_cluster.hash(state);
         self.thumb_cluster.hash(state);
+        self.key_overrides.hash(state);
         self.keyboard.hash(state);
+        self.fabrication.hash(state);
     }
 }
 
@@ -78,7 +168,28 @@ impl Show for Config {
                 changed |= self.preview.show_section(ui);
                 changed |= self.finger_cluster.show_section(ui);
                 changed |= self.thumb_cluster.show_section(ui);
+                changed |= self.key_overrides.show_section(ui);
+
+                let previous_switch_type = self.keyboard.switch_type;
+                let previous_hot_swap = self.keyboard.hot_swap;
                 changed |= self.keyboard.show_section(ui);
+                if self.keyboard.switch_type != previous_switch_type
+                    || self.keyboard.hot_swap != previous_hot_swap
+                {
+                    self.set_key_distances(previous_switch_type, previous_hot_swap);
+                }
+
+                changed |= self.fabrication.show_section(ui);
+
+                changed |= self.firmware.show_section(ui);
+
+                let keymap_size = self.keymap_size();
+                CollapsingHeader::new(RichText::new("Keymap").size(14.0))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        changed |= self.keymap.show(ui, keymap_size);
+                    });
+
                 changed |= self.colors.show_section(ui);
             })
         });
@@ -125,15 +236,34 @@ impl<'de> Deserialize<'de> for FingerClusterWrapper {
         let home_row_index = i8::from(finger_cluster.home_row_index);
         let maximum_index = i8::from(finger_cluster.rows) - 1;
 
-        if home_row_index >= 1 && home_row_index <= maximum_index {
-            finger_cluster.home_row_index.set_maximum(maximum_index);
-
-            Ok(Self(finger_cluster))
-        } else {
-            Err(D::Error::custom(format!(
+        if home_row_index < 1 || home_row_index > maximum_index {
+            return Err(D::Error::custom(format!(
                 "invalid value: home row index `{home_row_index}` is not between 1 and {maximum_index}",
-            )))
+            )));
         }
+        finger_cluster.home_row_index.set_maximum(maximum_index);
+
+        for rows in finger_cluster.columns.row_overrides().into_iter().flatten() {
+            let home_row_index = i8::from(rows.home_row_index);
+            let maximum_index = i8::from(rows.count) - 1;
+
+            if home_row_index > maximum_index {
+                return Err(D::Error::custom(format!(
+                    "invalid value: home row index `{home_row_index}` of a column is not between 0 and {maximum_index}",
+                )));
+            }
+        }
+
+        let (home_row_index, upper_count) = matrix_span(&finger_cluster.column_rows());
+        let row_count = home_row_index + upper_count;
+
+        if row_count > MAXIMUM_ROWS {
+            return Err(D::Error::custom(format!(
+                "invalid value: the columns span {row_count} rows, but at most {MAXIMUM_ROWS} are supported",
+            )));
+        }
+
+        Ok(Self(finger_cluster))
     }
 }
 
@@ -141,10 +271,17 @@ impl Show for FingerClusterWrapper {
     const TITLE: &'static str = FingerCluster::TITLE;
 
     fn show(&mut self, ui: &mut Ui) -> bool {
+        let previous_columns = self.0.columns.clone();
         let changed = self.0.show(ui);
 
         if changed {
             self.0.home_row_index.set_maximum(i8::from(self.0.rows) - 1);
+
+            let default_rows = self.0.default_rows();
+            self.0
+                .columns
+                .initialize_new_row_overrides(&previous_columns, default_rows);
+            self.0.clamp_rows();
         }
 
         changed
@@ -154,16 +291,114 @@ impl Show for FingerClusterWrapper {
 /// A configuration of a finger cluster.
 #[derive(Clone, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
 pub struct FingerCluster {
-    /// The number of rows.
+    /// The number of rows, unless overridden by a column.
     pub rows: Ranged<i8, 2, 5>,
     /// The settings per column.
     pub columns: Columns,
     /// The distance between two neighboring keys in X and Y direction.
     pub key_distance: Vec2<PositiveFloat>,
-    /// The row index of the home row (usually 1).
+    /// The row index of the home row (usually 1), unless overridden by a column.
     pub home_row_index: HomeRowIndex,
 }
 
+impl FingerCluster {
+    /// Returns the rows of every active column from left to right, taking overrides into account.
+    #[must_use]
+    pub fn column_rows(&self) -> Vec<Rows> {
+        let default_rows = self.default_rows();
+
+        self.columns
+            .row_overrides()
+            .into_iter()
+            .map(|rows| rows.unwrap_or(default_rows))
+            .collect()
+    }
+
+    /// Returns the rows of the key matrix, spanning the rows of all columns.
+    ///
+    /// The rows of each column are aligned at their home row.
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn matrix_rows(&self) -> Rows {
+        let (home_row_index, upper_count) = matrix_span(&self.column_rows());
+
+        Rows {
+            count: (home_row_index + upper_count)
+                .try_into()
+                .expect("row count should be within range"),
+            home_row_index: home_row_index
+                .try_into()
+                .expect("home row index should be within range"),
+        }
+    }
+
+    /// Returns the rows of columns without an override.
+    fn default_rows(&self) -> Rows {
+        Rows {
+            count: i8::from(self.rows)
+                .try_into()
+                .expect("row count should be within range"),
+            home_row_index: i8::from(self.home_row_index)
+                .try_into()
+                .expect("home row index should be within range"),
+        }
+    }
+
+    /// Clamps the rows such that each column contains its home row and all of them span at most
+    /// the maximum number of rows.
+    fn clamp_rows(&mut self) {
+        for rows in self.columns.row_overrides_mut().flatten() {
+            let maximum_index = i8::from(rows.count) - 1;
+
+            if i8::from(rows.home_row_index) > maximum_index {
+                rows.home_row_index = maximum_index
+                    .try_into()
+                    .expect("home row index should be within range");
+            }
+        }
+
+        let row_overrides = self.columns.row_overrides();
+        let (home_row_index, _) = matrix_span(&self.column_rows());
+        let maximum_upper_count = MAXIMUM_ROWS - home_row_index;
+
+        for rows in self.columns.row_overrides_mut().flatten() {
+            if rows.upper_count() > maximum_upper_count {
+                rows.count = (i8::from(rows.home_row_index) + maximum_upper_count)
+                    .try_into()
+                    .expect("row count should be within range");
+            }
+        }
+
+        let default_rows = self.default_rows();
+        if row_overrides.contains(&None) && default_rows.upper_count() > maximum_upper_count {
+            self.rows = (i8::from(default_rows.home_row_index) + maximum_upper_count)
+                .try_into()
+                .expect("row count should be within range");
+            self.home_row_index.set_maximum(i8::from(self.rows) - 1);
+        }
+    }
+}
+
+/// The maximum number of rows spanned by all columns.
+const MAXIMUM_ROWS: i8 = 5;
+
+/// Returns the home row index and the number of rows above it (including the home row) of a matrix
+/// spanning the given column rows.
+fn matrix_span(column_rows: &[Rows]) -> (i8, i8) {
+    let home_row_index = column_rows
+        .iter()
+        .map(|rows| i8::from(rows.home_row_index))
+        .max()
+        .unwrap_or_default();
+    let upper_count = column_rows
+        .iter()
+        .map(Rows::upper_count)
+        .max()
+        .unwrap_or_default();
+
+    (home_row_index, upper_count)
+}
+
 /// An index of the home row.
 #[derive(Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(transparent)]
@@ -250,9 +485,161 @@ pub struct ThumbCluster {
     pub resting_key_index: Ranged<i8, 0, 5>,
 }
 
+/// A type of key switch.
+#[derive(Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum SwitchType {
+    /// A Cherry MX compatible switch.
+    #[default]
+    #[serde(rename = "mx")]
+    Mx,
+    /// A Kailh Choc v1 low profile switch.
+    #[serde(rename = "choc_v1")]
+    ChocV1,
+    /// A Kailh Choc v2 low profile switch.
+    #[serde(rename = "choc_v2")]
+    ChocV2,
+    /// A Gateron low profile switch.
+    #[serde(rename = "gateron_low_profile")]
+    GateronLowProfile,
+}
+
+impl SwitchType {
+    /// Returns true if the switch is a low profile switch.
+    #[must_use]
+    pub fn is_low_profile(self) -> bool {
+        !matches!(self, SwitchType::Mx)
+    }
+
+    /// Returns true if there are Kailh hot-swap sockets for the switch.
+    #[must_use]
+    pub fn has_hot_swap_socket(self) -> bool {
+        !matches!(self, SwitchType::GateronLowProfile)
+    }
+
+    /// Returns the usual distance between two neighboring keys in X and Y direction.
+    ///
+    /// The hot-swap sockets stick out on both sides, so they need more room in X direction. The
+    /// sockets of Choc switches also leave no room for tracks above their holes without a bit more
+    /// room in Y direction.
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn key_distance(self, hot_swap: bool) -> Vec2<PositiveFloat> {
+        let (x, y) = match self {
+            SwitchType::Mx if hot_swap => (22.0, 19.05),
+            SwitchType::Mx | SwitchType::GateronLowProfile => (19.05, 19.05),
+            SwitchType::ChocV1 | SwitchType::ChocV2 if hot_swap => (24.3, 18.0),
+            SwitchType::ChocV1 | SwitchType::ChocV2 => (18.0, 17.0),
+        };
+
+        Vec2 {
+            x: x.try_into().expect("key distance should be positive"),
+            y: y.try_into().expect("key distance should be positive"),
+        }
+    }
+}
+
+impl Display for SwitchType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SwitchType::Mx => write!(f, "Cherry MX"),
+            SwitchType::ChocV1 => write!(f, "Kailh Choc v1"),
+            SwitchType::ChocV2 => write!(f, "Kailh Choc v2"),
+            SwitchType::GateronLowProfile => write!(f, "Gateron low profile"),
+        }
+    }
+}
+
+impl Show for SwitchType {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ComboBox::from_label("")
+            .selected_text(self.to_string())
+            .show_ui(ui, |ui| {
+                for value in [
+                    SwitchType::Mx,
+                    SwitchType::ChocV1,
+                    SwitchType::ChocV2,
+                    SwitchType::GateronLowProfile,
+                ] {
+                    changed |= ui
+                        .selectable_value(self, value, value.to_string())
+                        .changed();
+                }
+            });
+        changed
+    }
+}
+
+/// A microcontroller board.
+#[derive(Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum Controller {
+    /// An Adafruit KB2040.
+    #[default]
+    #[serde(rename = "kb2040")]
+    Kb2040,
+    /// A Waveshare RP2040-Zero.
+    #[serde(rename = "rp2040_zero")]
+    Rp2040Zero,
+    /// A Pro Micro with an ATmega32U4.
+    #[serde(rename = "pro_micro")]
+    ProMicro,
+    /// A nice!nano for wireless builds.
+    #[serde(rename = "nice_nano")]
+    NiceNano,
+}
+
+impl Controller {
+    /// Returns true if the controller connects the halves wirelessly.
+    ///
+    /// Wireless controllers are supported by ZMK, all others by QMK.
+    #[must_use]
+    pub fn is_wireless(self) -> bool {
+        matches!(self, Controller::NiceNano)
+    }
+}
+
+impl Display for Controller {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Controller::Kb2040 => write!(f, "KB2040"),
+            Controller::Rp2040Zero => write!(f, "RP2040-Zero"),
+            Controller::ProMicro => write!(f, "Pro Micro"),
+            Controller::NiceNano => write!(f, "nice!nano"),
+        }
+    }
+}
+
+impl Show for Controller {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ComboBox::from_label("")
+            .selected_text(self.to_string())
+            .show_ui(ui, |ui| {
+                for value in [
+                    Controller::Kb2040,
+                    Controller::Rp2040Zero,
+                    Controller::ProMicro,
+                    Controller::NiceNano,
+                ] {
+                    changed |= ui
+                        .selectable_value(self, value, value.to_string())
+                        .changed();
+                }
+            });
+        changed
+    }
+}
+
 /// A configuration of other keyboard settings.
 #[derive(Clone, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
 pub struct Keyboard {
+    /// The type of the key switches.
+    pub switch_type: SwitchType,
+    /// Whether the switches are plugged into Kailh hot-swap sockets instead of being soldered to
+    /// the matrix PCB.
+    pub hot_swap: bool,
+    /// The microcontroller board on the interface PCB.
+    pub controller: Controller,
     /// The keyboard tilting angle along X and Y
     pub tilting_angle: Vec2<FiniteFloat>,
     /// The space around the key cluster.
@@ -267,6 +654,68 @@ pub struct Keyboard {
     pub resolution: PositiveFloat,
 }
 
+/// A configuration of the fabrication parameters of the matrix PCB.
+#[derive(Clone, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
+pub struct Fabrication {
+    /// The thickness of the matrix PCB.
+    pub thickness: PositiveFloat,
+    /// The width of the tracks.
+    pub track_width: FabricationSize,
+    /// The clearance between neighboring tracks.
+    pub track_clearance: FabricationSize,
+    /// The clearance between copper and the outline.
+    pub edge_clearance: FabricationSize,
+    /// The width of the lines on the outline layer.
+    pub outline_width: FabricationSize,
+    /// The width of the flexible connectors between the keys.
+    pub connector_width: PositiveFloat,
+    /// The diameter of the router bit used for milling the outline.
+    pub router_bit_diameter: PositiveFloat,
+}
+
+/// A configuration of the metadata of the firmware.
+#[derive(Clone, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
+pub struct Firmware {
+    /// The name of the keyboard, also determining the name of the QMK keyboard directory.
+    pub name: String,
+    /// The maintainer of the firmware, usually a GitHub user name.
+    pub maintainer: String,
+    /// The USB vendor ID.
+    pub vendor_id: UsbId,
+    /// The USB product ID.
+    pub product_id: UsbId,
+    /// The version of the device.
+    pub version: DeviceVersion,
+}
+
+impl Firmware {
+    /// Returns the name of the keyboard as a QMK keyboard directory name.
+    ///
+    /// The name is converted to lower case with all other characters than letters and digits
+    /// replaced by underscores.
+    #[must_use]
+    pub fn directory_name(&self) -> String {
+        let name: String = self
+            .name
+            .trim()
+            .chars()
+            .map(|character| {
+                if character.is_ascii_alphanumeric() {
+                    character.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if name.is_empty() {
+            "concavum".to_owned()
+        } else {
+            name
+        }
+    }
+}
+
 /// A configuration of the colors used for displaying the keyboard.
 #[derive(Clone, Default, Serialize, Deserialize, Show, PartialEq, Eq, Hash)]
 pub struct Colors {
@@ -298,4 +747,19 @@ pub enum Error {
     /// A value is out of range.
     #[error("value is out of range")]
     OutOfRangeValue,
+    /// The schema version is not a valid version.
+    #[error("invalid value: version is not a non-negative integer")]
+    InvalidVersion,
+    /// The schema version is newer than the supported one.
+    #[error("configuration version `{0}` is newer than the supported version {CURRENT_VERSION}")]
+    UnsupportedVersion(u32),
+    /// A USB ID is not a hexadecimal number.
+    #[error("USB ID is not a hexadecimal number like `0xFEED`")]
+    InvalidUsbId,
+    /// A device version is not in the format major.minor.patch.
+    #[error("device version is not in the format `major.minor.patch` like `1.0.0`")]
+    InvalidDeviceVersion,
+    /// Failed to deserialize from TOML.
+    #[error(transparent)]
+    TomlDeserialize(#[from] toml::de::Error),
 }
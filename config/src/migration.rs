@@ -0,0 +1,305 @@
+use std::fmt::{self, Display, Formatter};
+
+use toml::{Table, Value};
+
+use crate::Error;
+
+/// The current version of the configuration schema.
+pub const CURRENT_VERSION: u32 = 7;
+
+/// The key of the schema version within a configuration.
+const VERSION_KEY: &str = "version";
+
+/// A migration upgrading a configuration to the next schema version.
+pub struct Migration {
+    /// The schema version the configuration is upgraded to.
+    pub version: u32,
+    /// A description of the changes performed by the migration.
+    pub description: &'static str,
+    /// The function performing the migration.
+    migrate: fn(&mut Table),
+}
+
+impl Display for Migration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "version {} to {}: {}",
+            self.version - 1,
+            self.version,
+            self.description
+        )
+    }
+}
+
+/// All migrations in ascending order of their version.
+///
+/// Configurations without a version were saved before versioning was introduced and are treated
+/// as version 0.
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "add the thumb key size as well as the matrix PCB preview and colors",
+        migrate: add_matrix_pcb_options,
+    },
+    Migration {
+        version: 2,
+        description: "add the splay angle of the normal columns",
+        migrate: add_splay_angles,
+    },
+    Migration {
+        version: 3,
+        description: "add the switch type",
+        migrate: add_switch_type,
+    },
+    Migration {
+        version: 4,
+        description: "add the hot-swap socket option",
+        migrate: add_hot_swap,
+    },
+    Migration {
+        version: 5,
+        description: "add the firmware metadata",
+        migrate: add_firmware_metadata,
+    },
+    Migration {
+        version: 6,
+        description: "add the controller",
+        migrate: add_controller,
+    },
+    Migration {
+        version: 7,
+        description: "add the fabrication parameters of the matrix PCB",
+        migrate: add_fabrication_parameters,
+    },
+];
+
+/// Upgrades the given configuration to the current schema version.
+///
+/// Returns the migrations which were applied in the order they were applied in.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the version is invalid or newer than the current version.
+pub fn migrate(table: &mut Table) -> Result<Vec<&'static Migration>, Error> {
+    let version = match table.get(VERSION_KEY) {
+        Some(Value::Integer(version)) => {
+            u32::try_from(*version).map_err(|_| Error::InvalidVersion)?
+        }
+        Some(_) => return Err(Error::InvalidVersion),
+        None => 0,
+    };
+
+    if version > CURRENT_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let migrations: Vec<_> = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > version)
+        .collect();
+
+    for migration in &migrations {
+        (migration.migrate)(table);
+        table.insert(VERSION_KEY.to_owned(), migration.version.into());
+    }
+
+    Ok(migrations)
+}
+
+/// Adds the options introduced with the matrix PCB generation in 1.3.0.
+fn add_matrix_pcb_options(table: &mut Table) {
+    insert_missing(table, &["thumb_cluster", "key_size"], "1.5u".into());
+    insert_missing(table, &["preview", "show_matrix_pcb"], true.into());
+    insert_missing(table, &["colors", "matrix_pcb"], "#008C4A".into());
+    insert_missing(table, &["colors", "ffc_connector"], "#8F8F8F".into());
+}
+
+/// Adds a splay angle of zero to all normal columns.
+fn add_splay_angles(table: &mut Table) {
+    let normal_columns = table
+        .get_mut("finger_cluster")
+        .and_then(|finger_cluster| finger_cluster.get_mut("columns"))
+        .and_then(|columns| columns.get_mut("normal_columns"))
+        .and_then(Value::as_array_mut);
+
+    for column in normal_columns.into_iter().flatten() {
+        if let Value::Table(column) = column {
+            insert_missing(column, &["splay_angle"], 0.into());
+        }
+    }
+}
+
+/// Adds the switch type, which was always Cherry MX before.
+fn add_switch_type(table: &mut Table) {
+    insert_missing(table, &["keyboard", "switch_type"], "mx".into());
+}
+
+/// Adds the hot-swap socket option, as the switches were always soldered to the matrix PCB before.
+fn add_hot_swap(table: &mut Table) {
+    insert_missing(table, &["keyboard", "hot_swap"], false.into());
+}
+
+/// Adds the firmware metadata, which was hardcoded in the QMK configuration before.
+fn add_firmware_metadata(table: &mut Table) {
+    insert_missing(table, &["firmware", "name"], "Concavum".into());
+    insert_missing(table, &["firmware", "maintainer"], "julianschuler".into());
+    insert_missing(table, &["firmware", "vendor_id"], "0xFEED".into());
+    insert_missing(table, &["firmware", "product_id"], "0xD6AF".into());
+    insert_missing(table, &["firmware", "version"], "2.0.0".into());
+}
+
+/// Adds the controller, which was always a KB2040 before.
+fn add_controller(table: &mut Table) {
+    insert_missing(table, &["keyboard", "controller"], "kb2040".into());
+}
+
+/// Adds the fabrication parameters of the matrix PCB, which were hardcoded before.
+fn add_fabrication_parameters(table: &mut Table) {
+    insert_missing(table, &["fabrication", "thickness"], 0.6.into());
+    insert_missing(table, &["fabrication", "track_width"], 0.15.into());
+    insert_missing(table, &["fabrication", "track_clearance"], 0.15.into());
+    insert_missing(table, &["fabrication", "edge_clearance"], 0.25.into());
+    insert_missing(table, &["fabrication", "outline_width"], 0.05.into());
+    insert_missing(table, &["fabrication", "connector_width"], 2.0.into());
+    insert_missing(table, &["fabrication", "router_bit_diameter"], 2.0.into());
+}
+
+/// Inserts the value at the given path if there is no value yet.
+///
+/// Missing tables along the path are created, paths through other values are left untouched.
+fn insert_missing(table: &mut Table, path: &[&str], value: Value) {
+    let Some((key, tables)) = path.split_last() else {
+        return;
+    };
+
+    let mut table = table;
+    for name in tables {
+        let entry = table
+            .entry(*name)
+            .or_insert_with(|| Value::Table(Table::new()));
+
+        match entry {
+            Value::Table(inner) => table = inner,
+            _ => return,
+        }
+    }
+
+    table.entry(*key).or_insert(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+
+    use super::*;
+
+    /// The default configuration as saved with schema version 1.
+    const VERSION_1_CONFIG: &str = r##"
+version = 1
+
+[preview]
+show_keys = true
+show_matrix_pcb = true
+show_interface_pcb = true
+show_keyboard = true
+show_bottom_plate = false
+
+[finger_cluster]
+rows = 3
+key_distance = [19.05, 19.05]
+home_row_index = 1
+
+[finger_cluster.columns]
+left_side_column = { active = true, side_angle = 15 }
+normal_columns = [
+  { curvature_angle = 20, offset = [0, 0] },
+  { curvature_angle = 20, offset = [0, -3] },
+  { curvature_angle = 20, offset = [0, 0] },
+  { curvature_angle = 20, offset = [-20, 5] }
+]
+right_side_column = { active = true, side_angle = 15 }
+
+[thumb_cluster]
+keys = 3
+key_size = "1.5u"
+curvature_angle = 15
+rotation = [-17, -29, 18.5]
+offset = [-22.1, -48, 10]
+key_distance = 19.05
+resting_key_index = 1
+
+[keyboard]
+tilting_angle = [15, 20]
+circumference_distance = 7
+rounding_radius = 3
+shell_thickness = 2.1
+bottom_plate_thickness = 1.6
+resolution = 1.0
+
+[colors]
+keyboard = "#83A598"
+keycap = "#3F3F3F"
+switch = "#FFFFFF"
+matrix_pcb = "#008C4A"
+interface_pcb = "#6C0D5D"
+ffc_connector = "#8F8F8F"
+background = "#282828"
+"##;
+
+    #[test]
+    fn version_1_config_is_upgraded_to_default() {
+        let (config, migrations) =
+            Config::from_toml(VERSION_1_CONFIG).expect("the configuration should be loadable");
+
+        let versions: Vec<_> = migrations
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+        assert_eq!(versions, (2..=CURRENT_VERSION).collect::<Vec<_>>());
+        assert_eq!(
+            toml::to_string(&config).expect("the configuration should be serializable"),
+            toml::to_string(&Config::default()).expect("the default should be serializable")
+        );
+    }
+
+    #[test]
+    fn unversioned_config_runs_all_migrations() {
+        let mut table: Table = toml::from_str(VERSION_1_CONFIG).expect("the TOML should be valid");
+        table.remove(VERSION_KEY);
+
+        let migrations = migrate(&mut table).expect("the migrations should succeed");
+
+        assert_eq!(migrations.len(), MIGRATIONS.len());
+        assert_eq!(table.get(VERSION_KEY), Some(&Value::from(CURRENT_VERSION)));
+    }
+
+    #[test]
+    fn current_config_runs_no_migrations() {
+        let mut table: Table =
+            toml::from_str(include_str!("default.toml")).expect("the TOML should be valid");
+
+        assert!(migrate(&mut table)
+            .expect("the migrations should succeed")
+            .is_empty());
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut table = Table::new();
+        table.insert(VERSION_KEY.to_owned(), (CURRENT_VERSION + 1).into());
+
+        assert!(matches!(
+            migrate(&mut table),
+            Err(Error::UnsupportedVersion(version)) if version == CURRENT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn invalid_version_is_rejected() {
+        let mut table = Table::new();
+        table.insert(VERSION_KEY.to_owned(), "one".into());
+
+        assert!(matches!(migrate(&mut table), Err(Error::InvalidVersion)));
+    }
+}
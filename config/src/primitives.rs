@@ -13,6 +13,7 @@ use show::{
 use crate::Error;
 
 const DRAG_SPEED: f64 = 0.1;
+const FINE_DRAG_SPEED: f64 = 0.01;
 
 /// A curvature angle between two neighboring column keys.
 pub type ColumnCurvatureAngle = Ranged<FiniteFloat, 0, 50>;
@@ -23,6 +24,9 @@ pub type ThumbCurvatureAngle = Ranged<FiniteFloat, -20, 50>;
 /// A side angle between two columns.
 pub type SideAngle = Ranged<FiniteFloat, 0, 30>;
 
+/// A splay angle of a column around its home key.
+pub type SplayAngle = Ranged<FiniteFloat, -20, 20>;
+
 /// A 2-dimensional vector.
 #[derive(Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
 pub struct Vec2<T> {
@@ -65,7 +69,7 @@ impl<T: Show> Show for Vec2<T> {
 }
 
 /// A 3-dimensional vector.
-#[derive(Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Default, Deserialize, PartialEq, Eq, Hash)]
 pub struct Vec3<T> {
     /// The X component of the vector.
     pub x: T,
@@ -209,6 +213,45 @@ impl Show for PositiveFloat {
     }
 }
 
+/// A strictly positive size of a fabrication feature, adjusted in finer steps.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+#[serde(try_from = "f64")]
+pub struct FabricationSize(PositiveFloat);
+
+impl From<FabricationSize> for f64 {
+    fn from(size: FabricationSize) -> Self {
+        size.0.into()
+    }
+}
+
+impl TryFrom<f64> for FabricationSize {
+    type Error = Error;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        PositiveFloat::try_from(value).map(Self)
+    }
+}
+
+impl Show for FabricationSize {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut value = f64::from(*self);
+
+        let changed = ui
+            .add(
+                DragValue::new(&mut value)
+                    .range(FINE_DRAG_SPEED..=f64::MAX)
+                    .speed(FINE_DRAG_SPEED),
+            )
+            .changed();
+
+        *self = value
+            .try_into()
+            .expect("value should be finite and positive");
+
+        changed
+    }
+}
+
 /// A range constrained value.
 #[derive(Clone, Copy, Serialize, PartialEq, Eq, Hash)]
 pub struct Ranged<T, const LOWER: i8, const UPPER: i8>(T);
@@ -299,3 +342,127 @@ impl<const LOWER: i8, const UPPER: i8> Show for Ranged<i8, LOWER, UPPER> {
             .changed()
     }
 }
+
+/// A USB vendor or product ID, serialized as a hexadecimal string.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct UsbId(u16);
+
+impl From<UsbId> for u16 {
+    fn from(id: UsbId) -> Self {
+        id.0
+    }
+}
+
+impl From<u16> for UsbId {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<String> for UsbId {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+            .and_then(|digits| u16::from_str_radix(digits, 16).ok())
+            .map(Self)
+            .ok_or(Error::InvalidUsbId)
+    }
+}
+
+impl From<UsbId> for String {
+    fn from(id: UsbId) -> Self {
+        id.to_string()
+    }
+}
+
+impl Display for UsbId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        write!(f, "0x{:04X}", self.0)
+    }
+}
+
+impl Show for UsbId {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        ui.add(DragValue::new(&mut self.0).hexadecimal(4, false, true))
+            .changed()
+    }
+}
+
+/// A device version in the format major.minor.patch, serialized as a string.
+///
+/// The version is stored in binary-coded decimal by USB devices, limiting the major version to two
+/// and the minor and patch version to a single digit.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct DeviceVersion {
+    /// The major version.
+    pub major: u8,
+    /// The minor version.
+    pub minor: u8,
+    /// The patch version.
+    pub patch: u8,
+}
+
+impl TryFrom<String> for DeviceVersion {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts = value.split('.').map(|part| {
+            part.bytes()
+                .all(|byte| byte.is_ascii_digit())
+                .then(|| part.parse::<u8>().ok())
+                .flatten()
+        });
+
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (
+                Some(Some(major @ 0..=99)),
+                Some(Some(minor @ 0..=9)),
+                Some(Some(patch @ 0..=9)),
+                None,
+            ) => Ok(Self {
+                major,
+                minor,
+                patch,
+            }),
+            _ => Err(Error::InvalidDeviceVersion),
+        }
+    }
+}
+
+impl From<DeviceVersion> for String {
+    fn from(version: DeviceVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl Display for DeviceVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl Show for DeviceVersion {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            // Reverse order since widgets are placed right to left
+            changed |= ui
+                .add(DragValue::new(&mut self.patch).range(0..=9))
+                .changed();
+            changed |= ui
+                .add(DragValue::new(&mut self.minor).range(0..=9))
+                .changed();
+            changed |= ui
+                .add(DragValue::new(&mut self.major).range(0..=99))
+                .changed();
+        });
+
+        changed
+    }
+}
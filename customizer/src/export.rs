@@ -0,0 +1,125 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::PathBuf,
+};
+
+use config::{Config, PositiveFloat};
+use gui::{diagnostics, export_files_headless};
+
+use crate::Error;
+
+/// The usage of the export command.
+pub const USAGE: &str = "\
+Usage: customizer export <CONFIG> [--out <DIRECTORY>] [--resolution <RESOLUTION>]
+
+Arguments:
+  <CONFIG>                   The configuration file to export the model files for
+
+Options:
+  --out <DIRECTORY>          The directory to write the model files to [default: concavum]
+  --resolution <RESOLUTION>  The resolution used for meshing, overrides the configuration";
+
+/// Runs the export command using the given arguments.
+///
+/// Prints the usage instead if it was requested.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the arguments are invalid or the export failed.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let Some(arguments) = Arguments::parse(args)? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    let toml = read_to_string(&arguments.config_path)?;
+    let (mut config, migrations) = Config::from_toml(&toml)?;
+    for migration in migrations {
+        eprintln!("Upgraded the configuration from {migration}");
+    }
+    if let Some(resolution) = arguments.resolution {
+        config.keyboard.resolution = resolution;
+    }
+
+    for diagnostic in diagnostics(&config) {
+        eprintln!("{diagnostic}");
+    }
+
+    eprintln!("Meshing the model, this might take a while...");
+    let files = export_files_headless(&config)?;
+
+    for file in files {
+        let path = arguments.out_dir.join(&file.path);
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(&path, file.content)?;
+
+        eprintln!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// The arguments of the export command.
+struct Arguments {
+    config_path: PathBuf,
+    out_dir: PathBuf,
+    resolution: Option<PositiveFloat>,
+}
+
+impl Arguments {
+    /// Parses the arguments from the given iterator.
+    ///
+    /// Returns `None` if the usage was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, Error> {
+        let mut config_path = None;
+        let mut out_dir = PathBuf::from("concavum");
+        let mut resolution = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--out" => out_dir = value(&mut args, &arg)?.into(),
+                "--resolution" => {
+                    let value = value(&mut args, &arg)?;
+                    let parsed = value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(|value| PositiveFloat::try_from(value).ok())
+                        .ok_or_else(|| {
+                            invalid_arguments(&format!("invalid resolution `{value}`"))
+                        })?;
+
+                    resolution = Some(parsed);
+                }
+                "--help" | "-h" => return Ok(None),
+                _ if arg.starts_with('-') => {
+                    return Err(invalid_arguments(&format!("unknown option `{arg}`")))
+                }
+                _ if config_path.is_none() => config_path = Some(arg.into()),
+                _ => return Err(invalid_arguments(&format!("unexpected argument `{arg}`"))),
+            }
+        }
+
+        let config_path =
+            config_path.ok_or_else(|| invalid_arguments("missing configuration file"))?;
+
+        Ok(Some(Self {
+            config_path,
+            out_dir,
+            resolution,
+        }))
+    }
+}
+
+/// Returns the value following the given option.
+fn value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, Error> {
+    args.next()
+        .ok_or_else(|| invalid_arguments(&format!("missing value for `{option}`")))
+}
+
+/// Returns an invalid arguments error with the given message and the usage appended.
+fn invalid_arguments(message: &str) -> Error {
+    Error::InvalidArguments(format!("{message}\n\n{USAGE}"))
+}
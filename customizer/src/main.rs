@@ -1,4 +1,13 @@
 //! This file starts the customizer by creating a new window and running the render loop.
+//! Alternatively, the model files can be exported without a window using the `export` command.
+
+mod export;
+
+use std::{
+    env::args,
+    fmt::{Debug, Formatter, Result as FormattingResult},
+    io,
+};
 
 use viewer::{Window, WindowError};
 
@@ -6,10 +15,50 @@ use viewer::{Window, WindowError};
 ///
 /// # Errors
 ///
-/// Returns a `WindowError` if the window could not be created.
-pub fn main() -> Result<(), WindowError> {
-    let window = Window::try_new()?;
-    window.run_render_loop();
+/// Returns an `Error` if the window could not be created or the export failed.
+pub fn main() -> Result<(), Error> {
+    let mut args = args().skip(1);
+
+    match args.next().as_deref() {
+        None => {
+            let window = Window::try_new()?;
+            window.run_render_loop();
+        }
+        Some("export") => export::run(args)?,
+        Some(command) => {
+            return Err(Error::InvalidArguments(format!(
+                "unknown command `{command}`\n\n{}",
+                export::USAGE
+            )))
+        }
+    }
 
     Ok(())
 }
+
+/// An error type for errors which can occur when running the customizer.
+#[derive(thiserror::Error)]
+pub enum Error {
+    /// The window could not be created.
+    #[error(transparent)]
+    Window(#[from] WindowError),
+    /// The model files could not be exported.
+    #[error(transparent)]
+    Export(#[from] gui::Error),
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to load the configuration.
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    /// The command line arguments are invalid.
+    #[error("{0}")]
+    InvalidArguments(String),
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormattingResult {
+        // Errors returned from main are printed using `Debug`, so forward to `Display`
+        write!(f, "{self}")
+    }
+}
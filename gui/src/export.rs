@@ -0,0 +1,237 @@
+use std::io::{Cursor, Write};
+
+use bom::Bom;
+use config::Config;
+use fidget::render::CancelToken;
+use model::Model;
+use pcb::{InterfacePcb, MatrixPcb};
+use three_d::{CpuMesh, Indices, Positions};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    model::{Mesh, Meshes},
+    Error,
+};
+
+/// A single file of a model export.
+pub struct ExportFile {
+    /// The path of the file relative to the export root.
+    pub path: String,
+    /// The content of the file.
+    pub content: Vec<u8>,
+}
+
+impl ExportFile {
+    /// Creates a new export file with the given path and content.
+    fn new(path: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Meshes the model for the given config and returns all the model files.
+///
+/// This does not require a window and blocks until the meshing is finished.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the model could not be meshed or a file could not be created.
+pub fn export_files_headless(config: &Config) -> Result<Vec<ExportFile>, Error> {
+    let model = Model::from_config(config);
+    let meshes = model
+        .meshes(CancelToken::new())
+        .ok_or(Error::InvalidMesh("The model could not be meshed"))?;
+
+    export_files(config, meshes)
+}
+
+/// Returns all the model files for the given config and meshes.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the config could not be serialized or a mesh is invalid.
+pub fn export_files(config: &Config, meshes: Meshes) -> Result<Vec<ExportFile>, Error> {
+    let toml = toml::to_string(config)?;
+    let bom = Bom::from_config(config);
+
+    let mut case = Vec::new();
+    case.write_stl(meshes.case)?;
+    let mut bottom_plate = Vec::new();
+    bottom_plate.write_stl(meshes.bottom_plate)?;
+
+    let mut files = vec![
+        ExportFile::new("config.toml", toml),
+        ExportFile::new("bom.csv", bom.to_csv_file()),
+        ExportFile::new("kle.json", qmk::kle_json(config)),
+        ExportFile::new("case/case.stl", case),
+        ExportFile::new("case/bottom_plate.stl", bottom_plate),
+        ExportFile::new(
+            "case/bottom_plate_to_dxf.scad",
+            "projection(cut = false) import(\"bottom_plate.stl\");\n",
+        ),
+    ];
+
+    // Wired controllers are supported by QMK and wireless ones by ZMK
+    if let Some(qmk_files) = qmk::Files::from_config(config) {
+        let qmk_keyboard = format!("qmk/keyboards/{}", qmk_files.directory);
+
+        files.extend([
+            ExportFile::new("qmk/via.json", qmk_files.via_json),
+            ExportFile::new(format!("{qmk_keyboard}/readme.md"), qmk_files.readme_md),
+            ExportFile::new(format!("{qmk_keyboard}/rules.mk"), qmk_files.rules_mk),
+            ExportFile::new(format!("{qmk_keyboard}/config.h"), qmk_files.config_h),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keyboard.json"),
+                qmk_files.keyboard_json,
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/default/keymap.c"),
+                qmk_files.keymap_c.clone(),
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/via/keymap.c"),
+                qmk_files.keymap_c.clone(),
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/via/rules.mk"),
+                qmk_files.via_rules_mk,
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/vial/keymap.c"),
+                qmk_files.keymap_c,
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/vial/rules.mk"),
+                qmk_files.vial_rules_mk,
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/vial/config.h"),
+                qmk_files.vial_config_h,
+            ),
+            ExportFile::new(
+                format!("{qmk_keyboard}/keymaps/vial/vial.json"),
+                qmk_files.vial_json,
+            ),
+        ]);
+    }
+    if let Some(zmk_files) = zmk::Files::from_config(config) {
+        files.extend([
+            ExportFile::new("zmk/build.yaml", zmk_files.build_yaml),
+            ExportFile::new("zmk/config/west.yml", zmk_files.west_yml),
+            ExportFile::new("zmk/config/concavum.conf", zmk_files.conf),
+            ExportFile::new("zmk/config/concavum.keymap", zmk_files.keymap),
+            ExportFile::new(
+                "zmk/config/boards/shields/concavum/Kconfig.shield",
+                zmk_files.kconfig_shield,
+            ),
+            ExportFile::new(
+                "zmk/config/boards/shields/concavum/Kconfig.defconfig",
+                zmk_files.kconfig_defconfig,
+            ),
+            ExportFile::new(
+                "zmk/config/boards/shields/concavum/concavum.dtsi",
+                zmk_files.dtsi,
+            ),
+            ExportFile::new(
+                "zmk/config/boards/shields/concavum/concavum_left.overlay",
+                zmk_files.left_overlay,
+            ),
+            ExportFile::new(
+                "zmk/config/boards/shields/concavum/concavum_right.overlay",
+                zmk_files.right_overlay,
+            ),
+        ]);
+    }
+
+    files.extend(pcb_files(config));
+
+    Ok(files)
+}
+
+/// Returns the PCB files for the given config.
+fn pcb_files(config: &Config) -> Vec<ExportFile> {
+    let matrix_pcb = MatrixPcb::from_config(config);
+
+    let mut files = vec![
+        ExportFile::new("pcb/matrix_pcb.kicad_pcb", matrix_pcb.to_kicad_board()),
+        ExportFile::new("pcb/matrix_pcb.kicad_sch", matrix_pcb.to_kicad_schematic()),
+        ExportFile::new(
+            "pcb/matrix_panel.kicad_pcb",
+            matrix_pcb.to_panel().to_kicad_board(),
+        ),
+    ];
+    files.extend(
+        matrix_pcb.to_fabrication_files().into_iter().map(|file| {
+            ExportFile::new(format!("pcb/gerber/matrix_pcb-{}", file.name), file.content)
+        }),
+    );
+    files.push(ExportFile::new(
+        "pcb/interface_pcb.kicad_pcb",
+        InterfacePcb::from_config(config).to_kicad_board(),
+    ));
+
+    files
+}
+
+/// Bundles the given files in a ZIP archive.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the ZIP archive could not be created.
+pub fn to_zip_archive(files: &[ExportFile]) -> Result<Vec<u8>, Error> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    for file in files {
+        zip.start_file(file.path.as_str(), SimpleFileOptions::default())?;
+        zip.write_all(&file.content)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// A trait for writing a mesh to a binary STL.
+trait WriteStl: Write {
+    /// Writes the given mesh to a binary STL.
+    fn write_stl(&mut self, mesh: CpuMesh) -> Result<(), Error>;
+}
+
+impl<W: Write> WriteStl for W {
+    fn write_stl(&mut self, mesh: CpuMesh) -> Result<(), Error> {
+        const HEADER: &[u8] = b"This is a binary STL file exported by the concavum customizer";
+
+        if let CpuMesh {
+            positions: Positions::F32(vertices),
+            indices: Indices::U32(indices),
+            ..
+        } = mesh
+        {
+            let triangle_count = indices.len() / 3;
+
+            self.write_all(HEADER)?;
+            self.write_all(&[0; 80 - HEADER.len()])?;
+            #[allow(clippy::cast_possible_truncation)]
+            self.write_all(&(triangle_count as u32).to_le_bytes())?;
+
+            for triangle in indices.chunks_exact(3) {
+                let a = vertices[triangle[0] as usize];
+                let b = vertices[triangle[1] as usize];
+                let c = vertices[triangle[2] as usize];
+
+                let normal = (b - a).cross(c - a);
+
+                for vector in [normal, a, b, c] {
+                    self.write_all(&vector.x.to_le_bytes())?;
+                    self.write_all(&vector.y.to_le_bytes())?;
+                    self.write_all(&vector.z.to_le_bytes())?;
+                }
+                self.write_all(&[0; size_of::<u16>()])?;
+            }
+
+            Ok(())
+        } else {
+            Err(Error::InvalidMesh("The mesh representation is invalid"))
+        }
+    }
+}
@@ -1,30 +1,30 @@
 use std::{
     future::Future,
-    io::{Cursor, Write},
     sync::mpsc::{channel, Receiver, Sender},
 };
 
-use bom::Bom;
-use config::{Config, PositiveFloat};
-use pcb::MatrixPcb;
-use qmk::Files;
+use config::{Config, Migration, PositiveFloat};
 use rfd::AsyncFileDialog;
 use show::egui::{Align, Align2, Button, Context, Layout, RichText, Ui, Window};
-use three_d::{CpuMesh, Indices, Positions};
-use zip::{write::SimpleFileOptions, ZipWriter};
 
-use crate::{reload::ModelReloader, Error, Meshes};
+use crate::{
+    export::{export_files, to_zip_archive},
+    reload::ModelReloader,
+    Error, Meshes,
+};
 
-type Update = Result<Option<Config>, Error>;
+type Update = Result<Option<(Config, Vec<&'static Migration>)>, Error>;
 
 /// The recommended resolution for meshing.
 const RECOMMENDED_RESOLUTION: f64 = 0.2;
 
-/// A menu for loading/saving configuration and exporting model files.
+/// A menu for loading/saving configuration, exporting model files and exporting/importing
+/// keyboard-layout-editor layouts.
 pub struct FileMenu {
     sender: Sender<Update>,
     receiver: Receiver<Update>,
     error: String,
+    notice: String,
     export_popup_open: bool,
     export_pending: bool,
 }
@@ -39,6 +39,7 @@ impl FileMenu {
             sender,
             receiver,
             error: String::new(),
+            notice: String::new(),
             export_popup_open: false,
             export_pending: false,
         }
@@ -83,6 +84,27 @@ impl FileMenu {
                 {
                     self.spawn_local(load_config());
                 }
+                ui.menu_button("KLE", |ui| {
+                    if ui
+                        .button("Export")
+                        .on_hover_text("Exports the key layout as a keyboard-layout-editor file")
+                        .clicked()
+                    {
+                        self.spawn_local(export_kle(config.clone()));
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button("Import")
+                        .on_hover_text(
+                            "Imports the legends of a keyboard-layout-editor file \
+                            as the first keymap layer",
+                        )
+                        .clicked()
+                    {
+                        self.spawn_local(import_kle(config.clone()));
+                        ui.close_menu();
+                    }
+                });
             });
         });
 
@@ -96,12 +118,16 @@ impl FileMenu {
                 Ok(update) => {
                     self.error = String::new();
 
-                    if let Some(config_update) = update {
+                    if let Some((config_update, migrations)) = update {
+                        self.notice = migration_notice(&migrations);
                         *config = config_update;
                         return true;
                     }
                 }
-                Err(error) => self.error = error.to_string(),
+                Err(error) => {
+                    self.error = error.to_string();
+                    self.notice = String::new();
+                }
             }
         }
 
@@ -166,6 +192,11 @@ impl FileMenu {
         &self.error
     }
 
+    /// Returns the current notice to display.
+    pub fn notice(&self) -> &str {
+        &self.notice
+    }
+
     /// Exports the model from the given config.
     fn export_model(&mut self, config: &Config, model_reloader: &ModelReloader) {
         match model_reloader.cached_meshes(config) {
@@ -211,7 +242,24 @@ async fn load_config() -> Update {
 
     let string = String::from_utf8(bytes)?;
 
-    Ok(Some(toml::from_str(&string)?))
+    Ok(Some(Config::from_toml(&string)?))
+}
+
+/// Returns a notice listing the given migrations applied to a loaded config.
+fn migration_notice(migrations: &[&Migration]) -> String {
+    if migrations.is_empty() {
+        return String::new();
+    }
+
+    let migrations: Vec<_> = migrations
+        .iter()
+        .map(|migration| format!("- {migration}"))
+        .collect();
+
+    format!(
+        "The configuration was upgraded from an older version:\n{}",
+        migrations.join("\n")
+    )
 }
 
 /// Saves the config to a TOML file.
@@ -230,96 +278,51 @@ async fn save_config(config: Config) -> Update {
     Ok(None)
 }
 
-/// Exports all the model files in a ZIP archive.
-async fn export_model(config: Config, meshes: Meshes) -> Update {
-    let toml = toml::to_string(&config)?;
-    let bom = Bom::from_config(&config);
-    let matrix_pcb = MatrixPcb::from_config(&config);
-    let qmk_files = Files::from_config(&config);
-    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
-
-    zip.start_file("config.toml", SimpleFileOptions::default())?;
-    zip.write_all(toml.as_bytes())?;
-    zip.start_file("bom.csv", SimpleFileOptions::default())?;
-    zip.write_all(bom.to_csv_file().as_bytes())?;
-
-    zip.start_file("case/case.stl", SimpleFileOptions::default())?;
-    zip.write_stl(meshes.case)?;
-    zip.start_file("case/bottom_plate.stl", SimpleFileOptions::default())?;
-    zip.write_stl(meshes.bottom_plate)?;
-    zip.start_file(
-        "case/bottom_plate_to_dxf.scad",
-        SimpleFileOptions::default(),
-    )?;
-    zip.write_all(b"projection(cut = false) import(\"bottom_plate.stl\");\n")?;
-
-    zip.start_file("pcb/matrix_pcb.kicad_pcb", SimpleFileOptions::default())?;
-    zip.write_all(matrix_pcb.to_kicad_board().as_bytes())?;
-    zip.start_file("pcb/kikit_parameters.json", SimpleFileOptions::default())?;
-    zip.write_all(include_bytes!("kikit_parameters.json"))?;
-
-    zip.start_file("qmk/config.h", SimpleFileOptions::default())?;
-    zip.write_all(qmk_files.config_h.as_bytes())?;
-    zip.start_file("qmk/keyboard.json", SimpleFileOptions::default())?;
-    zip.write_all(qmk_files.keyboard_json.as_bytes())?;
-    zip.start_file("qmk/keymaps/default/keymap.c", SimpleFileOptions::default())?;
-    zip.write_all(qmk_files.keymap_c.as_bytes())?;
-
-    let data = zip.finish()?;
+/// Imports the legends of a keyboard-layout-editor JSON file into the keymap of the config.
+async fn import_kle(mut config: Config) -> Update {
+    let file = AsyncFileDialog::new()
+        .add_filter("json", &["json"])
+        .pick_file()
+        .await
+        .ok_or(Error::NoFileSelected)?;
+
+    let bytes = file.read().await;
+
+    let string = String::from_utf8(bytes)?;
+    config.keymap = qmk::import_kle(&config, &string)?;
+
+    Ok(Some((config, Vec::new())))
+}
+
+/// Exports the key layout as a keyboard-layout-editor JSON file.
+async fn export_kle(config: Config) -> Update {
+    let json = qmk::kle_json(&config);
 
     let file = AsyncFileDialog::new()
-        .add_filter("zip", &["zip"])
-        .set_file_name("concavum.zip")
+        .add_filter("json", &["json"])
+        .set_file_name("kle.json")
         .save_file()
         .await
         .ok_or(Error::NoFileSelected)?;
 
-    file.write(&data.into_inner()).await?;
+    file.write(json.as_bytes()).await?;
 
     Ok(None)
 }
 
-/// A trait for writing a mesh to a binary STL.
-trait WriteStl: Write {
-    /// Writes the given mesh to a binary STL.
-    fn write_stl(&mut self, mesh: CpuMesh) -> Result<(), Error>;
-}
+/// Exports all the model files in a ZIP archive.
+async fn export_model(config: Config, meshes: Meshes) -> Update {
+    let files = export_files(&config, meshes)?;
+    let data = to_zip_archive(&files)?;
 
-impl<W: Write> WriteStl for W {
-    fn write_stl(&mut self, mesh: CpuMesh) -> Result<(), Error> {
-        const HEADER: &[u8] = b"This is a binary STL file exported by the concavum customizer";
-
-        if let CpuMesh {
-            positions: Positions::F32(vertices),
-            indices: Indices::U32(indices),
-            ..
-        } = mesh
-        {
-            let triangle_count = indices.len() / 3;
-
-            self.write_all(HEADER)?;
-            self.write_all(&[0; 80 - HEADER.len()])?;
-            #[allow(clippy::cast_possible_truncation)]
-            self.write_all(&(triangle_count as u32).to_le_bytes())?;
-
-            for triangle in indices.chunks_exact(3) {
-                let a = vertices[triangle[0] as usize];
-                let b = vertices[triangle[1] as usize];
-                let c = vertices[triangle[2] as usize];
-
-                let normal = (b - a).cross(c - a);
-
-                for vector in [normal, a, b, c] {
-                    self.write_all(&vector.x.to_le_bytes())?;
-                    self.write_all(&vector.y.to_le_bytes())?;
-                    self.write_all(&vector.z.to_le_bytes())?;
-                }
-                self.write_all(&[0; size_of::<u16>()])?;
-            }
+    let file = AsyncFileDialog::new()
+        .add_filter("zip", &["zip"])
+        .set_file_name("concavum.zip")
+        .save_file()
+        .await
+        .ok_or(Error::NoFileSelected)?;
 
-            Ok(())
-        } else {
-            Err(Error::InvalidMesh("The mesh representation is invalid"))
-        }
-    }
+    file.write(&data).await?;
+
+    Ok(None)
 }
@@ -1,5 +1,6 @@
 //! The `gui` crate contains everything related to displaying the GUI for changing the configuration.
 
+mod export;
 mod file_menu;
 mod model;
 mod reload;
@@ -11,8 +12,10 @@ use std::{
     string::FromUtf8Error,
 };
 
+use ::model::{validate, Diagnostic, Severity};
 use config::Config;
 use file_menu::FileMenu;
+use pcb::{InterfacePcb, MatrixPcb};
 use show::{
     egui::{Color32, RichText},
     Show,
@@ -25,7 +28,8 @@ use zip::result::ZipError;
 
 use reload::ModelReloader;
 
-pub use model::{DisplaySettings, InstancedMesh, Meshes, Settings};
+pub use export::{export_files, export_files_headless, to_zip_archive, ExportFile};
+pub use model::{DisplaySettings, InstancedMesh, MatrixPcbSettings, Meshes, Settings};
 pub use update::{Update, Updater};
 
 /// A graphical user interface for changing the configuration.
@@ -34,6 +38,7 @@ pub struct Gui {
     config: Config,
     model_reloader: ModelReloader,
     file_menu: FileMenu,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Gui {
@@ -56,6 +61,7 @@ impl Gui {
             config,
             model_reloader,
             file_menu,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -93,7 +99,12 @@ impl Gui {
                             RichText::new(self.file_menu.error())
                                 .monospace()
                                 .color(Color32::LIGHT_RED),
-                        )
+                        );
+                        ui.label(
+                            RichText::new(self.file_menu.notice())
+                                .monospace()
+                                .color(Color32::LIGHT_YELLOW),
+                        );
                     });
 
                 changed |= self.file_menu.show_export_popup(
@@ -105,6 +116,26 @@ impl Gui {
                 if changed {
                     self.model_reloader.reload(&self.config);
                 }
+                if let Some(diagnostics) = self.model_reloader.take_diagnostics(&self.config) {
+                    self.diagnostics = diagnostics;
+                }
+
+                Area::new("diagnostics".into())
+                    .anchor(Align2::LEFT_BOTTOM, [MARGIN, -MARGIN])
+                    .show(context, |ui| {
+                        for diagnostic in &self.diagnostics {
+                            let color = match diagnostic.severity {
+                                Severity::Warning => Color32::LIGHT_YELLOW,
+                                Severity::Error => Color32::LIGHT_RED,
+                            };
+
+                            ui.label(
+                                RichText::new(diagnostic.to_string())
+                                    .monospace()
+                                    .color(color),
+                            );
+                        }
+                    });
 
                 if is_reloading {
                     Area::new("spinner".into())
@@ -127,6 +158,26 @@ impl Gui {
     }
 }
 
+/// Validates the given configuration and checks the design rules and connectivity of the
+/// resulting matrix and interface PCBs.
+///
+/// Returns the problems found, ordered by their severity with errors first.
+#[must_use]
+pub fn diagnostics(config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = validate(config);
+
+    let matrix_pcb = MatrixPcb::from_config(config);
+    diagnostics.extend(matrix_pcb.check_design_rules());
+    diagnostics.extend(matrix_pcb.check_connectivity());
+
+    let interface_pcb = InterfacePcb::from_config(config);
+    diagnostics.extend(interface_pcb.check_design_rules());
+    diagnostics.extend(interface_pcb.check_connectivity());
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity == Severity::Warning);
+
+    diagnostics
+}
+
 /// An error type for errors which can occur when interacting with the GUI.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -138,8 +189,10 @@ pub enum Error {
     IoError(#[from] io::Error),
     /// Failed to serialize to TOML.
     TomlSerialize(#[from] toml::ser::Error),
-    /// Failed to deserialize from TOML.
-    TomlDeserialize(#[from] toml::de::Error),
+    /// Failed to load the configuration.
+    Config(#[from] config::Error),
+    /// Failed to import a keyboard-layout-editor layout.
+    Kle(#[from] qmk::KleError),
     /// A mesh is invalid.
     InvalidMesh(&'static str),
     /// Failed to create the ZIP archive.
@@ -153,7 +206,8 @@ impl Display for Error {
             Error::IoError(inner) => inner.fmt(f),
             Error::InvalidUtf8(inner) => inner.fmt(f),
             Error::TomlSerialize(inner) => inner.fmt(f),
-            Error::TomlDeserialize(inner) => inner.fmt(f),
+            Error::Config(inner) => inner.fmt(f),
+            Error::Kle(inner) => inner.fmt(f),
             Error::InvalidMesh(inner) => inner.fmt(f),
             Error::ZipError(inner) => inner.fmt(f),
         }
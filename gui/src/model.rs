@@ -1,13 +1,10 @@
 use std::iter::once;
 
-use config::KeySize;
+use config::{KeySize, SwitchType};
 use fidget::render::CancelToken;
-use glam::{DAffine3, DMat4};
+use glam::{dvec3, DAffine3, DMat4, DVec3};
 use model::{
-    matrix_pcb::{
-        ClusterConnector, ColumnConnector, ColumnKeyConnectors, Segment, ThumbKeyConnectors,
-        CONNECTOR_WIDTH, THICKNESS,
-    },
+    matrix_pcb::{ClusterConnector, ColumnConnector, Dimensions, KeyConnector, MatrixPcb, Segment},
     Bounds, Mesh as ModelMesh, MeshSettings, Model,
 };
 use three_d::{CpuMesh, Indices, Mat4, Positions, Vec3};
@@ -17,16 +14,16 @@ pub use model::DisplaySettings;
 /// The settings for displaying a model.
 #[derive(Clone, Default)]
 pub struct Settings {
+    /// The type of the key switches.
+    pub switch_type: SwitchType,
     /// The positions of the finger keys.
     pub finger_key_positions: Vec<Mat4>,
     /// The settings for the thumb keys.
     pub thumb_key_settings: ThumbKeySettings,
     /// The positions of the interface PCBs.
     pub interface_pcb_positions: Vec<Mat4>,
-    /// The meshes of the matrix PCBs.
-    pub matrix_pcb_meshes: Vec<InstancedMesh>,
-    /// The positions of the FFC connector pad.
-    pub ffc_pad_positions: Vec<Mat4>,
+    /// The settings for the matrix PCBs.
+    pub matrix_pcb_settings: MatrixPcbSettings,
     /// The display settings of the model.
     pub display_settings: DisplaySettings,
     /// The positions of the illuminating point lights.
@@ -42,6 +39,11 @@ pub struct ThumbKeySettings {
     pub thumb_key_positions: Vec<Mat4>,
 }
 
+/// The size of the interface PCB mesh.
+const INTERFACE_PCB_MESH_SIZE: DVec3 = dvec3(36.0, 42.0, 1.6);
+/// The size of the matrix PCB pad mesh.
+const MATRIX_PCB_PAD_MESH_SIZE: DVec3 = dvec3(13.0, 16.0, 0.6);
+
 pub fn make_settings(model: &Model, config: &config::Config) -> Settings {
     let finger_key_positions = model
         .key_positions
@@ -59,32 +61,81 @@ pub fn make_settings(model: &Model, config: &config::Config) -> Settings {
         thumb_key_positions,
         key_size: config.thumb_cluster.key_size,
     };
+    // The interface PCB mesh is scaled to the size of the interface PCB of the controller
+    let interface_pcb_scale =
+        DAffine3::from_scale(model.keyboard.interface_pcb_size / INTERFACE_PCB_MESH_SIZE);
     let interface_pcb_positions =
-        mirrored_positions(&model.keyboard.interface_pcb_position).to_vec();
-
-    let matrix_pcb_meshes = model
-        .matrix_pcb
-        .column_key_connectors
-        .iter()
-        .map(Into::into)
-        .chain(once((&model.matrix_pcb.thumb_key_connectors).into()))
-        .chain(model.matrix_pcb.column_connectors.iter().map(Into::into))
-        .chain(once((&model.matrix_pcb.cluster_connector).into()))
-        .collect();
-    let ffc_pad_positions = mirrored_positions(&model.matrix_pcb.ffc_pad_position).to_vec();
+        mirrored_positions(&(model.keyboard.interface_pcb_position * interface_pcb_scale)).to_vec();
+    let matrix_pcb_settings = (&model.matrix_pcb).into();
     let light_positions = light_positions_from_bounds(model.keyboard.case.bounds());
 
     Settings {
+        switch_type: config.keyboard.switch_type,
         finger_key_positions,
         thumb_key_settings,
         interface_pcb_positions,
-        matrix_pcb_meshes,
-        ffc_pad_positions,
+        matrix_pcb_settings,
         display_settings: model.display_settings.clone(),
         light_positions,
     }
 }
 
+/// The settings for the matrix PCBs.
+#[derive(Clone, Default)]
+pub struct MatrixPcbSettings {
+    /// The meshes of the matrix PCBs.
+    pub meshes: Vec<InstancedMesh>,
+    /// The positions of the pads underneath the keys.
+    pub pad_positions: Vec<Mat4>,
+    /// The positions of the FFC connector pad.
+    pub ffc_pad_positions: Vec<Mat4>,
+}
+
+impl From<&MatrixPcb> for MatrixPcbSettings {
+    fn from(matrix_pcb: &MatrixPcb) -> Self {
+        let dimensions = matrix_pcb.dimensions;
+        let thumb_key_connectors = &matrix_pcb.thumb_key_connectors;
+        let meshes = matrix_pcb
+            .column_key_connectors
+            .iter()
+            .flat_map(|connectors| {
+                key_connector_meshes(&connectors.connectors, &connectors.positions, dimensions)
+            })
+            .chain(key_connector_meshes(
+                &thumb_key_connectors.connectors,
+                &thumb_key_connectors.positions,
+                dimensions,
+            ))
+            .chain(
+                matrix_pcb
+                    .column_connectors
+                    .iter()
+                    .map(|connector| column_connector_mesh(connector, dimensions)),
+            )
+            .chain(once(cluster_connector_mesh(
+                &matrix_pcb.cluster_connector,
+                dimensions,
+            )))
+            .collect();
+        // The pad mesh is scaled to the size of the pads of the switches
+        let pad_scale = DAffine3::from_scale(
+            dimensions.pad_size.extend(dimensions.thickness) / MATRIX_PCB_PAD_MESH_SIZE,
+        );
+        let pad_positions = matrix_pcb
+            .pad_positions
+            .iter()
+            .flat_map(|&position| mirrored_positions(&(position * pad_scale)))
+            .collect();
+        let ffc_pad_positions = mirrored_positions(&matrix_pcb.ffc_pad_position).to_vec();
+
+        Self {
+            meshes,
+            pad_positions,
+            ffc_pad_positions,
+        }
+    }
+}
+
 /// The meshes of the keyboard and bottom plate.
 #[derive(Clone)]
 pub struct Meshes {
@@ -183,66 +234,56 @@ pub struct InstancedMesh {
     pub transformations: Vec<Mat4>,
 }
 
-impl From<&ColumnKeyConnectors> for InstancedMesh {
-    fn from(connectors: &ColumnKeyConnectors) -> Self {
-        let mesh = segment_to_mesh(&connectors.connector);
-        let transformations = connectors
-            .positions
-            .iter()
-            .flat_map(|(left, right)| [left, right])
-            .flat_map(mirrored_positions)
-            .collect();
-
-        Self {
-            mesh,
-            transformations,
+/// Creates an instanced mesh with the given dimensions for a connector between two columns.
+fn column_connector_mesh(connector: &ColumnConnector, dimensions: Dimensions) -> InstancedMesh {
+    let (mesh, position) = match connector {
+        ColumnConnector::Normal(connector) => {
+            (segment_to_mesh(connector, dimensions), DAffine3::IDENTITY)
         }
-    }
-}
+        ColumnConnector::Side(connector) => {
+            (segment_to_mesh(connector, dimensions), connector.position)
+        }
+    };
 
-impl From<&ThumbKeyConnectors> for InstancedMesh {
-    fn from(connectors: &ThumbKeyConnectors) -> Self {
-        let mesh = segment_to_mesh(&connectors.connector);
-        let transformations = connectors
-            .positions
-            .iter()
-            .flat_map(|(left, right)| [left, right])
-            .flat_map(mirrored_positions)
-            .collect();
+    let transformations = mirrored_positions(&position).to_vec();
 
-        Self {
-            mesh,
-            transformations,
-        }
+    InstancedMesh {
+        mesh,
+        transformations,
     }
 }
 
-impl From<&ColumnConnector> for InstancedMesh {
-    fn from(connector: &ColumnConnector) -> Self {
-        let (mesh, position) = match connector {
-            ColumnConnector::Normal(connector) => (segment_to_mesh(connector), DAffine3::IDENTITY),
-            ColumnConnector::Side(connector) => (segment_to_mesh(connector), connector.position),
-        };
-
-        let transformations = mirrored_positions(&position).to_vec();
-
-        Self {
-            mesh,
-            transformations,
-        }
+/// Creates an instanced mesh with the given dimensions for the cluster connector.
+fn cluster_connector_mesh(
+    cluster_connector: &ClusterConnector,
+    dimensions: Dimensions,
+) -> InstancedMesh {
+    let mesh = segment_to_mesh(cluster_connector, dimensions);
+    let transformations = mirrored_positions(&DAffine3::IDENTITY).to_vec();
+
+    InstancedMesh {
+        mesh,
+        transformations,
     }
 }
 
-impl From<&ClusterConnector> for InstancedMesh {
-    fn from(cluster_connector: &ClusterConnector) -> Self {
-        let mesh = segment_to_mesh(cluster_connector);
-        let transformations = mirrored_positions(&DAffine3::IDENTITY).to_vec();
-
-        Self {
-            mesh,
-            transformations,
-        }
-    }
+/// Creates an instanced mesh with the given dimensions for each connector between two
+/// neighboring keys.
+fn key_connector_meshes<'a>(
+    connectors: &'a [KeyConnector],
+    positions: &'a [(DAffine3, DAffine3)],
+    dimensions: Dimensions,
+) -> impl Iterator<Item = InstancedMesh> + 'a {
+    connectors
+        .iter()
+        .zip(positions)
+        .map(move |(connector, (left, right))| InstancedMesh {
+            mesh: segment_to_mesh(connector, dimensions),
+            transformations: [left, right]
+                .into_iter()
+                .flat_map(mirrored_positions)
+                .collect(),
+        })
 }
 
 /// Creates two key positions mirrored along the YZ-plane given a single one.
@@ -284,8 +325,8 @@ macro_rules! face_indices {
     };
 }
 
-/// Converts a segment to a `CpuMesh`.
-fn segment_to_mesh(segment: &impl Segment) -> CpuMesh {
+/// Converts a segment with the given dimensions to a `CpuMesh`.
+fn segment_to_mesh(segment: &impl Segment, dimensions: Dimensions) -> CpuMesh {
     let positions = segment.positions();
     let indices = (0..(u32::try_from(positions.len()).expect("length should fit in u32") - 1))
         .flat_map(|segment_index| {
@@ -302,8 +343,8 @@ fn segment_to_mesh(segment: &impl Segment) -> CpuMesh {
         .into_iter()
         .flat_map(|position| {
             let center = position.translation;
-            let x = CONNECTOR_WIDTH / 2.0 * position.x_axis;
-            let z = THICKNESS / 2.0 * position.z_axis;
+            let x = dimensions.connector_width / 2.0 * position.x_axis;
+            let z = dimensions.thickness / 2.0 * position.z_axis;
 
             [
                 center - x - z,
@@ -14,10 +14,11 @@ use fidget::render::CancelToken;
 use wasm_bindgen::prelude::*;
 
 use config::Config;
-use model::Model;
+use model::{Diagnostic, Model};
 use web_time::Instant;
 
 use crate::{
+    diagnostics,
     model::{make_settings, Mesh, Meshes},
     update::{Update, Updater},
 };
@@ -38,6 +39,7 @@ pub struct ModelReloader {
     previous_config: Option<Config>,
     cancel_token: CancelToken,
     cache: Cache,
+    checked_diagnostics: CheckedDiagnostics,
 }
 
 /// A reload task to be performed in a separate thread.
@@ -45,18 +47,46 @@ struct ReloadTask {
     model: Model,
     config: Config,
     cancel_token: CancelToken,
+    /// Whether the meshes are already cached, so only the diagnostics have to be checked.
+    is_cached: bool,
 }
 
 /// A cache for already meshed configurations.
 type Cache = Arc<Mutex<HashMap<Config, Meshes>>>;
 
+/// The diagnostics of the last checked configuration, shared with the reload thread.
+#[derive(Clone, Default)]
+struct CheckedDiagnostics(Arc<Mutex<Option<(Config, Vec<Diagnostic>)>>>);
+
+impl CheckedDiagnostics {
+    /// Replaces the checked diagnostics with the ones of the given configuration.
+    fn set(&self, config: Config, diagnostics: Vec<Diagnostic>) {
+        *self.0.lock().expect("the lock should not be poisoned") = Some((config, diagnostics));
+    }
+
+    /// Takes the checked diagnostics if they belong to the given configuration.
+    fn take(&self, config: &Config) -> Option<Vec<Diagnostic>> {
+        self.0
+            .lock()
+            .expect("the lock should not be poisoned")
+            .take_if(|(checked_config, _)| checked_config == config)
+            .map(|(_, diagnostics)| diagnostics)
+    }
+}
+
 impl ModelReloader {
     /// Creates a new model reloader using the given updater.
     pub fn new(updater: Updater) -> Self {
         let (sender, receiver) = channel();
         let cache: Cache = Arc::default();
+        let checked_diagnostics = CheckedDiagnostics::default();
 
-        Self::spawn_reload_thread(receiver, updater.clone(), cache.clone());
+        Self::spawn_reload_thread(
+            receiver,
+            updater.clone(),
+            cache.clone(),
+            checked_diagnostics.clone(),
+        );
 
         Self {
             updater,
@@ -64,6 +94,7 @@ impl ModelReloader {
             previous_config: None,
             cancel_token: CancelToken::new(),
             cache,
+            checked_diagnostics,
         }
     }
 
@@ -83,45 +114,73 @@ impl ModelReloader {
         self.previous_config = Some(config.clone());
 
         let model = Model::from_config(config);
+        let cancel_token = CancelToken::new();
+        self.cancel_token = cancel_token.clone();
 
-        if let Some(meshes) = self.cached_meshes(config) {
+        let cached_meshes = self.cached_meshes(config);
+        let is_cached = cached_meshes.is_some();
+
+        if let Some(meshes) = cached_meshes {
             self.updater
                 .send_update(Update::New(make_settings(&model, config), meshes));
         } else {
-            let cancel_token = CancelToken::new();
-            self.cancel_token = cancel_token.clone();
-
             self.updater
                 .send_update(Update::Settings(make_settings(&model, config)));
-
-            let _ = self.sender.send(ReloadTask {
-                model,
-                config: config.clone(),
-                cancel_token,
-            });
         }
+
+        let _ = self.sender.send(ReloadTask {
+            model,
+            config: config.clone(),
+            cancel_token,
+            is_cached,
+        });
+    }
+
+    /// Takes the diagnostics of the given configuration if they have been checked since the last
+    /// call.
+    pub fn take_diagnostics(&self, config: &Config) -> Option<Vec<Diagnostic>> {
+        self.checked_diagnostics.take(config)
     }
 
     /// Returns the cached meshes corresponding to the given configuration.
     pub fn cached_meshes(&self, config: &Config) -> Option<Meshes> {
         self.cache
             .lock()
-            .expect("the lock should not be poisened")
+            .expect("the lock should not be poisoned")
             .get(config)
             .cloned()
     }
 
     /// Spawns a thread for handling the actual reloading.
-    fn spawn_reload_thread(receiver: Receiver<ReloadTask>, updater: Updater, cache: Cache) {
+    ///
+    /// The diagnostics are checked here as well after the final meshes, since building the matrix
+    /// PCB and checking its design rules takes too long for the GUI thread and should not delay
+    /// the meshes. Tasks superseded by newer ones are dropped before handling them.
+    fn spawn_reload_thread(
+        receiver: Receiver<ReloadTask>,
+        updater: Updater,
+        cache: Cache,
+        checked_diagnostics: CheckedDiagnostics,
+    ) {
         spawn(move || 'outer: loop {
-            let Ok(ReloadTask {
+            let Ok(mut task) = receiver.recv() else {
+                break;
+            };
+            while let Ok(newer_task) = receiver.try_recv() {
+                task = newer_task;
+            }
+
+            let ReloadTask {
                 model,
                 config,
                 cancel_token,
-            }) = receiver.recv()
-            else {
-                break;
-            };
+                is_cached,
+            } = task;
+
+            if is_cached {
+                checked_diagnostics.set(config.clone(), diagnostics(&config));
+                continue;
+            }
 
             let start = Instant::now();
 
@@ -146,12 +205,14 @@ impl ModelReloader {
 
             cache
                 .lock()
-                .expect("the lock should not be poisened")
-                .insert(config, meshes.clone());
+                .expect("the lock should not be poisoned")
+                .insert(config.clone(), meshes.clone());
 
             updater.send_update(Update::Meshes(meshes));
 
             info!("Reloaded model in {:?}", start.elapsed());
+
+            checked_diagnostics.set(config.clone(), diagnostics(&config));
         });
     }
 }
@@ -0,0 +1,199 @@
+//! The `matrix` crate implements the key matrix wiring shared by the PCBs and firmware.
+
+use std::ops::Range;
+
+use config::Config;
+
+/// The Pro Micro pins connected to the columns of the left half from the outside in.
+const LEFT_COLUMN_PINS: [u8; 6] = [8, 7, 6, 5, 4, 19];
+/// The Pro Micro pins connected to the rows of the left half, starting at the thumb key row.
+const LEFT_ROW_PINS: [u8; 6] = [9, 10, 16, 14, 15, 18];
+/// The Pro Micro pins connected to the columns of the right half from the inside out.
+const RIGHT_COLUMN_PINS: [u8; 6] = [4, 19, 18, 15, 14, 16];
+/// The Pro Micro pins connected to the rows of the right half, starting at the thumb key row.
+const RIGHT_ROW_PINS: [u8; 6] = [10, 9, 8, 7, 6, 5];
+
+/// The names of the RP2040-Zero pins in the order of the Pro Micro pins they replace.
+///
+/// The RP2040-Zero does not share the Pro Micro footprint, so this is the assignment its interface
+/// PCB is wired with.
+pub const RP2040_ZERO_PINS: [(u8, &str); 18] = [
+    (0, "GP1"),
+    (1, "GP2"),
+    (2, "GP29"),
+    (3, "GP28"),
+    (4, "GP11"),
+    (5, "GP12"),
+    (6, "GP26"),
+    (7, "GP13"),
+    (8, "GP15"),
+    (9, "GP14"),
+    (10, "GP5"),
+    (14, "GP6"),
+    (15, "GP9"),
+    (16, "GP10"),
+    (18, "GP7"),
+    (19, "GP8"),
+    (20, "GP0"),
+    (21, "GP27"),
+];
+
+/// A half of the keyboard.
+#[derive(Clone, Copy)]
+pub enum Side {
+    /// The left half.
+    Left,
+    /// The right half.
+    Right,
+}
+
+/// The key matrix of a single half and its wiring to the controller.
+///
+/// The matrix of each half consists of the thumb key row followed by the finger key rows from the
+/// bottom up, with the thumb keys and finger keys sharing the columns.
+pub struct Matrix {
+    column_rows: Vec<Range<usize>>,
+    rows: usize,
+    thumb_keys: usize,
+}
+
+impl Matrix {
+    /// Creates the key matrix from the given configuration.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        let matrix_rows = config.finger_cluster.matrix_rows();
+        #[allow(clippy::cast_sign_loss)]
+        let rows = i8::from(matrix_rows.count) as usize;
+        #[allow(clippy::cast_sign_loss)]
+        let column_rows = config
+            .finger_cluster
+            .column_rows()
+            .into_iter()
+            .map(|column_rows| {
+                let first_row =
+                    i8::from(matrix_rows.home_row_index) - i8::from(column_rows.home_row_index);
+                let last_row = first_row + i8::from(column_rows.count);
+
+                first_row as usize..last_row as usize
+            })
+            .collect();
+        #[allow(clippy::cast_sign_loss)]
+        let thumb_keys = i8::from(config.thumb_cluster.keys) as usize;
+
+        Self {
+            column_rows,
+            rows,
+            thumb_keys,
+        }
+    }
+
+    /// Returns the finger key rows containing a key for each column, starting at the bottom row
+    /// and the innermost column.
+    #[must_use]
+    pub fn column_rows(&self) -> &[Range<usize>] {
+        &self.column_rows
+    }
+
+    /// Returns the number of finger key columns.
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.column_rows.len()
+    }
+
+    /// Returns the number of finger key rows.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of thumb keys.
+    #[must_use]
+    pub fn thumb_keys(&self) -> usize {
+        self.thumb_keys
+    }
+
+    /// Returns the number of matrix columns of a single half.
+    #[must_use]
+    pub fn column_count(&self) -> usize {
+        self.columns().max(self.thumb_keys)
+    }
+
+    /// Returns the number of matrix rows of a single half, including the thumb key row.
+    #[must_use]
+    pub fn row_count(&self) -> usize {
+        self.rows + 1
+    }
+
+    /// Returns true if the given column contains a key in the given row.
+    ///
+    /// The columns are indexed from the inside out and the rows from the bottom up.
+    #[must_use]
+    pub fn has_key(&self, column: usize, row: usize) -> bool {
+        self.column_rows[column].contains(&row)
+    }
+
+    /// Returns the row and column of the given finger key in the matrix of its half.
+    ///
+    /// The columns are indexed from the inside out and the rows from the bottom up.
+    #[must_use]
+    pub fn finger_key_position(&self, side: Side, column: usize, row: usize) -> (usize, usize) {
+        let (column_offset, _) = self.column_offsets();
+
+        let matrix_column = match side {
+            Side::Left => column_offset + self.columns() - 1 - column,
+            Side::Right => column_offset + column,
+        };
+
+        (row + 1, matrix_column)
+    }
+
+    /// Returns the row and column of the given thumb key in the matrix of its half.
+    ///
+    /// The thumb keys are indexed from the inside out.
+    #[must_use]
+    pub fn thumb_key_position(&self, side: Side, index: usize) -> (usize, usize) {
+        let (column_offset, thumb_key_offset) = self.column_offsets();
+
+        let matrix_column = match side {
+            Side::Left => thumb_key_offset + self.thumb_keys - 1 - index,
+            Side::Right => column_offset + index,
+        };
+
+        (0, matrix_column)
+    }
+
+    /// Returns the Pro Micro pins of the matrix columns and rows of the given side.
+    ///
+    /// Boards with a different pinout use the pins at the same positions, the RP2040-Zero uses the
+    /// pins given by [`RP2040_ZERO_PINS`].
+    #[must_use]
+    pub fn pins(&self, side: Side) -> (Vec<u8>, Vec<u8>) {
+        let column_count = self.column_count();
+        let row_count = self.row_count();
+
+        match side {
+            Side::Left => (
+                LEFT_COLUMN_PINS[LEFT_COLUMN_PINS.len() - column_count..].to_vec(),
+                LEFT_ROW_PINS[..row_count].to_vec(),
+            ),
+            Side::Right => (
+                RIGHT_COLUMN_PINS[..column_count].to_vec(),
+                RIGHT_ROW_PINS[..row_count].to_vec(),
+            ),
+        }
+    }
+
+    /// Returns the matrix column offsets of the finger and thumb keys.
+    ///
+    /// The outermost columns of the left half and the innermost ones of the right half are left
+    /// out if there are less finger or thumb keys than matrix columns.
+    fn column_offsets(&self) -> (usize, usize) {
+        let columns = self.columns();
+
+        if columns >= self.thumb_keys {
+            (0, columns - self.thumb_keys)
+        } else {
+            (self.thumb_keys - columns, 0)
+        }
+    }
+}
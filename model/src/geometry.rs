@@ -1,4 +1,4 @@
-use glam::{dvec2, dvec3, DMat2, DMat3, DVec2, DVec3};
+use glam::{dvec2, dvec3, DAffine3, DMat2, DMat3, DVec2, DVec3};
 use nalgebra::Matrix3;
 
 /// Returns a vector along the X-axis with the given value of `x`.
@@ -108,6 +108,94 @@ impl Project<Plane> for DVec3 {
     }
 }
 
+/// A box with arbitrary orientation in 3D space.
+pub struct OrientedBox {
+    center: DVec3,
+    axes: [DVec3; 3],
+    half_size: DVec3,
+}
+
+impl OrientedBox {
+    /// Creates a box from the given position, the offset of its center and its size.
+    ///
+    /// The offset and size are given in the coordinate system of the position.
+    pub fn new(position: DAffine3, center_offset: DVec3, size: DVec3) -> Self {
+        Self {
+            center: position.transform_point3(center_offset),
+            axes: [
+                position.x_axis.normalize(),
+                position.y_axis.normalize(),
+                position.z_axis.normalize(),
+            ],
+            half_size: size / 2.0,
+        }
+    }
+
+    /// Returns true if `self` and `other` overlap by more than the given tolerance.
+    ///
+    /// Uses the separating axis theorem to check for the overlap.
+    pub fn overlaps(&self, other: &OrientedBox, tolerance: f64) -> bool {
+        let distance = other.center - self.center;
+
+        let cross_axes = self
+            .axes
+            .iter()
+            .flat_map(|&a| other.axes.iter().map(move |&b| a.cross(b)))
+            .filter(|axis| axis.length_squared() > 1e-12)
+            .map(DVec3::normalize);
+
+        !self
+            .axes
+            .into_iter()
+            .chain(other.axes)
+            .chain(cross_axes)
+            .any(|axis| {
+                distance.dot(axis).abs() + tolerance
+                    >= self.projected_radius(axis) + other.projected_radius(axis)
+            })
+    }
+
+    /// Returns the radius of the projection of `self` onto the given axis.
+    fn projected_radius(&self, axis: DVec3) -> f64 {
+        self.axes
+            .iter()
+            .zip(self.half_size.to_array())
+            .map(|(box_axis, half_size)| half_size * box_axis.dot(axis).abs())
+            .sum()
+    }
+
+    /// Returns the corner points of the box.
+    pub fn corners(&self) -> impl Iterator<Item = DVec3> + '_ {
+        [-1.0, 1.0].into_iter().flat_map(move |x| {
+            [-1.0, 1.0].into_iter().flat_map(move |y| {
+                [-1.0, 1.0].into_iter().map(move |z| {
+                    self.center
+                        + x * self.half_size.x * self.axes[0]
+                        + y * self.half_size.y * self.axes[1]
+                        + z * self.half_size.z * self.axes[2]
+                })
+            })
+        })
+    }
+}
+
+/// Returns true if the point is inside the polygon given by its vertices.
+pub fn polygon_contains(vertices: &[DVec2], point: DVec2) -> bool {
+    let mut inside = false;
+
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
 /// Returns true if the triangle given by `p1`, `p2` and `p3` is counterclockwise or collinear.
 pub fn counterclockwise_or_collinear(p1: DVec2, p2: DVec2, p3: DVec2) -> bool {
     (p2.x - p1.x) * (p3.y - p1.y) - (p2.y - p1.y) * (p3.x - p1.x) >= 0.0
@@ -1,30 +1,45 @@
 use std::f64::consts::{FRAC_PI_2, PI};
 
+use config::Controller;
 use fidget::context::Tree;
 use glam::{dvec2, dvec3, DAffine3, DMat2, DMat3, DVec2, DVec3, Vec3Swizzles};
 
 use crate::{
-    geometry::{rotate_90_degrees, vec_y, vec_z, Tangent},
+    geometry::{rotate_90_degrees, vec_y, vec_z, OrientedBox, Tangent},
     keyboard::InsertHolder,
     primitives::{BoxShape, Circle, Csg, IntoTree, Rectangle, Transforms},
 };
 
-/// A PCB containing the interfaces to the outside (USB and TRRS) and inside (FFC).
+/// A PCB containing the controller and the interfaces to the outside (USB and TRRS) and inside
+/// (FFC).
 pub struct InterfacePcb {
     /// The position of the interface PCB.
     pub position: DAffine3,
+    /// The size of the interface PCB.
+    pub size: DVec3,
+    ports: Ports,
 }
 
 impl InterfacePcb {
-    const SIZE: DVec3 = dvec3(36.0, 42.0, 1.6);
     const HOLDER_THICKNESS: f64 = 1.0;
     const TOLERANCE: f64 = 0.1;
 
-    /// Creates a new interface PCB from the given insert holder and outline points.
-    pub fn new(insert_holder: &InsertHolder, points: &[DVec2], outline_offset: f64) -> Self {
+    /// Creates a new interface PCB for the given controller from the given insert holder and
+    /// outline points.
+    pub fn new(
+        controller: Controller,
+        insert_holder: &InsertHolder,
+        points: &[DVec2],
+        outline_offset: f64,
+    ) -> Self {
+        let size = Self::size(controller);
         let tangent = insert_holder.tangent();
-        let offset =
-            Self::offset_along_tangent(tangent, &points[..=insert_holder.index()], outline_offset);
+        let offset = Self::offset_along_tangent(
+            tangent,
+            &points[..=insert_holder.index()],
+            outline_offset,
+            size.x,
+        );
 
         let Tangent {
             point,
@@ -39,7 +54,36 @@ impl InterfacePcb {
         let position =
             DAffine3::from_mat3_translation(DMat3::from_mat2(rotation_matrix), translation);
 
-        Self { position }
+        Self {
+            position,
+            size,
+            ports: controller.into(),
+        }
+    }
+
+    /// Returns a box enclosing the interface PCB and the components on top of it.
+    pub fn bounding_box(&self) -> OrientedBox {
+        // The USB port and TRRS jack are the highest components
+        const COMPONENT_HEIGHT: f64 = 5.0;
+
+        let height = self.size.z + COMPONENT_HEIGHT;
+
+        OrientedBox::new(
+            self.position,
+            dvec3(self.size.x / 2.0, -self.size.y / 2.0, height / 2.0),
+            self.size.xy().extend(height),
+        )
+    }
+
+    /// Returns the size of the interface PCB for the given controller.
+    fn size(controller: Controller) -> DVec3 {
+        match controller {
+            // The Pro Micro footprint is shared by all of these
+            Controller::Kb2040 | Controller::ProMicro | Controller::NiceNano => {
+                dvec3(36.0, 42.0, 1.6)
+            }
+            Controller::Rp2040Zero => dvec3(30.0, 36.0, 1.6),
+        }
     }
 
     /// Returns the holder for the interface PCB.
@@ -48,18 +92,18 @@ impl InterfacePcb {
         const RETENTION_CLIP_WIDTH: f64 = 7.0;
         const RETENTION_CLIP_DEPTH: f64 = 0.5;
 
-        let y_offset = -Self::SIZE.y / 2.0 - WIDTH - Self::TOLERANCE;
-        let z_offset = (Self::SIZE.z - Self::HOLDER_THICKNESS) / 2.0;
+        let y_offset = -self.size.y / 2.0 - WIDTH - Self::TOLERANCE;
+        let z_offset = (self.size.z - Self::HOLDER_THICKNESS) / 2.0;
 
         let holder_size = dvec3(
-            Self::SIZE.x + 2.0 * (WIDTH + Self::TOLERANCE),
+            self.size.x + 2.0 * (WIDTH + Self::TOLERANCE),
             bounds_diameter,
-            Self::SIZE.z + Self::HOLDER_THICKNESS,
+            self.size.z + Self::HOLDER_THICKNESS,
         );
         let pcb_cutout_size =
-            (Self::SIZE.xy() + DVec2::splat(2.0 * Self::TOLERANCE)).extend(bounds_diameter);
+            (self.size.xy() + DVec2::splat(2.0 * Self::TOLERANCE)).extend(bounds_diameter);
         let bottom_cutout_size =
-            (Self::SIZE.xy() - dvec2(2.0 * WIDTH, WIDTH - Self::TOLERANCE)).extend(bounds_diameter);
+            (self.size.xy() - dvec2(2.0 * WIDTH, WIDTH - Self::TOLERANCE)).extend(bounds_diameter);
 
         let holder = BoxShape::new(holder_size).into_tree().translate(dvec3(
             0.0,
@@ -69,15 +113,15 @@ impl InterfacePcb {
         let retention_clip = Circle::new(WIDTH / 2.0 + RETENTION_CLIP_DEPTH)
             .into_tree()
             .extrude(
-                Self::SIZE.x / 2.0 - RETENTION_CLIP_WIDTH,
-                Self::SIZE.x / 2.0 + WIDTH + Self::TOLERANCE,
+                self.size.x / 2.0 - RETENTION_CLIP_WIDTH,
+                self.size.x / 2.0 + WIDTH + Self::TOLERANCE,
             )
             .affine(DAffine3 {
                 matrix3: DMat3::from_rotation_y(FRAC_PI_2),
                 translation: dvec3(
                     0.0,
                     y_offset + WIDTH / 2.0,
-                    Self::SIZE.z + (RETENTION_CLIP_DEPTH * (WIDTH + RETENTION_CLIP_DEPTH)).sqrt(),
+                    self.size.z + (RETENTION_CLIP_DEPTH * (WIDTH + RETENTION_CLIP_DEPTH)).sqrt(),
                 ),
             })
             .remap_xyz(Tree::x().abs(), Tree::y(), Tree::z());
@@ -87,7 +131,7 @@ impl InterfacePcb {
         let bottom_cutout = BoxShape::new(bottom_cutout_size)
             .into_tree()
             .translate(vec_y(f64::midpoint(WIDTH, Self::TOLERANCE)));
-        let translation = dvec3(Self::SIZE.x / 2.0, -Self::SIZE.y / 2.0, 0.0);
+        let translation = dvec3(self.size.x / 2.0, -self.size.y / 2.0, 0.0);
 
         holder
             .difference(pcb_cutout.union(bottom_cutout))
@@ -95,43 +139,52 @@ impl InterfacePcb {
             .affine(self.position * DAffine3::from_translation(translation))
     }
 
-    /// Returns the cutouts required for the USB and TRRS ports for the given side.
+    /// Returns the cutouts required for the USB port and the TRRS jack or power switch.
     pub fn cutouts(&self, bounds_diameter: f64) -> Tree {
         const USB_SIZE: DVec2 = dvec2(9.0, 3.2);
         const USB_RADIUS: f64 = 1.1;
-        const USB_OFFSET: DVec3 = dvec3(24.9, 0.0, 3.2);
         const JACK_RADIUS: f64 = 2.5;
-        const JACK_OFFSET: DVec3 = dvec3(5.4, 0.0, 2.45);
-
-        let jack_cutout = Circle::new(JACK_RADIUS + Self::TOLERANCE)
-            .into_tree()
-            .extrude(-Self::SIZE.y / 2.0, bounds_diameter);
+        const POWER_SWITCH_SIZE: DVec2 = dvec2(6.0, 3.0);
+        const POWER_SWITCH_RADIUS: f64 = 0.5;
 
         let rotation_x = DAffine3::from_rotation_x(-PI / 2.0);
-        let height_offset = vec_z(Self::SIZE.z);
+        let height_offset = vec_z(self.size.z);
+        let cutout = |shape: Tree, offset: DVec3| {
+            shape.extrude(-self.size.y / 2.0, bounds_diameter).affine(
+                self.position * DAffine3::from_translation(offset + height_offset) * rotation_x,
+            )
+        };
 
         let usb_cutout = Rectangle::new(USB_SIZE - DVec2::splat(2.0 * USB_RADIUS))
             .into_tree()
-            .offset(USB_RADIUS + Self::TOLERANCE)
-            .extrude(-Self::SIZE.y / 2.0, bounds_diameter);
-
-        let usb_translation = DAffine3::from_translation(USB_OFFSET + height_offset);
-        let jack_translation = DAffine3::from_translation(JACK_OFFSET + height_offset);
+            .offset(USB_RADIUS + Self::TOLERANCE);
+        let side_cutout = match self.ports.side_port {
+            SidePort::Jack => Circle::new(JACK_RADIUS + Self::TOLERANCE).into_tree(),
+            SidePort::PowerSwitch => {
+                Rectangle::new(POWER_SWITCH_SIZE - DVec2::splat(2.0 * POWER_SWITCH_RADIUS))
+                    .into_tree()
+                    .offset(POWER_SWITCH_RADIUS + Self::TOLERANCE)
+            }
+        };
 
-        usb_cutout
-            .affine(self.position * usb_translation * rotation_x)
-            .union(jack_cutout.affine(self.position * jack_translation * rotation_x))
+        cutout(usb_cutout, self.ports.usb_offset)
+            .union(cutout(side_cutout, self.ports.side_port_offset))
     }
 
     /// Calculates the offset of the interface PCB along the tangent direction to the tangent point.
-    fn offset_along_tangent(tangent: Tangent, points: &[DVec2], outline_offset: f64) -> f64 {
+    fn offset_along_tangent(
+        tangent: Tangent,
+        points: &[DVec2],
+        outline_offset: f64,
+        pcb_width: f64,
+    ) -> f64 {
         let Tangent {
             point,
             direction,
             normal,
         } = tangent;
         let mut offset = f64::INFINITY;
-        let width = Self::SIZE.x + 2.0 * Self::TOLERANCE;
+        let width = pcb_width + 2.0 * Self::TOLERANCE;
 
         for window in points.windows(2).rev() {
             let left_point = window[1];
@@ -163,3 +216,46 @@ impl InterfacePcb {
         offset
     }
 }
+
+/// The ports of an interface PCB on the edge facing outwards.
+struct Ports {
+    /// The offset of the center of the USB port from the outer corner of the interface PCB.
+    usb_offset: DVec3,
+    /// The port next to the USB port.
+    side_port: SidePort,
+    /// The offset of the center of the side port from the outer corner of the interface PCB.
+    side_port_offset: DVec3,
+}
+
+impl From<Controller> for Ports {
+    fn from(controller: Controller) -> Self {
+        const JACK_OFFSET: DVec3 = dvec3(5.4, 0.0, 2.45);
+
+        match controller {
+            Controller::Kb2040 | Controller::ProMicro => Self {
+                usb_offset: dvec3(24.9, 0.0, 3.2),
+                side_port: SidePort::Jack,
+                side_port_offset: JACK_OFFSET,
+            },
+            // Wireless halves do not need a TRRS jack but a switch for disconnecting the battery
+            Controller::NiceNano => Self {
+                usb_offset: dvec3(24.9, 0.0, 3.2),
+                side_port: SidePort::PowerSwitch,
+                side_port_offset: dvec3(5.4, 0.0, 1.5),
+            },
+            Controller::Rp2040Zero => Self {
+                usb_offset: dvec3(20.6, 0.0, 2.8),
+                side_port: SidePort::Jack,
+                side_port_offset: JACK_OFFSET,
+            },
+        }
+    }
+}
+
+/// A port next to the USB port.
+enum SidePort {
+    /// A TRRS jack for connecting the halves.
+    Jack,
+    /// A slide switch for turning the power off.
+    PowerSwitch,
+}
@@ -1,6 +1,6 @@
 use std::{
     iter::once,
-    ops::{Deref, Mul},
+    ops::{Deref, DerefMut, Mul},
 };
 
 use config::{ColumnConfig, ColumnType as ConfigColumnType, FingerCluster};
@@ -13,10 +13,13 @@ use crate::{
 };
 
 /// The positions of a column of finger keys.
+#[allow(clippy::struct_field_names)]
 pub struct Column {
     keys: Vec<DAffine3>,
     /// The type of the column.
     pub column_type: ColumnType,
+    /// The index of the home row within the column.
+    pub home_row_index: usize,
 }
 
 /// The type of a finger key column.
@@ -38,9 +41,18 @@ impl From<ConfigColumnType> for ColumnType {
 }
 
 impl Column {
-    /// Creates a new column given the contained keys and a column type.
-    pub fn new(keys: Vec<DAffine3>, column_type: ColumnType) -> Self {
-        Self { keys, column_type }
+    /// Creates a new column given the contained keys, a column type and the home row index.
+    pub fn new(keys: Vec<DAffine3>, column_type: ColumnType, home_row_index: usize) -> Self {
+        Self {
+            keys,
+            column_type,
+            home_row_index,
+        }
+    }
+
+    /// Returns the position of the home row key of the column.
+    pub fn home(&self) -> DAffine3 {
+        self.keys[self.home_row_index]
     }
 
     /// Returns the first finger key of the column.
@@ -62,6 +74,12 @@ impl Deref for Column {
     }
 }
 
+impl DerefMut for Column {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.keys
+    }
+}
+
 impl Mul<&Column> for DAffine3 {
     type Output = Column;
 
@@ -71,6 +89,7 @@ impl Mul<&Column> for DAffine3 {
         Column {
             keys,
             column_type: column.column_type,
+            home_row_index: column.home_row_index,
         }
     }
 }
@@ -80,7 +99,7 @@ pub struct Columns {
     inner: Vec<Column>,
     /// The clearances between neighboring finger keys.
     pub key_clearance: DVec2,
-    /// The index of the home row.
+    /// The index of the home row within the key matrix spanning the rows of all columns.
     pub home_row_index: i8,
 }
 
@@ -93,12 +112,13 @@ impl Columns {
             .first()
             .map(|column_config| column_config.column_type.side())
             .unwrap_or_default();
-        let home_row_index = config.home_row_index.into();
+        let home_row_index = config.matrix_rows().home_row_index.into();
 
         let inner = column_configs
             .into_iter()
+            .zip(config.column_rows())
             .enumerate()
-            .map(|(i, column_config)| {
+            .map(|(i, (column_config, rows))| {
                 #[allow(clippy::cast_precision_loss)]
                 let index = i as f64 - first_side;
                 let ColumnConfig {
@@ -106,8 +126,10 @@ impl Columns {
                     curvature_angle,
                     offset,
                     side_angle,
+                    splay_angle,
                 } = column_config;
                 let side = column_type.side();
+                let column_home_row_index = i8::from(rows.home_row_index);
 
                 let side_angle = side_angle.to_radians();
                 let side_angle_tan = side_angle.tan();
@@ -125,15 +147,22 @@ impl Columns {
                     )
                 };
 
+                // Side columns are splayed around the home key of their neighboring normal column
+                let pivot = dvec3(key_distance.x * (index + side), offset.x, 0.0);
+                let splay_transform = DAffine3::from_translation(pivot)
+                    * DAffine3::from_rotation_z(splay_angle.to_radians())
+                    * DAffine3::from_translation(-pivot);
+
                 let translation = dvec3(x, offset.x, offset.y + z_offset);
-                let column_transform = DAffine3::from_translation(translation)
+                let column_transform = splay_transform
+                    * DAffine3::from_translation(translation)
                     * DAffine3::from_rotation_y(side * side_angle);
 
                 let curvature_angle = curvature_angle.to_radians();
                 let keys = if curvature_angle == 0.0 {
-                    (0..config.rows.into())
+                    (0..rows.count.into())
                         .map(|j| {
-                            let y = key_distance.y * f64::from(j - home_row_index);
+                            let y = key_distance.y * f64::from(j - column_home_row_index);
                             column_transform * DAffine3::from_translation(vec_y(y))
                         })
                         .collect()
@@ -141,9 +170,10 @@ impl Columns {
                     let keycap_radius = key_distance.y / 2.0 / (curvature_angle / 2.0).tan();
                     let curvature_radius = keycap_radius + CURVATURE_HEIGHT;
 
-                    (0..config.rows.into())
+                    (0..rows.count.into())
                         .map(|j| {
-                            let total_angle = curvature_angle * f64::from(j - home_row_index);
+                            let total_angle =
+                                curvature_angle * f64::from(j - column_home_row_index);
                             let (sin, rcos) = (total_angle.sin(), 1.0 - total_angle.cos());
 
                             let x = -side
@@ -160,7 +190,8 @@ impl Columns {
                         .collect()
                 };
 
-                Column::new(keys, column_type.into())
+                #[allow(clippy::cast_sign_loss)]
+                Column::new(keys, column_type.into(), column_home_row_index as usize)
             })
             .collect();
 
@@ -304,6 +335,12 @@ impl Deref for Columns {
     }
 }
 
+impl DerefMut for Columns {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 impl Mul<Columns> for DAffine3 {
     type Output = Columns;
 
@@ -3,8 +3,8 @@ mod thumb_keys;
 
 use std::ops::Mul;
 
-use config::Config;
-use glam::{dvec3, DAffine3, DVec2};
+use config::{Config, KeyIndex, KeyOverride};
+use glam::{dvec3, DAffine3, DQuat, DVec2, DVec3, EulerRot};
 
 use crate::util::bounds_from_outline_points_and_height;
 
@@ -29,16 +29,10 @@ impl KeyPositions {
         const CENTER_OFFSET: f64 = 10.0;
         const Z_OFFSET: f64 = 12.0;
 
-        let columns = Columns::from_config(&config.finger_cluster);
-        let thumb_keys = ThumbKeys::from_config(&config.thumb_cluster);
-
         let tilting_angle: DVec2 = config.keyboard.tilting_angle.into();
         let tilted_positions = (DAffine3::from_rotation_y(tilting_angle.y.to_radians())
             * DAffine3::from_rotation_x(tilting_angle.x.to_radians()))
-            * Self {
-                columns,
-                thumb_keys,
-            };
+            * Self::untilted_from_config(config);
 
         let z_offset = Z_OFFSET
             - tilted_positions
@@ -62,6 +56,46 @@ impl KeyPositions {
         DAffine3::from_translation(dvec3(CENTER_OFFSET - bounds.min.x, 0.0, z_offset))
             * tilted_positions
     }
+
+    /// Creates the key positions from the given config before tilting and placing the keyboard.
+    ///
+    /// The XY-plane then corresponds to the plane of the keyboard.
+    #[must_use]
+    pub fn untilted_from_config(config: &Config) -> Self {
+        let mut columns = Columns::from_config(&config.finger_cluster);
+        let mut thumb_keys = ThumbKeys::from_config(&config.thumb_cluster);
+
+        for key_override in config.key_overrides.iter() {
+            let position = match key_override.key {
+                KeyIndex::Finger { column, row } => columns
+                    .get_mut(usize::from(column))
+                    .and_then(|column| column.get_mut(usize::from(row))),
+                KeyIndex::Thumb { thumb } => thumb_keys.get_mut(usize::from(thumb)),
+            };
+
+            if let Some(position) = position {
+                *position *= override_transform(key_override);
+            }
+        }
+
+        Self {
+            columns,
+            thumb_keys,
+        }
+    }
+}
+
+/// Returns the transformation of a key override relative to the position of the key.
+fn override_transform(key_override: &KeyOverride) -> DAffine3 {
+    let rotation: DVec3 = key_override.rotation.into();
+    let rotation = DQuat::from_euler(
+        EulerRot::ZYX,
+        rotation.z.to_radians(),
+        rotation.y.to_radians(),
+        rotation.x.to_radians(),
+    );
+
+    DAffine3::from_rotation_translation(rotation, key_override.translation.into())
 }
 
 impl Mul<KeyPositions> for DAffine3 {
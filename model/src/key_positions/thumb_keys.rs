@@ -1,4 +1,4 @@
-use std::ops::{Deref, Mul};
+use std::ops::{Deref, DerefMut, Mul};
 
 use config::{KeySize, ThumbCluster};
 use glam::{dvec2, dvec3, DAffine3, DQuat, DVec2, DVec3, EulerRot, Vec3Swizzles};
@@ -128,6 +128,12 @@ impl Deref for ThumbKeys {
     }
 }
 
+impl DerefMut for ThumbKeys {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 impl Mul<ThumbKeys> for DAffine3 {
     type Output = ThumbKeys;
 
@@ -1,6 +1,6 @@
 use config::Keyboard;
 use fidget::context::Tree;
-use glam::{DAffine3, DMat3, DVec2, DVec3};
+use glam::{dvec3, DAffine3, DMat3, DVec2, DVec3, Vec3Swizzles};
 
 use crate::{
     geometry::{Line, Plane, Project},
@@ -43,15 +43,13 @@ impl FingerCluster {
             cluster_height,
             circumference_distance,
         );
-        let insert_holders = Self::insert_holders(
+        let (insert_holders, interface_pcb) = Self::insert_holders_and_interface_pcb(
+            columns,
             &outline_points,
             outline_offset,
-            columns.len(),
-            columns.first().len(),
+            config,
         );
 
-        let interface_pcb = InterfacePcb::new(&insert_holders[2], &outline_points, outline_offset);
-
         let outline = SimplePolygon::new(outline_points).into_tree();
         let cluster_outline = outline.offset(circumference_distance);
         let cluster = cluster_outline.extrude(-cluster_height, cluster_height);
@@ -71,21 +69,37 @@ impl FingerCluster {
         }
     }
 
-    fn insert_holders(
+    /// Places the insert holders and the interface PCB of the finger cluster.
+    pub fn placed_parts(columns: &Columns, config: &Keyboard) -> ([InsertHolder; 3], InterfacePcb) {
+        let outline_points = columns.outline_points();
+        let outline_offset =
+            f64::from(config.circumference_distance) - f64::from(config.shell_thickness);
+
+        Self::insert_holders_and_interface_pcb(columns, &outline_points, outline_offset, config)
+    }
+
+    fn insert_holders_and_interface_pcb(
+        columns: &Columns,
         outline_points: &[DVec2],
         outline_offset: f64,
-        columns: usize,
-        rows: usize,
-    ) -> [InsertHolder; 3] {
-        let first_index = columns - 1;
-        let second_index = first_index + rows;
-        let third_index = second_index + columns;
-
-        [
-            InsertHolder::from_outline_points(outline_points, first_index, outline_offset),
-            InsertHolder::from_outline_points(outline_points, second_index, outline_offset),
-            InsertHolder::from_outline_points(outline_points, third_index, outline_offset),
-        ]
+        config: &Keyboard,
+    ) -> ([InsertHolder; 3], InterfacePcb) {
+        let hot_swap = config.hot_swap && config.switch_type.has_hot_swap_socket();
+        let first_index = columns.len() - 1;
+        let second_index = first_index + columns.last().len();
+        let third_index = second_index + columns.len();
+
+        let insert_holders = [first_index, second_index, third_index].map(|index| {
+            InsertHolder::from_outline_points(outline_points, index, outline_offset, hot_swap)
+        });
+        let interface_pcb = InterfacePcb::new(
+            config.controller,
+            &insert_holders[2],
+            outline_points,
+            outline_offset,
+        );
+
+        (insert_holders, interface_pcb)
     }
 }
 
@@ -132,6 +146,23 @@ impl<'a> ClearanceBuilder<'a> {
             }
         }
 
+        for window in columns.windows(2) {
+            let (left, right) = (&window[0], &window[1]);
+            let normal_columns = matches!(
+                (left.column_type, right.column_type),
+                (ColumnType::Normal, ColumnType::Normal)
+            );
+            let splayed = !left
+                .first()
+                .x_axis
+                .abs_diff_eq(right.first().x_axis, EPSILON);
+
+            if normal_columns && splayed {
+                let splay_clearance = self.splay_clearance(left, right);
+                clearance = clearance.union(splay_clearance);
+            }
+        }
+
         clearance
     }
 
@@ -146,6 +177,27 @@ impl<'a> ClearanceBuilder<'a> {
         prism_from_projected_points(points, &plane, 2.0 * self.columns.key_clearance.x)
     }
 
+    /// Returns the clearance filling the gap between two normal columns with different splay angles.
+    fn splay_clearance(&self, left: &Column, right: &Column) -> Tree {
+        let extrusion_height = self.bounds.diameter();
+
+        // Extrude both columns towards each other, starting at their centers
+        let first = left.first();
+        let plane = Plane::new(first.translation, first.x_axis);
+        let points = self.clearance_points(left);
+        let left_clearance = prism_from_projected_points(points, &plane, extrusion_height);
+
+        let first = right.first();
+        let plane = Plane::new(
+            first.translation - extrusion_height * first.x_axis,
+            first.x_axis,
+        );
+        let points = self.clearance_points(right);
+        let right_clearance = prism_from_projected_points(points, &plane, extrusion_height);
+
+        left_clearance.intersection(right_clearance)
+    }
+
     fn side_column_clearance(
         &self,
         column: &Column,
@@ -286,6 +338,9 @@ impl SupportPlanes {
         let key_clearance = columns.key_clearance;
         let reference_column = columns.get(1).unwrap_or_else(|| columns.first());
         let x_axis = reference_column.first().x_axis;
+
+        // Remove the splay of the reference column to keep the support planes independent of it
+        let x_axis = dvec3(x_axis.xy().length(), 0.0, x_axis.z);
         let normal = x_axis.cross(DVec3::Y);
 
         let mut lower_points: Vec<_> = columns
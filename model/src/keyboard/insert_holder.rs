@@ -1,8 +1,8 @@
 use fidget::context::Tree;
-use glam::DVec2;
+use glam::{DAffine3, DVec2};
 
 use crate::{
-    geometry::{rotate_90_degrees, Tangent},
+    geometry::{rotate_90_degrees, vec_z, OrientedBox, Tangent},
     primitives::{Circle, Corner, Csg, IntoTree, Transforms},
 };
 
@@ -21,7 +21,15 @@ impl InsertHolder {
     const RADIUS: f64 = Self::INSERT_RADIUS + Self::WALL_THICKNESS;
 
     /// Creates a new insert holder from the given outline points and an index.
-    pub fn from_outline_points(points: &[DVec2], index: usize, outline_offset: f64) -> Self {
+    ///
+    /// With hot-swap sockets, the holder is moved into the wall until the insert touches its inner
+    /// face, so it clears the sockets of the keys next to it.
+    pub fn from_outline_points(
+        points: &[DVec2],
+        index: usize,
+        outline_offset: f64,
+        hot_swap: bool,
+    ) -> Self {
         let n = points.len();
         let previous_point = points[(index + n - 1) % n];
         let point = points[index];
@@ -31,7 +39,12 @@ impl InsertHolder {
         let edge2 = rotate_90_degrees(point - next_point).normalize();
 
         let outwards_direction = (edge1 + edge2).normalize();
-        let center = point + (outline_offset - Self::RADIUS) * outwards_direction;
+        let inset = if hot_swap {
+            Self::INSERT_RADIUS
+        } else {
+            Self::RADIUS
+        };
+        let center = point + (outline_offset - inset) * outwards_direction;
 
         Self {
             center,
@@ -59,6 +72,18 @@ impl InsertHolder {
         self.center
     }
 
+    /// Returns a box enclosing the insert holder, aligned with the bisector of its corner.
+    pub fn bounding_box(&self) -> OrientedBox {
+        let outwards_direction = (self.edge1 + self.edge2).normalize();
+
+        OrientedBox::new(
+            DAffine3::from_translation(self.center.extend(0.0))
+                * DAffine3::from_rotation_z(outwards_direction.to_angle()),
+            vec_z(Self::HEIGHT / 2.0),
+            DVec2::splat(2.0 * Self::RADIUS).extend(Self::HEIGHT),
+        )
+    }
+
     /// Returns the outline vertex index of the insert holder.
     pub fn index(&self) -> usize {
         self.index
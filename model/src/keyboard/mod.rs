@@ -7,14 +7,16 @@ use std::iter::once;
 
 use config::Keyboard as Config;
 use fidget::context::Tree;
-use glam::{dvec2, DAffine3, DVec2, DVec3};
+use glam::{dvec2, DAffine3, DVec3};
 
 pub use insert_holder::InsertHolder;
 
 use crate::{
     geometry::{vec_z, Plane},
+    interface_pcb::InterfacePcb,
     key_positions::KeyPositions,
     primitives::{BoxShape, Csg, HalfSpace, IntoTree, RoundedCsg, Shape, Transforms, EPSILON},
+    switch::Switch,
 };
 
 use bottom_plate::BottomPlate;
@@ -31,6 +33,8 @@ pub struct Keyboard {
     pub preview: Shape,
     /// The position of the interface PCB.
     pub interface_pcb_position: DAffine3,
+    /// The size of the interface PCB.
+    pub interface_pcb_size: DVec3,
 }
 
 impl Keyboard {
@@ -82,9 +86,11 @@ impl Keyboard {
             interface_pcb.holder(bounds.diameter()),
             &cluster_outline,
         );
-        let cluster = cluster
-            .union(holders)
-            .difference(Self::switch_cutouts(key_positions, shell_thickness));
+        let cluster = cluster.union(holders).difference(Self::switch_cutouts(
+            key_positions,
+            &config.switch_type.into(),
+            shell_thickness,
+        ));
 
         let case = cluster.difference(interface_pcb.cutouts(bounds.diameter()));
         let case = Shape::new(&case, bounds);
@@ -94,25 +100,43 @@ impl Keyboard {
             bottom_plate,
             preview,
             interface_pcb_position: interface_pcb.position,
+            interface_pcb_size: interface_pcb.size,
         }
     }
 
-    /// Calculates the switch cutouts from the given key positions.
-    fn switch_cutouts(key_positions: &KeyPositions, shell_thickness: f64) -> Tree {
-        const CUTOUT_SIZE: DVec2 = dvec2(14.0, 14.0);
-        const PLATE_THICKNESS: f64 = 1.5;
+    /// Places the insert holders and the interface PCB for the given key positions and
+    /// configuration without creating any shapes.
+    pub fn placed_parts(
+        key_positions: &KeyPositions,
+        config: &Config,
+    ) -> (Vec<InsertHolder>, InterfacePcb) {
+        let (finger_insert_holders, interface_pcb) =
+            FingerCluster::placed_parts(&key_positions.columns, config);
+        let insert_holders = finger_insert_holders
+            .into_iter()
+            .chain(once(ThumbCluster::placed_insert_holder(
+                &key_positions.thumb_keys,
+                config,
+            )))
+            .collect();
+
+        (insert_holders, interface_pcb)
+    }
+
+    /// Calculates the switch cutouts from the given key positions and switch.
+    fn switch_cutouts(key_positions: &KeyPositions, switch: &Switch, shell_thickness: f64) -> Tree {
         const PLATE_CLEARANCE: f64 = 1.0;
 
-        let switch_cutout_size = CUTOUT_SIZE.extend(2.0 * (shell_thickness + EPSILON));
-        let plate_cutout_height = shell_thickness - PLATE_THICKNESS;
+        let switch_cutout_size = switch.cutout_size.extend(2.0 * (shell_thickness + EPSILON));
+        let plate_cutout_height = shell_thickness - switch.plate_thickness;
 
         let switch_cutout = if plate_cutout_height > 0.0 {
-            let plate_cutout_size = (CUTOUT_SIZE + dvec2(0.0, 2.0 * PLATE_CLEARANCE))
+            let plate_cutout_size = (switch.cutout_size + dvec2(0.0, 2.0 * PLATE_CLEARANCE))
                 .extend(plate_cutout_height + EPSILON);
 
             BoxShape::new(plate_cutout_size)
                 .into_tree()
-                .translate(vec_z(-plate_cutout_size.z / 2.0 - PLATE_THICKNESS))
+                .translate(vec_z(-plate_cutout_size.z / 2.0 - switch.plate_thickness))
                 .union(BoxShape::new(switch_cutout_size))
         } else {
             BoxShape::new(switch_cutout_size).into_tree()
@@ -1,6 +1,6 @@
 use config::Keyboard;
 use fidget::context::Tree;
-use glam::{DAffine3, DVec3};
+use glam::{DAffine3, DVec2, DVec3};
 
 use crate::{
     geometry::{Line, Plane},
@@ -40,7 +40,7 @@ impl ThumbCluster {
             cluster_height,
             circumference_distance,
         );
-        let insert_holder = InsertHolder::from_outline_points(&outline_points, 1, outline_offset);
+        let insert_holder = Self::insert_holder(&outline_points, outline_offset, config);
 
         let cluster_outline = ConvexPolygon::new(outline_points)
             .into_tree()
@@ -61,6 +61,24 @@ impl ThumbCluster {
         }
     }
 
+    /// Places the insert holder of the thumb cluster.
+    pub fn placed_insert_holder(thumb_keys: &ThumbKeys, config: &Keyboard) -> InsertHolder {
+        let outline_offset =
+            f64::from(config.circumference_distance) - f64::from(config.shell_thickness);
+
+        Self::insert_holder(&thumb_keys.outline_points(), outline_offset, config)
+    }
+
+    fn insert_holder(
+        outline_points: &[DVec2],
+        outline_offset: f64,
+        config: &Keyboard,
+    ) -> InsertHolder {
+        let hot_swap = config.hot_swap && config.switch_type.has_hot_swap_socket();
+
+        InsertHolder::from_outline_points(outline_points, 1, outline_offset, hot_swap)
+    }
+
     /// Returns the clearance shape of the thumb cluster.
     fn clearance(thumb_keys: &ThumbKeys, bounds: Bounds) -> Tree {
         let key_clearance = thumb_keys.key_clearance;
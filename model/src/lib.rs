@@ -5,7 +5,9 @@ mod interface_pcb;
 mod key_positions;
 mod keyboard;
 mod primitives;
+mod switch;
 mod util;
+mod validation;
 
 /// The matrix PCB module cointans everything related to creating a model of the matrix PCB.
 pub mod matrix_pcb;
@@ -13,11 +15,12 @@ pub mod matrix_pcb;
 use config::{Colors, Config, Preview};
 
 use keyboard::Keyboard;
-use matrix_pcb::MatrixPcb;
+use matrix_pcb::{Dimensions, MatrixPcb};
 
 pub use fidget::mesh::{Mesh, Settings as MeshSettings};
 pub use key_positions::KeyPositions;
 pub use primitives::Bounds;
+pub use validation::{validate, Diagnostic, Key, Severity};
 
 /// A set of settings used for displaying a model.
 #[derive(Clone, Default)]
@@ -57,7 +60,7 @@ impl Model {
     pub fn from_config(config: &Config) -> Self {
         let key_positions = KeyPositions::from_config(config);
         let keyboard = Keyboard::new(&key_positions, &config.keyboard);
-        let matrix_pcb = MatrixPcb::from_positions(&key_positions);
+        let matrix_pcb = MatrixPcb::from_positions(&key_positions, Dimensions::from(config));
 
         Self {
             keyboard,
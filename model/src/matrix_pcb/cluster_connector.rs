@@ -5,10 +5,8 @@ use glam::{dvec3, DAffine3, DMat3, DVec2, DVec3, Vec3Swizzles};
 use crate::{
     geometry::{rotate_90_degrees, vec_y, Ellipse, Line, Plane},
     matrix_pcb::{
-        pad_center,
         segments::{Arc, BezierCurve},
-        Segment, ARC_RADIUS, CONNECTOR_WIDTH, FFC_PAD_OFFSET, FFC_PAD_SIZE, MINIMUM_SEGMENT_LENGTH,
-        PAD_SIZE,
+        Dimensions, Segment, FFC_PAD_OFFSET, FFC_PAD_SIZE,
     },
     util::SideY,
 };
@@ -21,12 +19,19 @@ pub struct ClusterConnector {
     pub finger_cluster_arc_angle: f64,
     /// The angle of the thumb cluster arc.
     pub thumb_cluster_arc_angle: f64,
+    /// The dimensions of the connector.
+    pub dimensions: Dimensions,
 }
 
 impl ClusterConnector {
-    /// Creates a new cluster connector from the finger and thumb cluster anchor key positions.
+    /// Creates a new cluster connector with the given dimensions from the finger and thumb cluster
+    /// anchor key positions.
     #[must_use]
-    pub fn from_anchor_key_positions(finger_key: DAffine3, thumb_key: DAffine3) -> Self {
+    pub fn from_anchor_key_positions(
+        finger_key: DAffine3,
+        thumb_key: DAffine3,
+        dimensions: Dimensions,
+    ) -> Self {
         // The plane normal is given as the average normal vector of the arc planes
         let plane_normal = (finger_key.z_axis + thumb_key.z_axis).normalize();
         let x_axis = DVec3::Y.cross(plane_normal);
@@ -40,14 +45,23 @@ impl ClusterConnector {
         };
         let world_to_plane = plane_to_world.inverse();
 
-        let thumb_arc_center = (world_to_plane * arc_center(thumb_key, SideY::Top)).xy();
-        let finger_arc_center = (world_to_plane * arc_center(finger_key, SideY::Bottom)).xy();
+        let thumb_arc_center =
+            (world_to_plane * arc_center(thumb_key, SideY::Top, dimensions)).xy();
+        let finger_arc_center =
+            (world_to_plane * arc_center(finger_key, SideY::Bottom, dimensions)).xy();
 
         // Extend arcs to circles and project them to the XY-plane
-        let finger_ellipse =
-            calculate_circle_projection(world_to_plane * finger_key.z_axis, finger_arc_center);
-        let thumb_ellipse =
-            calculate_circle_projection(world_to_plane * thumb_key.z_axis, thumb_arc_center);
+        let arc_radius = dimensions.arc_radius();
+        let finger_ellipse = calculate_circle_projection(
+            world_to_plane * finger_key.z_axis,
+            finger_arc_center,
+            arc_radius,
+        );
+        let thumb_ellipse = calculate_circle_projection(
+            world_to_plane * thumb_key.z_axis,
+            thumb_arc_center,
+            arc_radius,
+        );
 
         // Calculate the tangents between the ellipses and calculate the direction from it
         let tangents = finger_ellipse.tangents_to(&thumb_ellipse);
@@ -58,14 +72,20 @@ impl ClusterConnector {
         let finger_cluster_arc_angle = calculate_arc_angle(finger_key, direction, plane_normal);
         let thumb_cluster_arc_angle = calculate_arc_angle(thumb_key, direction, plane_normal);
 
-        let start = arc_end(finger_cluster_arc_angle, finger_key, SideY::Bottom);
-        let end = arc_end(thumb_cluster_arc_angle, thumb_key, SideY::Top);
+        let start = arc_end(
+            finger_cluster_arc_angle,
+            finger_key,
+            SideY::Bottom,
+            dimensions,
+        );
+        let end = arc_end(thumb_cluster_arc_angle, thumb_key, SideY::Top, dimensions);
         let bezier_curve = BezierCurve::from_positions(start, end);
 
         Self {
             bezier_curve,
             finger_cluster_arc_angle,
             thumb_cluster_arc_angle,
+            dimensions,
         }
     }
 }
@@ -82,8 +102,10 @@ impl Segment for ClusterConnector {
             .last()
             .expect("there should always be a position");
 
-        let finger_cluster_arc = Arc::new(ARC_RADIUS, self.finger_cluster_arc_angle, DVec3::NEG_Z);
-        let thumb_cluster_arc = Arc::new(ARC_RADIUS, self.thumb_cluster_arc_angle, DVec3::NEG_Z);
+        let arc_radius = self.dimensions.arc_radius();
+        let segment_length = self.dimensions.minimum_segment_length();
+        let finger_cluster_arc = Arc::new(arc_radius, self.finger_cluster_arc_angle, DVec3::NEG_Z);
+        let thumb_cluster_arc = Arc::new(arc_radius, self.thumb_cluster_arc_angle, DVec3::NEG_Z);
 
         let finger_cluster_arc_positions = finger_cluster_arc.positions();
         let thumb_cluster_arc_positions = thumb_cluster_arc.positions();
@@ -92,12 +114,12 @@ impl Segment for ClusterConnector {
             * *finger_cluster_arc_positions
                 .last()
                 .expect("there should always be a position")
-            * DAffine3::from_translation(vec_y(MINIMUM_SEGMENT_LENGTH));
+            * DAffine3::from_translation(vec_y(segment_length));
         let end_position = thumb_cluster_arc_position
             * *thumb_cluster_arc_positions
                 .last()
                 .expect("there should always be a position")
-            * DAffine3::from_translation(vec_y(MINIMUM_SEGMENT_LENGTH));
+            * DAffine3::from_translation(vec_y(segment_length));
 
         once(start_position)
             .chain(
@@ -121,44 +143,46 @@ impl Segment for ClusterConnector {
     }
 }
 /// Returns the start point of the arc for the given key position and side.
-fn arc_start(position: DAffine3, side: SideY) -> DVec3 {
+fn arc_start(position: DAffine3, side: SideY, dimensions: Dimensions) -> DVec3 {
+    let segment_length = dimensions.minimum_segment_length();
     let offset = match side {
         SideY::Bottom => {
-            (-FFC_PAD_OFFSET - FFC_PAD_SIZE.y / 2.0 - MINIMUM_SEGMENT_LENGTH) * position.y_axis
+            (-FFC_PAD_OFFSET - FFC_PAD_SIZE.y / 2.0 - segment_length) * position.y_axis
         }
         SideY::Top => {
-            (PAD_SIZE.x - CONNECTOR_WIDTH) / 2.0 * position.x_axis
-                + (PAD_SIZE.y / 2.0 + MINIMUM_SEGMENT_LENGTH) * position.y_axis
+            (dimensions.pad_size.x - dimensions.connector_width) / 2.0 * position.x_axis
+                + (dimensions.pad_size.y / 2.0 + segment_length) * position.y_axis
         }
     };
 
-    pad_center(position) + offset
+    dimensions.pad_center(position) + offset
 }
 
 /// Returns the center point of the arc starting at the given key position and side.
-fn arc_center(position: DAffine3, side: SideY) -> DVec3 {
-    arc_start(position, side) + side.direction() * ARC_RADIUS * position.x_axis
+fn arc_center(position: DAffine3, side: SideY, dimensions: Dimensions) -> DVec3 {
+    arc_start(position, side, dimensions)
+        + side.direction() * dimensions.arc_radius() * position.x_axis
 }
 
 /// Returns the end position of the arc with the given angle starting at the given key position and side.
-fn arc_end(angle: f64, position: DAffine3, side: SideY) -> DAffine3 {
-    let translation = side.direction() * ARC_RADIUS * DVec3::X;
+fn arc_end(angle: f64, position: DAffine3, side: SideY, dimensions: Dimensions) -> DAffine3 {
+    let translation = side.direction() * dimensions.arc_radius() * DVec3::X;
 
     DAffine3 {
         matrix3: position.matrix3,
-        translation: arc_start(position, side),
+        translation: arc_start(position, side, dimensions),
     } * DAffine3 {
         matrix3: DMat3::from_rotation_z(-angle),
         translation,
     } * DAffine3::from_translation(-translation)
 }
 
-/// Calculates the projection of a circle on a plane with the given normal axis
-/// to the XY-plane, placing its projected center to the given position.
-fn calculate_circle_projection(normal: DVec3, center: DVec2) -> Ellipse {
+/// Calculates the projection of a circle with the given radius on a plane with the given normal
+/// axis to the XY-plane, placing its projected center to the given position.
+fn calculate_circle_projection(normal: DVec3, center: DVec2, radius: f64) -> Ellipse {
     let minor_axis = normal.xy().try_normalize().unwrap_or(DVec2::X);
 
-    Ellipse::new(minor_axis, center, normal.z * ARC_RADIUS, ARC_RADIUS)
+    Ellipse::new(minor_axis, center, normal.z * radius, radius)
 }
 
 /// Selects the tangent along the line segment connecting the finger and thumb cluster arcs.
@@ -9,9 +9,8 @@ use crate::{
     geometry::vec_y,
     key_positions::{Column, ColumnType, ThumbKeys},
     matrix_pcb::{
-        pad_center,
         segments::{Arc, BezierCurve, Line, Segment},
-        ARC_RADIUS, CONNECTOR_WIDTH, PAD_SIZE,
+        Dimensions,
     },
     util::{SideX, SideY},
 };
@@ -39,36 +38,34 @@ impl KeyConnector {
         }
     }
 
-    /// Creates a new key connector from the given column.
+    /// Creates a new key connector between two neighboring keys of a column.
     #[must_use]
-    fn from_column(column: &Column) -> Self {
-        if let Some(&next_position) = column.get(1) {
-            let position = column.first();
-            let start_point = vertical_connector_point(position, SideY::Top);
-            let end_point = vertical_connector_point(next_position, SideY::Bottom);
+    fn from_column_keys(
+        position: DAffine3,
+        next_position: DAffine3,
+        dimensions: Dimensions,
+    ) -> Self {
+        let start_point = vertical_connector_point(position, SideY::Top, dimensions);
+        let end_point = vertical_connector_point(next_position, SideY::Bottom, dimensions);
 
-            let direction = position.matrix3.inverse() * (end_point - start_point);
+        let direction = position.matrix3.inverse() * (end_point - start_point);
 
-            Self::new(direction.yz())
-        } else {
-            Self::Line(Line::new(1.0))
-        }
+        Self::new(direction.yz())
     }
 
-    /// Creates new key connectors for the given thumb keys.
+    /// Creates a new key connector between two neighboring thumb keys.
     #[must_use]
-    fn from_thumb_keys(thumb_keys: &ThumbKeys) -> Self {
-        if let Some(&next_position) = thumb_keys.get(1) {
-            let position = thumb_keys.first();
-            let start_point = horizontal_connector_point(position, SideX::Right);
-            let end_point = horizontal_connector_point(next_position, SideX::Left);
+    fn from_thumb_keys(
+        position: DAffine3,
+        next_position: DAffine3,
+        dimensions: Dimensions,
+    ) -> Self {
+        let start_point = horizontal_connector_point(position, SideX::Right, dimensions);
+        let end_point = horizontal_connector_point(next_position, SideX::Left, dimensions);
 
-            let direction = position.matrix3.inverse() * (end_point - start_point);
+        let direction = position.matrix3.inverse() * (end_point - start_point);
 
-            Self::new(direction.xz())
-        } else {
-            Self::Line(Line::new(1.0))
-        }
+        Self::new(direction.xz())
     }
 }
 
@@ -91,18 +88,22 @@ impl Segment for KeyConnector {
 /// The connectors between keys in a column.
 #[allow(clippy::module_name_repetitions)]
 pub struct ColumnKeyConnectors {
-    /// The connector itself.
-    pub connector: KeyConnector,
-    /// The positions of the connector.
+    /// The connectors between two neighboring keys.
+    pub connectors: Vec<KeyConnector>,
+    /// The positions of the connectors.
     pub positions: Vec<(DAffine3, DAffine3)>,
     /// The side offsets between two neighboring keys.
     pub offsets: Vec<f64>,
+    /// The index of the home row within the column.
+    pub home_row_index: usize,
 }
 
 impl ColumnKeyConnectors {
-    /// Creates new key connectors for a given column.
+    /// Creates new key connectors with the given dimensions for a given column.
     #[must_use]
-    pub fn from_column(column: &Column) -> Self {
+    pub fn from_column(column: &Column, dimensions: Dimensions) -> Self {
+        let connector_offset = (dimensions.pad_size.x - dimensions.connector_width) / 2.0;
+
         let (positions, offsets) = column
             .windows(2)
             .map(|window| {
@@ -114,10 +115,10 @@ impl ColumnKeyConnectors {
                     .transform_point3(top_position.translation);
 
                 let offset = transformed_next_key.x;
-                let left_x_offset = offset.max(0.0) - (PAD_SIZE.x - CONNECTOR_WIDTH) / 2.0;
-                let right_x_offset = offset.min(0.0) + (PAD_SIZE.x - CONNECTOR_WIDTH) / 2.0;
+                let left_x_offset = offset.max(0.0) - connector_offset;
+                let right_x_offset = offset.min(0.0) + connector_offset;
 
-                let start_point = vertical_connector_point(bottom_position, SideY::Top);
+                let start_point = vertical_connector_point(bottom_position, SideY::Top, dimensions);
                 let left_position = DAffine3 {
                     matrix3: bottom_position.matrix3,
                     translation: start_point + left_x_offset * bottom_position.x_axis,
@@ -130,12 +131,16 @@ impl ColumnKeyConnectors {
                 ((left_position, right_position), offset)
             })
             .unzip();
-        let connector = KeyConnector::from_column(column);
+        let connectors = column
+            .windows(2)
+            .map(|window| KeyConnector::from_column_keys(window[0], window[1], dimensions))
+            .collect();
 
         Self {
-            connector,
+            connectors,
             positions,
             offsets,
+            home_row_index: column.home_row_index,
         }
     }
 }
@@ -143,41 +148,45 @@ impl ColumnKeyConnectors {
 /// The connectors between keys in the thumb cluster.
 #[allow(clippy::module_name_repetitions)]
 pub struct ThumbKeyConnectors {
-    /// The connector itself.
-    pub connector: KeyConnector,
-    /// The positions of the connector.
+    /// The connectors between two neighboring keys.
+    pub connectors: Vec<KeyConnector>,
+    /// The positions of the connectors.
     pub positions: Vec<(DAffine3, DAffine3)>,
 }
 
 impl ThumbKeyConnectors {
-    /// Creates new key connectors for the given thumb keys.
+    /// Creates new key connectors with the given dimensions for the given thumb keys.
     #[must_use]
-    pub fn from_thumb_keys(thumb_keys: &ThumbKeys) -> Self {
+    pub fn from_thumb_keys(thumb_keys: &ThumbKeys, dimensions: Dimensions) -> Self {
+        let connector_offset = (dimensions.pad_size.y - dimensions.connector_width) / 2.0;
+
         let positions = thumb_keys
             .windows(2)
             .map(|window| {
                 let left_position = window[0];
                 let matrix3 = left_position.matrix3 * DMat3::from_rotation_z(-FRAC_PI_2);
 
-                let start_point = horizontal_connector_point(left_position, SideX::Right);
+                let start_point =
+                    horizontal_connector_point(left_position, SideX::Right, dimensions);
                 let bottom_position = DAffine3 {
                     matrix3,
-                    translation: start_point
-                        - (PAD_SIZE.y - CONNECTOR_WIDTH) / 2.0 * left_position.y_axis,
+                    translation: start_point - connector_offset * left_position.y_axis,
                 };
                 let top_position = DAffine3 {
                     matrix3,
-                    translation: start_point
-                        + (PAD_SIZE.y - CONNECTOR_WIDTH) / 2.0 * left_position.y_axis,
+                    translation: start_point + connector_offset * left_position.y_axis,
                 };
 
                 (bottom_position, top_position)
             })
             .collect();
-        let connector = KeyConnector::from_thumb_keys(thumb_keys);
+        let connectors = thumb_keys
+            .windows(2)
+            .map(|window| KeyConnector::from_thumb_keys(window[0], window[1], dimensions))
+            .collect();
 
         Self {
-            connector,
+            connectors,
             positions,
         }
     }
@@ -192,23 +201,24 @@ pub enum ColumnConnector {
 }
 
 impl ColumnConnector {
-    /// Creates a new connector between two neighboring columns.
+    /// Creates a new connector with the given dimensions between two neighboring columns.
     #[must_use]
     pub fn from_columns(
         left_column: &Column,
         right_column: &Column,
-        home_row_index: usize,
+        dimensions: Dimensions,
     ) -> Self {
-        let left_position = left_column[home_row_index];
-        let right_position = right_column[home_row_index];
+        let left_position = left_column.home();
+        let right_position = right_column.home();
 
         match (left_column.column_type, right_column.column_type) {
             (ColumnType::Normal, ColumnType::Normal) => Self::Normal(
-                NormalColumnConnector::from_positions(left_position, right_position),
+                NormalColumnConnector::from_positions(left_position, right_position, dimensions),
             ),
             _ => Self::Side(SideColumnConnector::from_positions(
                 left_position,
                 right_position,
+                dimensions,
             )),
         }
     }
@@ -222,17 +232,23 @@ pub struct NormalColumnConnector {
     pub segment_length: f64,
     /// The side of the left arc.
     pub left_arc_side: SideY,
+    /// The radius of the arcs.
+    pub arc_radius: f64,
 }
 
 impl NormalColumnConnector {
     /// Creates a new normal column connector from the given key positions.
     #[must_use]
-    fn from_positions(left_position: DAffine3, right_position: DAffine3) -> Self {
+    fn from_positions(
+        left_position: DAffine3,
+        right_position: DAffine3,
+        dimensions: Dimensions,
+    ) -> Self {
+        let arc_radius = dimensions.arc_radius();
         let transformed_right_translation = left_position
             .inverse()
             .transform_point3(right_position.translation);
 
-        let segment_length = (transformed_right_translation.x - PAD_SIZE.x) / 2.0 - ARC_RADIUS;
         let left_arc_side = match transformed_right_translation.y {
             y if y > 0.1 => SideY::Bottom,
             y if y < -0.1 => SideY::Top,
@@ -245,17 +261,42 @@ impl NormalColumnConnector {
             }
         };
 
+        // Measure the gap at the attachment points, as it differs along the pads of splayed columns
+        let start_point = normal_column_connector_position(
+            left_position,
+            -arc_radius,
+            SideX::Right,
+            left_arc_side,
+            dimensions,
+        )
+        .translation;
+        let end_point = normal_column_connector_position(
+            right_position,
+            -arc_radius,
+            SideX::Left,
+            left_arc_side.opposite(),
+            dimensions,
+        )
+        .translation;
+        let direction = (left_position.x_axis + right_position.x_axis).normalize();
+        let gap = (end_point - start_point).dot(direction);
+
+        // The Bézier curve bridges the remaining offset if the gap is too small
+        let segment_length = (gap / 2.0 - arc_radius).max(dimensions.minimum_segment_length());
+
         let start_position = normal_column_connector_position(
             left_position,
             segment_length,
             SideX::Right,
             left_arc_side,
+            dimensions,
         );
         let end_position = normal_column_connector_position(
             right_position,
             segment_length,
             SideX::Left,
             left_arc_side.opposite(),
+            dimensions,
         );
 
         let bezier_curve = BezierCurve::from_positions(start_position, end_position);
@@ -264,6 +305,7 @@ impl NormalColumnConnector {
             bezier_curve,
             segment_length,
             left_arc_side,
+            arc_radius,
         }
     }
 }
@@ -271,7 +313,7 @@ impl NormalColumnConnector {
 impl Segment for NormalColumnConnector {
     fn positions(&self) -> Vec<DAffine3> {
         let arc_positions = Arc::new(
-            ARC_RADIUS,
+            self.arc_radius,
             FRAC_PI_2,
             self.left_arc_side.direction() * DVec3::NEG_Z,
         )
@@ -321,10 +363,14 @@ pub struct SideColumnConnector {
 }
 
 impl SideColumnConnector {
-    /// Creates a new side column connector from the given key positions.
-    fn from_positions(left_position: DAffine3, right_position: DAffine3) -> Self {
-        let start_point = horizontal_connector_point(left_position, SideX::Right);
-        let end_point = horizontal_connector_point(right_position, SideX::Left);
+    /// Creates a new side column connector with the given dimensions from the given key positions.
+    fn from_positions(
+        left_position: DAffine3,
+        right_position: DAffine3,
+        dimensions: Dimensions,
+    ) -> Self {
+        let start_point = horizontal_connector_point(left_position, SideX::Right, dimensions);
+        let end_point = horizontal_connector_point(right_position, SideX::Left, dimensions);
 
         let direction = left_position.matrix3.inverse() * (end_point - start_point);
         let connector = KeyConnector::new(direction.xz());
@@ -352,13 +398,15 @@ impl Segment for SideColumnConnector {
 }
 
 /// The attachment point of a vertical connector at the given side.
-fn vertical_connector_point(position: DAffine3, side: SideY) -> DVec3 {
-    pad_center(position) + side.direction() * PAD_SIZE.y / 2.0 * position.y_axis
+fn vertical_connector_point(position: DAffine3, side: SideY, dimensions: Dimensions) -> DVec3 {
+    dimensions.pad_center(position)
+        + side.direction() * dimensions.pad_size.y / 2.0 * position.y_axis
 }
 
 /// The attachment point of a horizontal connector at the given side.
-fn horizontal_connector_point(position: DAffine3, side: SideX) -> DVec3 {
-    pad_center(position) + side.direction() * PAD_SIZE.x / 2.0 * position.x_axis
+fn horizontal_connector_point(position: DAffine3, side: SideX, dimensions: Dimensions) -> DVec3 {
+    dimensions.pad_center(position)
+        + side.direction() * dimensions.pad_size.x / 2.0 * position.x_axis
 }
 
 /// The attachment point of a normal column connector with the given radius in the given corner.
@@ -367,11 +415,15 @@ fn normal_column_connector_position(
     segment_length: f64,
     side_x: SideX,
     side_y: SideY,
+    dimensions: Dimensions,
 ) -> DAffine3 {
-    let translation = pad_center(position)
-        + side_x.direction() * (PAD_SIZE.x / 2.0 + segment_length + ARC_RADIUS) * position.x_axis
+    let arc_radius = dimensions.arc_radius();
+    let translation = dimensions.pad_center(position)
+        + side_x.direction()
+            * (dimensions.pad_size.x / 2.0 + segment_length + arc_radius)
+            * position.x_axis
         + side_y.direction()
-            * ((PAD_SIZE.y - CONNECTOR_WIDTH) / 2.0 - ARC_RADIUS)
+            * ((dimensions.pad_size.y - dimensions.connector_width) / 2.0 - arc_radius)
             * position.y_axis;
 
     DAffine3 {
@@ -2,38 +2,82 @@ mod cluster_connector;
 mod connectors;
 mod segments;
 
+use config::Config;
 use glam::{dvec2, DAffine3, DVec2, DVec3};
 
-use crate::key_positions::{ColumnType, KeyPositions};
+use crate::{
+    key_positions::{ColumnType, KeyPositions},
+    switch::Switch,
+};
 
 pub use cluster_connector::ClusterConnector;
 pub use connectors::{
-    ColumnConnector, ColumnKeyConnectors, NormalColumnConnector, SideColumnConnector,
+    ColumnConnector, ColumnKeyConnectors, KeyConnector, NormalColumnConnector, SideColumnConnector,
     ThumbKeyConnectors,
 };
 pub use segments::Segment;
 
-/// The thickness of the matrix PCB.
-pub const THICKNESS: f64 = 0.6;
-/// The size of the PCB pads underneath each key.
-pub const PAD_SIZE: DVec2 = dvec2(13.0, 16.0);
 /// The size of the PCB pad below the FFC connector.
 pub const FFC_PAD_SIZE: DVec2 = dvec2(19.0, 4.0);
 /// The offset of the FFC connector pad to the center of the key.
 pub const FFC_PAD_OFFSET: f64 = 7.9;
-/// The width of the connectors between keys.
-pub const CONNECTOR_WIDTH: f64 = 2.0;
-/// The diameter of the routing bit.
-pub const ROUTER_BIT_DIAMETER: f64 = 2.0;
-/// The radius of the arcs.
-pub const ARC_RADIUS: f64 = CONNECTOR_WIDTH / 2.0 + ROUTER_BIT_DIAMETER / 2.0;
-/// The minimum length of the connector segments.
-pub const MINIMUM_SEGMENT_LENGTH: f64 = ROUTER_BIT_DIAMETER / 2.0;
 
-const SWITCH_HEIGHT: f64 = 5.0;
+/// The dimensions of the matrix PCB depending on its fabrication and the switch type.
+#[derive(Clone, Copy)]
+pub struct Dimensions {
+    /// The size of the PCB pads underneath each key.
+    pub pad_size: DVec2,
+    /// The height of the switches below the key positions.
+    pub switch_height: f64,
+    /// The thickness of the matrix PCB.
+    pub thickness: f64,
+    /// The width of the connectors between keys.
+    pub connector_width: f64,
+    /// The diameter of the routing bit.
+    pub router_bit_diameter: f64,
+}
+
+impl Dimensions {
+    /// Returns the radius of the arcs.
+    #[must_use]
+    pub fn arc_radius(self) -> f64 {
+        self.connector_width / 2.0 + self.router_bit_diameter / 2.0
+    }
+
+    /// Returns the minimum length of the connector segments.
+    #[must_use]
+    pub fn minimum_segment_length(self) -> f64 {
+        self.router_bit_diameter / 2.0
+    }
+
+    /// Returns the center point of a PCB pad for the given key position.
+    #[must_use]
+    pub fn pad_center(self, position: DAffine3) -> DVec3 {
+        position.translation - (self.switch_height + self.thickness / 2.0) * position.z_axis
+    }
+}
+
+impl From<&Config> for Dimensions {
+    fn from(config: &Config) -> Self {
+        let fabrication = &config.fabrication;
+        let switch = Switch::from(config.keyboard.switch_type);
+
+        Self {
+            pad_size: switch.matrix_pcb_pad_size(config.keyboard.hot_swap),
+            switch_height: switch.bottom,
+            thickness: fabrication.thickness.into(),
+            connector_width: fabrication.connector_width.into(),
+            router_bit_diameter: fabrication.router_bit_diameter.into(),
+        }
+    }
+}
 
 /// A PCB connecting the keys to each other in a matrix.
 pub struct MatrixPcb {
+    /// The dimensions of the matrix PCB.
+    pub dimensions: Dimensions,
+    /// The positions of the pads underneath the keys.
+    pub pad_positions: Vec<DAffine3>,
     /// The key connectors between keys in the columns.
     pub column_key_connectors: Vec<ColumnKeyConnectors>,
     /// The key connectors between keys in the thumb cluster.
@@ -47,21 +91,30 @@ pub struct MatrixPcb {
 }
 
 impl MatrixPcb {
-    /// Creates a new matrix PCB from the given key positions.
+    /// Creates a new matrix PCB with the given dimensions from the given key positions.
     #[allow(clippy::missing_panics_doc)]
-    pub fn from_positions(positions: &KeyPositions) -> Self {
+    #[must_use]
+    pub fn from_positions(positions: &KeyPositions, dimensions: Dimensions) -> Self {
         let columns = &positions.columns;
-        #[allow(clippy::cast_sign_loss)]
-        let home_row_index = columns.home_row_index as usize;
 
+        let pad_positions = columns
+            .iter()
+            .flat_map(|column| column.iter())
+            .chain(positions.thumb_keys.iter())
+            .map(|&position| DAffine3 {
+                matrix3: position.matrix3,
+                translation: dimensions.pad_center(position),
+            })
+            .collect();
         let column_key_connectors = columns
             .iter()
-            .map(ColumnKeyConnectors::from_column)
+            .map(|column| ColumnKeyConnectors::from_column(column, dimensions))
             .collect();
-        let thumb_key_connectors = ThumbKeyConnectors::from_thumb_keys(&positions.thumb_keys);
+        let thumb_key_connectors =
+            ThumbKeyConnectors::from_thumb_keys(&positions.thumb_keys, dimensions);
         let column_connectors = columns
             .windows(2)
-            .map(|window| ColumnConnector::from_columns(&window[0], &window[1], home_row_index))
+            .map(|window| ColumnConnector::from_columns(&window[0], &window[1], dimensions))
             .collect();
 
         let finger_anchor_key = positions
@@ -73,15 +126,18 @@ impl MatrixPcb {
         let cluster_connector = ClusterConnector::from_anchor_key_positions(
             finger_anchor_key,
             positions.thumb_keys.first(),
+            dimensions,
         );
 
         let ffc_pad_position = DAffine3 {
             matrix3: finger_anchor_key.matrix3,
-            translation: pad_center(finger_anchor_key)
+            translation: dimensions.pad_center(finger_anchor_key)
                 - FFC_PAD_OFFSET * finger_anchor_key.matrix3.y_axis,
         };
 
         Self {
+            dimensions,
+            pad_positions,
             column_key_connectors,
             thumb_key_connectors,
             column_connectors,
@@ -90,8 +146,3 @@ impl MatrixPcb {
         }
     }
 }
-
-/// Returns the center point of a PCB pad for the given key position.
-fn pad_center(position: DAffine3) -> DVec3 {
-    position.translation - (SWITCH_HEIGHT + THICKNESS / 2.0) * position.z_axis
-}
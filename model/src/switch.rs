@@ -0,0 +1,124 @@
+use config::SwitchType;
+use glam::{dvec2, DVec2};
+
+/// The profile of a Cherry MX switch as heights above the key position and half sizes.
+const MX_PROFILE: [(f64, DVec2); 4] = [
+    (-5.0, dvec2(7.0, 7.0)),
+    (0.0, dvec2(7.8, 7.8)),
+    (2.2, dvec2(6.87, 6.87)),
+    (4.4, dvec2(5.94, 5.94)),
+];
+/// The profile of a 1u DSA keycap as heights above the key position and half sizes.
+const DSA_KEYCAP_PROFILE: [(f64, DVec2); 8] = [
+    (6.6, dvec2(9.12, 9.12)),
+    (7.6, dvec2(8.88, 8.88)),
+    (8.6, dvec2(8.69, 8.69)),
+    (9.6, dvec2(8.46, 8.46)),
+    (10.6, dvec2(8.18, 8.18)),
+    (11.6, dvec2(7.49, 7.49)),
+    (12.6, dvec2(7.08, 7.08)),
+    (13.6, dvec2(6.62, 6.62)),
+];
+/// The profile of a low profile switch as heights above the key position and half sizes.
+const LOW_PROFILE_PROFILE: [(f64, DVec2); 3] = [
+    (-2.2, dvec2(6.9, 6.9)),
+    (0.0, dvec2(7.5, 7.5)),
+    (1.5, dvec2(6.6, 6.6)),
+];
+/// The profile of a 1u low profile keycap as heights above the key position and half sizes.
+const LOW_PROFILE_KEYCAP_PROFILE: [(f64, DVec2); 2] =
+    [(3.0, dvec2(8.75, 8.25)), (4.5, dvec2(8.5, 8.0))];
+
+/// The dimensions of a key switch and its keycap.
+pub struct Switch {
+    /// The size of the switch cutout in the plate.
+    pub cutout_size: DVec2,
+    /// The thickness of the plate the switch is clipped into.
+    pub plate_thickness: f64,
+    /// The profile of the switch as heights above the key position and half sizes.
+    ///
+    /// Each entry covers the height range up to the next one and uses the maximum half size within it.
+    pub profile: &'static [(f64, DVec2)],
+    /// The height of the switch top above the key position.
+    pub top: f64,
+    /// The profile of a 1u keycap in the same format as the switch profile.
+    pub keycap_profile: &'static [(f64, DVec2)],
+    /// The height of the keycap top above the key position.
+    pub keycap_top: f64,
+    /// The depth of the switch bottom the matrix PCB is attached to below the key position.
+    pub bottom: f64,
+    /// The size of the matrix PCB pad below the switch, leaving room for the tracks next to the
+    /// pins.
+    pub pad_size: DVec2,
+    /// The Kailh hot-swap socket fitting the switch, if there is one.
+    pub hot_swap_socket: Option<HotSwapSocket>,
+}
+
+impl Switch {
+    /// Returns the hot-swap socket the switch is plugged into, if enabled and fitting the switch.
+    #[must_use]
+    pub fn socket(&self, hot_swap: bool) -> Option<HotSwapSocket> {
+        self.hot_swap_socket.filter(|_| hot_swap)
+    }
+
+    /// Returns the size of the matrix PCB pad below the switch, which is larger for pads holding
+    /// the hot-swap sockets.
+    #[must_use]
+    pub fn matrix_pcb_pad_size(&self, hot_swap: bool) -> DVec2 {
+        self.socket(hot_swap)
+            .map_or(self.pad_size, |socket| socket.pad_size)
+    }
+}
+
+/// The dimensions of a Kailh hot-swap socket.
+#[derive(Clone, Copy)]
+pub struct HotSwapSocket {
+    /// The size of the matrix PCB pad holding the sockets for both sides, leaving room for the
+    /// tracks next to the socket pads.
+    pub pad_size: DVec2,
+    /// The height of the socket below the matrix PCB.
+    pub height: f64,
+}
+
+impl From<SwitchType> for Switch {
+    fn from(switch_type: SwitchType) -> Self {
+        let low_profile = Self {
+            cutout_size: dvec2(13.8, 13.8),
+            plate_thickness: 1.3,
+            profile: &LOW_PROFILE_PROFILE,
+            top: 3.0,
+            keycap_profile: &LOW_PROFILE_KEYCAP_PROFILE,
+            keycap_top: 6.3,
+            bottom: 2.2,
+            pad_size: dvec2(16.0, 16.0),
+            hot_swap_socket: Some(HotSwapSocket {
+                pad_size: dvec2(22.3, 17.0),
+                height: 1.8,
+            }),
+        };
+
+        match switch_type {
+            SwitchType::Mx => Self {
+                cutout_size: dvec2(14.0, 14.0),
+                plate_thickness: 1.5,
+                profile: &MX_PROFILE,
+                top: 6.6,
+                keycap_profile: &DSA_KEYCAP_PROFILE,
+                keycap_top: 14.4,
+                bottom: 5.0,
+                pad_size: dvec2(13.0, 16.0),
+                hot_swap_socket: Some(HotSwapSocket {
+                    pad_size: dvec2(20.0, 16.0),
+                    height: 1.85,
+                }),
+            },
+            SwitchType::ChocV1 | SwitchType::ChocV2 => low_profile,
+            SwitchType::GateronLowProfile => Self {
+                cutout_size: dvec2(13.9, 13.9),
+                pad_size: dvec2(14.0, 16.0),
+                hot_swap_socket: None,
+                ..low_profile
+            },
+        }
+    }
+}
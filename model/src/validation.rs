@@ -0,0 +1,447 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    iter::once,
+};
+
+use config::{Config, KeyIndex, KeySize};
+use glam::{dvec2, dvec3, DAffine3, DVec2, Vec3Swizzles};
+
+use crate::{
+    geometry::{polygon_contains, OrientedBox},
+    key_positions::{Column, Columns, KeyPositions},
+    keyboard::Keyboard,
+    switch::Switch,
+};
+
+/// The distance above which two keys can never collide.
+const MAXIMUM_COLLISION_DISTANCE: f64 = 60.0;
+/// The overlap tolerated before reporting a collision.
+const COLLISION_TOLERANCE: f64 = 0.01;
+
+/// The severity of a diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The configuration might not work as intended.
+    Warning,
+    /// The configuration results in a keyboard which can not be built.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A key of the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// A finger key given by its column and row index.
+    Finger {
+        /// The index of the column, including the side columns.
+        column: usize,
+        /// The index of the row.
+        row: usize,
+    },
+    /// A thumb key given by its index.
+    Thumb(usize),
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Finger { column, row } => write!(f, "finger key (column {column}, row {row})"),
+            Key::Thumb(index) => write!(f, "thumb key {index}"),
+        }
+    }
+}
+
+impl From<KeyIndex> for Key {
+    fn from(key_index: KeyIndex) -> Self {
+        match key_index {
+            KeyIndex::Finger { column, row } => Key::Finger {
+                column: column.into(),
+                row: row.into(),
+            },
+            KeyIndex::Thumb { thumb } => Key::Thumb(thumb.into()),
+        }
+    }
+}
+
+/// A problem found when validating a configuration.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// The severity of the problem.
+    pub severity: Severity,
+    /// The keys involved in the problem.
+    pub keys: Vec<Key>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Validates the geometry resulting from the given configuration.
+///
+/// Returns the problems found, ordered by their severity with errors first.
+#[must_use]
+pub fn validate(config: &Config) -> Vec<Diagnostic> {
+    let key_positions = KeyPositions::from_config(config);
+    let keys = key_volumes(&key_positions, config);
+
+    let mut diagnostics = Vec::new();
+
+    for (i, first) in keys.iter().enumerate() {
+        for second in &keys[i + 1..] {
+            if first.collides_with(second) {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    keys: vec![first.key, second.key],
+                    message: format!("{} collides with {}", first.key, second.key),
+                });
+            }
+        }
+    }
+
+    let (insert_holders, interface_pcb) = Keyboard::placed_parts(&key_positions, &config.keyboard);
+    let parts = insert_holders
+        .iter()
+        .enumerate()
+        .map(|(index, insert_holder)| {
+            (
+                format!("insert holder {}", index + 1),
+                insert_holder.bounding_box(),
+            )
+        })
+        .chain(once((
+            "the interface PCB".to_owned(),
+            interface_pcb.bounding_box(),
+        )));
+
+    for (name, bounding_box) in parts {
+        for key in keys
+            .iter()
+            .filter(|key| key.collides_with_box(&bounding_box))
+        {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                keys: vec![key.key],
+                message: format!("{} collides with {name}", key.key),
+            });
+        }
+    }
+
+    // The keys are lifted to keep their centers well above the bottom plate, but steeply tilted
+    // keys can still reach below its top with the switch, matrix PCB pad or socket
+    let bottom_plate_top = f64::from(config.keyboard.bottom_plate_thickness);
+    for key in keys
+        .iter()
+        .filter(|key| key.min_z() < bottom_plate_top - COLLISION_TOLERANCE)
+    {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            keys: vec![key.key],
+            message: format!("{} reaches below the top of the bottom plate", key.key),
+        });
+    }
+
+    let finger_cluster_outline = key_positions.columns.outline_points();
+    for (index, position) in key_positions.thumb_keys.iter().enumerate() {
+        if polygon_contains(&finger_cluster_outline, position.translation.xy()) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                keys: vec![Key::Thumb(index)],
+                message: format!("{} is placed inside the finger cluster", Key::Thumb(index)),
+            });
+        }
+    }
+
+    let ffc_column_index = usize::from(config.finger_cluster.columns.left_side_column.active);
+    diagnostics.extend(unconnected_key_diagnostics(
+        &key_positions.columns,
+        ffc_column_index,
+    ));
+
+    for (index, key_override) in config.key_overrides.iter().enumerate() {
+        let key = Key::from(key_override.key);
+        let exists = match key {
+            Key::Finger { column, row } => key_positions
+                .columns
+                .get(column)
+                .is_some_and(|column| row < column.len()),
+            Key::Thumb(index) => index < key_positions.thumb_keys.len(),
+        };
+
+        if !exists {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                keys: Vec::new(),
+                message: format!(
+                    "key override {} is ignored, as there is no {key}",
+                    index + 1
+                ),
+            });
+        }
+    }
+
+    if config.keyboard.hot_swap && !config.keyboard.switch_type.has_hot_swap_socket() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            keys: Vec::new(),
+            message: format!(
+                "hot-swap sockets are ignored, as there are none for {} switches",
+                config.keyboard.switch_type
+            ),
+        });
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity == Severity::Warning);
+
+    diagnostics
+}
+
+/// Returns the volumes of all keys at the given positions.
+fn key_volumes(key_positions: &KeyPositions, config: &Config) -> Vec<KeyVolume> {
+    let switch = &Switch::from(config.keyboard.switch_type);
+    let pad_bottom = -switch.bottom - f64::from(config.fabrication.thickness);
+    let pad_size = switch.matrix_pcb_pad_size(config.keyboard.hot_swap);
+    // Include the matrix PCB pad and the hot-swap socket below the switch
+    let switch_profile: &[_] = &switch
+        .socket(config.keyboard.hot_swap)
+        .map(|socket| (pad_bottom - socket.height, pad_size / 2.0))
+        .into_iter()
+        .chain(once((pad_bottom, pad_size / 2.0)))
+        .chain(switch.profile.iter().copied())
+        .collect::<Vec<_>>();
+    let thumb_keycap_extension = match config.thumb_cluster.key_size {
+        KeySize::U1 => 0.0,
+        KeySize::U1_5 => 0.25 * f64::from(config.thumb_cluster.key_distance),
+    };
+
+    let finger_keys = key_positions
+        .columns
+        .iter()
+        .enumerate()
+        .flat_map(|(column, keys)| {
+            keys.iter().enumerate().map(move |(row, &position)| {
+                KeyVolume::new(
+                    Key::Finger { column, row },
+                    position,
+                    switch,
+                    switch_profile,
+                    0.0,
+                )
+            })
+        });
+    let thumb_keys = key_positions
+        .thumb_keys
+        .iter()
+        .enumerate()
+        .map(|(index, &position)| {
+            KeyVolume::new(
+                Key::Thumb(index),
+                position,
+                switch,
+                switch_profile,
+                thumb_keycap_extension,
+            )
+        });
+
+    finger_keys.chain(thumb_keys).collect()
+}
+
+/// Returns diagnostics for the finger keys the matrix PCB can not connect to the FFC connector.
+///
+/// The rows are connected through the columns between a key and the column with the FFC
+/// connector. The latter therefore requires a key in the same row, while the others can also
+/// route the row past their first or last key.
+fn unconnected_key_diagnostics(
+    columns: &Columns,
+    ffc_column_index: usize,
+) -> impl Iterator<Item = Diagnostic> + '_ {
+    #[allow(clippy::cast_sign_loss)]
+    let matrix_home_row_index = columns.home_row_index as usize;
+    let matrix_rows = move |column: &Column| {
+        let first_row = matrix_home_row_index - column.home_row_index;
+
+        first_row..first_row + column.len()
+    };
+
+    columns
+        .iter()
+        .enumerate()
+        .flat_map(move |(column_index, column)| {
+            let inner_column_indices: Vec<_> = match column_index.cmp(&ffc_column_index) {
+                Ordering::Less => (column_index + 1..ffc_column_index).collect(),
+                Ordering::Equal => Vec::new(),
+                Ordering::Greater => (ffc_column_index + 1..column_index).rev().collect(),
+            };
+
+            matrix_rows(column)
+                .enumerate()
+                .filter(move |_| column_index != ffc_column_index)
+                .filter_map(move |(row, matrix_row)| {
+                    let key = Key::Finger {
+                        column: column_index,
+                        row,
+                    };
+                    let blocking_column_index = inner_column_indices.iter().find(|&&index| {
+                        let rows = matrix_rows(&columns[index]);
+
+                        !rows.contains(&matrix_row)
+                            && matrix_row + 1 != rows.start
+                            && matrix_row != rows.end
+                    });
+
+                    let message = if let Some(index) = blocking_column_index {
+                        format!(
+                            "{key} can not be connected by the matrix PCB, as its row can not be \
+                            routed past column {index}"
+                        )
+                    } else if !matrix_rows(&columns[ffc_column_index]).contains(&matrix_row) {
+                        format!(
+                            "{key} can not be connected by the matrix PCB, as column \
+                            {ffc_column_index} with the FFC connector has no key in the same row"
+                        )
+                    } else {
+                        return None;
+                    };
+
+                    Some(Diagnostic {
+                        severity: Severity::Error,
+                        keys: vec![key],
+                        message,
+                    })
+                })
+        })
+}
+
+/// The volume occupied by a key including the keycap, switch and matrix PCB pad.
+struct KeyVolume {
+    key: Key,
+    position: DAffine3,
+    boxes: Vec<OrientedBox>,
+}
+
+impl KeyVolume {
+    /// Creates the volume of a key at the given position using the given switch profile.
+    ///
+    /// The keycap is extended by the given length along the Y-axis on both sides.
+    fn new(
+        key: Key,
+        position: DAffine3,
+        switch: &Switch,
+        switch_profile: &[(f64, DVec2)],
+        keycap_extension: f64,
+    ) -> Self {
+        let keycap_profile: Vec<_> = switch
+            .keycap_profile
+            .iter()
+            .map(|&(height, half_size)| (height, half_size + dvec2(0.0, keycap_extension)))
+            .collect();
+
+        let boxes = Self::profile_boxes(position, switch_profile, switch.top)
+            .chain(Self::profile_boxes(
+                position,
+                &keycap_profile,
+                switch.keycap_top,
+            ))
+            .collect();
+
+        Self {
+            key,
+            position,
+            boxes,
+        }
+    }
+
+    /// Returns the boxes approximating the given profile at the given position.
+    fn profile_boxes(
+        position: DAffine3,
+        profile: &[(f64, DVec2)],
+        top: f64,
+    ) -> impl Iterator<Item = OrientedBox> + '_ {
+        profile
+            .iter()
+            .enumerate()
+            .map(move |(i, &(bottom, half_size))| {
+                let top = profile.get(i + 1).map_or(top, |&(height, _)| height);
+                let height = top - bottom;
+
+                OrientedBox::new(
+                    position,
+                    dvec3(0.0, 0.0, bottom + height / 2.0),
+                    (2.0 * half_size).extend(height),
+                )
+            })
+    }
+
+    /// Returns true if the volume of `self` collides with the one of `other`.
+    fn collides_with(&self, other: &KeyVolume) -> bool {
+        self.position
+            .translation
+            .distance(other.position.translation)
+            < MAXIMUM_COLLISION_DISTANCE
+            && self.boxes.iter().any(|a| {
+                other
+                    .boxes
+                    .iter()
+                    .any(|b| a.overlaps(b, COLLISION_TOLERANCE))
+            })
+    }
+
+    /// Returns the minimum Z value of the volume of `self`.
+    fn min_z(&self) -> f64 {
+        self.boxes
+            .iter()
+            .flat_map(OrientedBox::corners)
+            .map(|corner| corner.z)
+            .min_by(f64::total_cmp)
+            .unwrap_or(self.position.translation.z)
+    }
+
+    /// Returns true if the volume of `self` collides with the given box.
+    fn collides_with_box(&self, other: &OrientedBox) -> bool {
+        self.boxes
+            .iter()
+            .any(|a| a.overlaps(other, COLLISION_TOLERANCE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::SwitchType;
+
+    use super::*;
+
+    #[test]
+    fn default_hot_swap_configs_are_collision_free() {
+        for switch_type in [SwitchType::Mx, SwitchType::ChocV1, SwitchType::ChocV2] {
+            let mut config = Config::default();
+            config.set_switch_type(switch_type, true);
+
+            assert!(
+                validate(&config).is_empty(),
+                "{switch_type} keyboard with hot-swap sockets should be valid"
+            );
+        }
+    }
+
+    #[test]
+    fn keys_below_the_bottom_plate_are_reported() {
+        let mut config = Config::default();
+        config.keyboard.bottom_plate_thickness = 20.0.try_into().expect("20.0 is positive");
+
+        assert!(validate(&config).iter().any(|diagnostic| diagnostic
+            .message
+            .contains("below the top of the bottom plate")));
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use crate::drc::{overlaps, DesignRuleChecker, ObjectKind, Violation, ViolationKind, TOLERANCE};
+
+impl DesignRuleChecker {
+    /// Checks the connectivity of the added objects and returns the violations.
+    ///
+    /// Objects are connected if they touch on a common copper layer, with through hole pads
+    /// connecting both layers like vias. Touching objects of different nets are reported as
+    /// shorts. Every part of a net not connected to its largest part is reported as an open or,
+    /// if it only consists of a single pad, as an unrouted pad.
+    pub fn check_connectivity(mut self) -> Vec<Violation> {
+        let bounds = self.sort_objects();
+        let mut parents: Vec<_> = (0..self.objects.len()).collect();
+        let mut violations = Vec::new();
+
+        for (index, (object, &(minimum, maximum))) in self.objects.iter().zip(&bounds).enumerate() {
+            for (other_index, (other, &(other_minimum, other_maximum))) in
+                self.objects.iter().zip(&bounds).enumerate().skip(index + 1)
+            {
+                if other_minimum.x > maximum.x + TOLERANCE {
+                    break;
+                }
+                if object.kind == ObjectKind::Hole
+                    || other.kind == ObjectKind::Hole
+                    || object.net.is_none()
+                    || other.net.is_none()
+                    || object.layers & other.layers == 0
+                    || !overlaps(minimum, maximum, other_minimum, other_maximum, TOLERANCE)
+                {
+                    continue;
+                }
+
+                let (distance, position) = object.shape.distance(&other.shape);
+                if distance > TOLERANCE {
+                    continue;
+                }
+
+                if object.net == other.net {
+                    union(&mut parents, index, other_index);
+                } else {
+                    let nets = [object.net.min(other.net), object.net.max(other.net)];
+
+                    self.add_violation(&mut violations, ViolationKind::Short, nets, true, position);
+                }
+            }
+        }
+
+        // Group the objects of every net by the connected part they belong to
+        let mut nets: BTreeMap<u32, BTreeMap<usize, Vec<usize>>> = BTreeMap::new();
+        for (index, object) in self.objects.iter().enumerate() {
+            if let (Some(net), false) = (object.net, object.kind == ObjectKind::Hole) {
+                nets.entry(net)
+                    .or_default()
+                    .entry(find(&mut parents, index))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        for (net, parts) in nets {
+            let Some(largest) = parts.values().max_by_key(|part| part.len()) else {
+                continue;
+            };
+
+            for part in parts.values().filter(|&part| part != largest) {
+                let first = &self.objects[part[0]];
+                let kind = if part.len() == 1 && first.kind == ObjectKind::Pad {
+                    ViolationKind::UnroutedPad
+                } else {
+                    ViolationKind::Open
+                };
+
+                self.add_violation(
+                    &mut violations,
+                    kind,
+                    [Some(net), None],
+                    false,
+                    first.shape.center(),
+                );
+            }
+        }
+
+        violations
+    }
+}
+
+/// Returns the representative of the set containing the given index.
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    let mut current = index;
+    while parents[current] != root {
+        current = std::mem::replace(&mut parents[current], root);
+    }
+
+    root
+}
+
+/// Merges the sets containing the given indices.
+fn union(parents: &mut [usize], first: usize, second: usize) {
+    let first_root = find(parents, first);
+    let second_root = find(parents, second);
+
+    parents[second_root] = first_root;
+}
@@ -0,0 +1,314 @@
+mod connectivity;
+mod shape;
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+use glam::DVec2;
+use model::{Diagnostic, Severity};
+
+use crate::unit::Length;
+
+pub use shape::Shape;
+
+/// The tolerance for distances, absorbing rounding errors.
+const TOLERANCE: f64 = 0.001;
+/// The bit representing the top copper layer.
+const TOP_LAYER: u8 = 0b01;
+/// The bit representing the bottom copper layer.
+const BOTTOM_LAYER: u8 = 0b10;
+
+/// A trait for adding the shapes of objects to a design rule checker.
+pub trait AddShapes {
+    /// Adds the copper and edge shapes of `self` to the given design rule checker.
+    fn add_shapes(&self, checker: &mut DesignRuleChecker);
+}
+
+/// The kind of a copper object.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    /// A track connecting pads.
+    Track,
+    /// A pad of a footprint.
+    Pad,
+    /// A non-plated hole.
+    Hole,
+}
+
+/// A checker for the clearances between the objects of a PCB.
+pub struct DesignRuleChecker {
+    clearance: Length,
+    edge_clearance: Length,
+    net_names: HashMap<u32, String>,
+    objects: Vec<Object>,
+    edges: Vec<Shape>,
+}
+
+impl DesignRuleChecker {
+    /// Creates a new design rule checker using the given clearances and net names.
+    pub fn new(clearance: Length, edge_clearance: Length, net_names: HashMap<u32, String>) -> Self {
+        Self {
+            clearance,
+            edge_clearance,
+            net_names,
+            objects: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds an object with the given shape and net on the given copper layer.
+    ///
+    /// Objects on the `*.Cu` layer are added to all copper layers, others are ignored.
+    pub fn add_object(&mut self, kind: ObjectKind, layer: &str, shape: Shape, net: Option<u32>) {
+        let layers = match layer {
+            "F.Cu" => TOP_LAYER,
+            "B.Cu" => BOTTOM_LAYER,
+            "*.Cu" => TOP_LAYER | BOTTOM_LAYER,
+            _ => return,
+        };
+
+        self.objects.push(Object {
+            kind,
+            layers,
+            net: net.filter(|&net| net != 0),
+            shape,
+        });
+    }
+
+    /// Adds a part of the board edge with the given shape.
+    pub fn add_edge(&mut self, shape: Shape) {
+        self.edges.push(shape);
+    }
+
+    /// Checks the clearances between the added objects and returns the violations.
+    ///
+    /// Violations of the same kind between the same nets are combined.
+    pub fn finish(mut self) -> Vec<Violation> {
+        let clearance = f64::from(self.clearance);
+        let edge_clearance = f64::from(self.edge_clearance);
+
+        let bounds = self.sort_objects();
+        let mut violations = Vec::new();
+
+        for (index, (object, &(minimum, maximum))) in self.objects.iter().zip(&bounds).enumerate() {
+            for (other, &(other_minimum, other_maximum)) in
+                self.objects.iter().zip(&bounds).skip(index + 1)
+            {
+                if other_minimum.x > maximum.x + clearance {
+                    break;
+                }
+                // Holes may overlap each other, merging into a single larger hole
+                if object.layers & other.layers == 0
+                    || object.net.is_some() && object.net == other.net
+                    || object.kind == ObjectKind::Hole && other.kind == ObjectKind::Hole
+                    || !overlaps(minimum, maximum, other_minimum, other_maximum, clearance)
+                {
+                    continue;
+                }
+
+                let (distance, position) = object.shape.distance(&other.shape);
+                if distance < clearance - TOLERANCE {
+                    let (kind, nets) = match (object.kind, other.kind) {
+                        (ObjectKind::Track, ObjectKind::Track) => {
+                            (ViolationKind::Tracks, [object.net, other.net])
+                        }
+                        (ObjectKind::Track, _) => {
+                            (ViolationKind::TrackPad, [object.net, other.net])
+                        }
+                        (_, ObjectKind::Track) => {
+                            (ViolationKind::TrackPad, [other.net, object.net])
+                        }
+                        _ => (ViolationKind::Pads, [object.net, other.net]),
+                    };
+
+                    self.add_violation(&mut violations, kind, nets, distance < 0.0, position);
+                }
+            }
+
+            if object.kind == ObjectKind::Hole {
+                continue;
+            }
+
+            for edge in &self.edges {
+                let (edge_minimum, edge_maximum) = edge.bounds();
+                if !overlaps(minimum, maximum, edge_minimum, edge_maximum, edge_clearance) {
+                    continue;
+                }
+
+                let (distance, position) = object.shape.distance(edge);
+                if distance < edge_clearance - TOLERANCE {
+                    self.add_violation(
+                        &mut violations,
+                        ViolationKind::Edge,
+                        [object.net, None],
+                        distance < 0.0,
+                        position,
+                    );
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Sorts the objects by the minimum X coordinate of their bounds and returns the bounds.
+    fn sort_objects(&mut self) -> Vec<(DVec2, DVec2)> {
+        self.objects.sort_by(|first, second| {
+            first
+                .shape
+                .bounds()
+                .0
+                .x
+                .total_cmp(&second.shape.bounds().0.x)
+        });
+
+        self.objects
+            .iter()
+            .map(|object| object.shape.bounds())
+            .collect()
+    }
+
+    /// Adds a violation of the given kind between the given nets to the violations.
+    ///
+    /// The violation is combined with an existing one of the same kind between the same nets.
+    fn add_violation(
+        &self,
+        violations: &mut Vec<Violation>,
+        kind: ViolationKind,
+        nets: [Option<u32>; 2],
+        overlap: bool,
+        position: DVec2,
+    ) {
+        let nets = nets.map(|net| net.and_then(|net| self.net_names.get(&net)).cloned());
+
+        if let Some(violation) = violations
+            .iter_mut()
+            .find(|violation| violation.kind == kind && violation.nets == nets)
+        {
+            violation.count += 1;
+        } else {
+            violations.push(Violation {
+                kind,
+                nets,
+                clearance: if kind == ViolationKind::Edge {
+                    self.edge_clearance
+                } else {
+                    self.clearance
+                },
+                overlap,
+                position,
+                count: 1,
+            });
+        }
+    }
+}
+
+/// A violation of the design rules.
+pub struct Violation {
+    kind: ViolationKind,
+    nets: [Option<String>; 2],
+    clearance: Length,
+    overlap: bool,
+    position: DVec2,
+    count: usize,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let [first, second] = self.nets.clone().map(|net| match net {
+            Some(name) => format!("net {name}"),
+            None => "no net".to_owned(),
+        });
+        let clearance = f64::from(self.clearance);
+
+        match self.kind {
+            ViolationKind::Tracks => write!(
+                f,
+                "tracks of {first} and {second} are closer than {clearance} mm"
+            )?,
+            ViolationKind::TrackPad => write!(
+                f,
+                "track of {first} is closer than {clearance} mm to a pad of {second}"
+            )?,
+            ViolationKind::Pads if self.overlap => {
+                write!(f, "pads of {first} and {second} overlap")?;
+            }
+            ViolationKind::Pads => write!(
+                f,
+                "pads of {first} and {second} are closer than {clearance} mm"
+            )?,
+            ViolationKind::Edge => write!(
+                f,
+                "copper of {first} is closer than {clearance} mm to the board edge"
+            )?,
+            ViolationKind::Short => write!(f, "{first} and {second} are shorted")?,
+            ViolationKind::Open => write!(
+                f,
+                "copper of {first} is not connected to the rest of the net"
+            )?,
+            ViolationKind::UnroutedPad => {
+                write!(f, "pad of {first} is not connected to the rest of the net")?;
+            }
+        }
+
+        write!(f, " at ({:.2}, {:.2})", self.position.x, self.position.y)?;
+
+        match self.count {
+            1 => Ok(()),
+            2 => write!(f, " and one other location"),
+            count => write!(f, " and {} other locations", count - 1),
+        }
+    }
+}
+
+impl Violation {
+    /// Converts the violation to a diagnostic for the board with the given name.
+    pub fn into_diagnostic(self, board_name: &str) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            keys: Vec::new(),
+            message: format!("{board_name}: {self}"),
+        }
+    }
+}
+
+/// The kind of a design rule violation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ViolationKind {
+    /// Two tracks are too close to each other.
+    Tracks,
+    /// A track is too close to a pad.
+    TrackPad,
+    /// Two pads are too close to each other or overlap.
+    Pads,
+    /// Copper is too close to the board edge.
+    Edge,
+    /// Copper of two different nets touches.
+    Short,
+    /// A part of a net is not connected to the rest of the net.
+    Open,
+    /// A pad is not connected to the rest of its net.
+    UnroutedPad,
+}
+
+/// A copper object of the PCB.
+struct Object {
+    kind: ObjectKind,
+    layers: u8,
+    net: Option<u32>,
+    shape: Shape,
+}
+
+/// Returns true if the given bounding boxes are closer than the given distance.
+fn overlaps(
+    minimum: DVec2,
+    maximum: DVec2,
+    other_minimum: DVec2,
+    other_maximum: DVec2,
+    distance: f64,
+) -> bool {
+    (minimum - distance).cmple(other_maximum).all()
+        && (other_minimum - distance).cmple(maximum).all()
+}
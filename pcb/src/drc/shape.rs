@@ -0,0 +1,248 @@
+use std::f64::consts::TAU;
+
+use glam::{dvec2, DVec2};
+
+use crate::{
+    gerber::circle_center,
+    primitives::{Point, Position},
+    unit::Length,
+};
+
+/// The maximum deviation of the segments approximating an arc from the arc.
+const ARC_TOLERANCE: f64 = 0.0005;
+
+/// A convex polygon expanded by a radius.
+///
+/// Polygons with one or two vertices represent circles and line segments with round ends.
+pub struct Shape {
+    vertices: Vec<DVec2>,
+    radius: f64,
+}
+
+impl Shape {
+    /// Creates a circle with the given center and diameter.
+    pub fn circle(center: Point, diameter: Length) -> Self {
+        Self {
+            vertices: vec![to_vector(center)],
+            radius: f64::from(diameter) / 2.0,
+        }
+    }
+
+    /// Creates a line segment with the given width.
+    pub fn segment(start: Point, end: Point, width: Length) -> Self {
+        Self {
+            vertices: vec![to_vector(start), to_vector(end)],
+            radius: f64::from(width) / 2.0,
+        }
+    }
+
+    /// Creates line segments with the given width approximating the arc through the given points.
+    pub fn arc(start: Point, mid: Point, end: Point, width: Length) -> Vec<Self> {
+        let start_vector = to_vector(start);
+        let mid_vector = to_vector(mid);
+        let end_vector = to_vector(end);
+
+        let Some(center) = circle_center(start_vector, mid_vector, end_vector) else {
+            return vec![Self::segment(start, end, width)];
+        };
+
+        let radius = (start_vector - center).length();
+        let start_angle = (start_vector - center).to_angle();
+        let mid_angle = (mid_vector - center).to_angle();
+        let end_angle = (end_vector - center).to_angle();
+
+        // The arc runs from the start to the end angle passing the mid angle
+        let mut sweep = (end_angle - start_angle).rem_euclid(TAU);
+        if (mid_angle - start_angle).rem_euclid(TAU) > sweep {
+            sweep -= TAU;
+        }
+
+        let maximum_step = 2.0 * (1.0 - ARC_TOLERANCE / radius).clamp(-1.0, 1.0).acos();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = ((sweep.abs() / maximum_step).ceil() as usize).max(1);
+
+        #[allow(clippy::cast_precision_loss)]
+        let points: Vec<_> = (0..=count)
+            .map(|index| {
+                let angle = start_angle + sweep * index as f64 / count as f64;
+
+                center + radius * DVec2::from_angle(angle)
+            })
+            .collect();
+
+        points
+            .windows(2)
+            .map(|window| Self {
+                vertices: window.to_vec(),
+                radius: f64::from(width) / 2.0,
+            })
+            .collect()
+    }
+
+    /// Creates a rectangle with rounded corners centered at the given position.
+    pub fn rounded_rectangle(
+        position: Position,
+        width: Length,
+        height: Length,
+        radius: Length,
+    ) -> Self {
+        let inner_width = (width - 2 * radius) / 2;
+        let inner_height = (height - 2 * radius) / 2;
+
+        let vertices = [
+            (inner_width, inner_height),
+            (-inner_width, inner_height),
+            (-inner_width, -inner_height),
+            (inner_width, -inner_height),
+        ]
+        .into_iter()
+        .map(|(x, y)| to_vector(position.transform_point(Point::new(x, y))))
+        .collect();
+
+        Self {
+            vertices,
+            radius: f64::from(radius),
+        }
+    }
+
+    /// Returns the minimum and maximum corner of the bounding box of the shape.
+    pub fn bounds(&self) -> (DVec2, DVec2) {
+        let (minimum, maximum) = self.vertices.iter().fold(
+            (DVec2::INFINITY, DVec2::NEG_INFINITY),
+            |(minimum, maximum), &vertex| (minimum.min(vertex), maximum.max(vertex)),
+        );
+
+        (minimum - self.radius, maximum + self.radius)
+    }
+
+    /// Returns the center of the shape given by the mean of its vertices.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn center(&self) -> DVec2 {
+        self.vertices.iter().sum::<DVec2>() / self.vertices.len() as f64
+    }
+
+    /// Returns the distance to the other shape and the point halfway between the closest points.
+    ///
+    /// The distance is zero if the shapes touch and negative if they overlap.
+    pub fn distance(&self, other: &Shape) -> (f64, DVec2) {
+        let radii = self.radius + other.radius;
+
+        if let Some(&vertex) = other.vertices.iter().find(|&&vertex| self.contains(vertex)) {
+            return (-radii, vertex);
+        }
+        if let Some(&vertex) = self.vertices.iter().find(|&&vertex| other.contains(vertex)) {
+            return (-radii, vertex);
+        }
+
+        let (distance, first, second) = self
+            .edges()
+            .flat_map(|edge| other.edges().map(move |other_edge| (edge, other_edge)))
+            .map(|((start, end), (other_start, other_end))| {
+                segment_distance(start, end, other_start, other_end)
+            })
+            .min_by(|(first, ..), (second, ..)| first.total_cmp(second))
+            .expect("shapes should always have at least one vertex");
+
+        (distance - radii, (first + second) / 2.0)
+    }
+
+    /// Returns the edges of the polygon, which are degenerated for circles.
+    fn edges(&self) -> impl Iterator<Item = (DVec2, DVec2)> + '_ {
+        let count = self.vertices.len();
+
+        (0..count).map(move |index| (self.vertices[index], self.vertices[(index + 1) % count]))
+    }
+
+    /// Returns true if the given point lies strictly inside the polygon.
+    fn contains(&self, point: DVec2) -> bool {
+        if self.vertices.len() < 3 {
+            return false;
+        }
+
+        let signs: Vec<_> = self
+            .edges()
+            .map(|(start, end)| (end - start).perp_dot(point - start))
+            .collect();
+
+        signs.iter().all(|&sign| sign > 0.0) || signs.iter().all(|&sign| sign < 0.0)
+    }
+}
+
+/// Converts the given point to a vector.
+fn to_vector(point: Point) -> DVec2 {
+    dvec2(f64::from(point.x()), f64::from(point.y()))
+}
+
+/// Returns the distance between the given line segments and the closest points on them.
+fn segment_distance(
+    first_start: DVec2,
+    first_end: DVec2,
+    second_start: DVec2,
+    second_end: DVec2,
+) -> (f64, DVec2, DVec2) {
+    if let Some(intersection) =
+        segment_intersection(first_start, first_end, second_start, second_end)
+    {
+        return (0.0, intersection, intersection);
+    }
+
+    [
+        (
+            first_start,
+            closest_point(second_start, second_end, first_start),
+        ),
+        (
+            first_end,
+            closest_point(second_start, second_end, first_end),
+        ),
+        (
+            closest_point(first_start, first_end, second_start),
+            second_start,
+        ),
+        (
+            closest_point(first_start, first_end, second_end),
+            second_end,
+        ),
+    ]
+    .into_iter()
+    .map(|(first, second)| (first.distance(second), first, second))
+    .min_by(|(first, ..), (second, ..)| first.total_cmp(second))
+    .expect("there are always four candidates")
+}
+
+/// Returns the point on the given line segment closest to the given point.
+fn closest_point(start: DVec2, end: DVec2, point: DVec2) -> DVec2 {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+
+    if length_squared == 0.0 {
+        return start;
+    }
+
+    let parameter = (direction.dot(point - start) / length_squared).clamp(0.0, 1.0);
+
+    start + parameter * direction
+}
+
+/// Returns the intersection of the given line segments if they properly cross each other.
+fn segment_intersection(
+    first_start: DVec2,
+    first_end: DVec2,
+    second_start: DVec2,
+    second_end: DVec2,
+) -> Option<DVec2> {
+    let first_direction = first_end - first_start;
+    let second_direction = second_end - second_start;
+    let denominator = first_direction.perp_dot(second_direction);
+
+    if denominator.abs() <= f64::EPSILON {
+        return None;
+    }
+
+    let offset = second_start - first_start;
+    let first_parameter = offset.perp_dot(second_direction) / denominator;
+    let second_parameter = offset.perp_dot(first_direction) / denominator;
+
+    ((0.0..=1.0).contains(&first_parameter) && (0.0..=1.0).contains(&second_parameter))
+        .then(|| first_start + first_parameter * first_direction)
+}
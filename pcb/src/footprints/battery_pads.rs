@@ -0,0 +1,105 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// Pads for soldering the wires of a battery.
+pub struct BatteryPads {
+    reference: String,
+    position: Position,
+    positive_net: Net,
+    negative_net: Net,
+}
+
+impl BatteryPads {
+    /// The position of the positive pad.
+    pub const POSITIVE_PAD: Point = Point::new(Length::new(0.0), Length::new(0.0));
+    /// The position of the negative pad.
+    pub const NEGATIVE_PAD: Point = Point::new(Length::new(0.0), Length::new(2.54));
+
+    /// Creates new battery pads at the given position using the given nets.
+    pub fn new(
+        reference: String,
+        position: Position,
+        positive_net: Net,
+        negative_net: Net,
+    ) -> Self {
+        Self {
+            reference,
+            position,
+            positive_net,
+            negative_net,
+        }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let wire_drill_diameter = 1.into();
+
+        vec![
+            Pad::new(
+                "1",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::POSITIVE_PAD.x(), Self::POSITIVE_PAD.y(), angle),
+                size!(1.7, 1.7),
+                wire_drill_diameter,
+                Some(self.positive_net),
+            ),
+            Pad::new(
+                "2",
+                PadType::ThruHole,
+                PadShape::Circle,
+                position!(Self::NEGATIVE_PAD.x(), Self::NEGATIVE_PAD.y(), angle),
+                size!(1.7, 1.7),
+                wire_drill_diameter,
+                Some(self.negative_net),
+            ),
+        ]
+    }
+}
+
+impl From<BatteryPads> for Footprint {
+    fn from(battery_pads: BatteryPads) -> Self {
+        let angle = battery_pads.position.angle();
+
+        Self(
+            "battery_pads_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: battery_pads.position,
+                descr: "Battery wire pads dual side".to_owned(),
+                tags: "battery wire pads dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        battery_pads.reference.clone(),
+                        position!(-2.5, 0, angle),
+                    ),
+                    Property::new("Value", "Battery".to_owned(), position!(-2.5, 2.54, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-1.1, -1.1), point!(2.7, -1.1), "CrtYd"),
+                    (point!(2.7, -1.1), point!(2.7, 3.64), "CrtYd"),
+                    (point!(2.7, 3.64), point!(-1.1, 3.64), "CrtYd"),
+                    (point!(-1.1, 3.64), point!(-1.1, -1.1), "CrtYd"),
+                    (point!(1.9, -0.6), point!(1.9, 0.6), "SilkS"),
+                    (point!(1.3, 0), point!(2.5, 0), "SilkS"),
+                    (point!(1.3, 2.54), point!(2.5, 2.54), "SilkS"),
+                ],
+                fp_texts: Vec::new(),
+                pads: battery_pads.pads(),
+            },
+        )
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
-    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property, Text},
     kicad_pcb::Net,
     lines_front_back, point, position,
-    primitives::{Position, Uuid},
+    primitives::{Point, Position, Uuid},
     size,
+    unit::Length,
 };
 
 /// An FFC connector.
@@ -23,6 +24,12 @@ impl FfcConnector {
         }
     }
 
+    /// Returns the position of the signal pad with the given index relative to the connector.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn pad_position(index: usize) -> Point {
+        Point::new((index as f32 - 5.5) * Length::new(1.0), 0.into())
+    }
+
     #[allow(clippy::too_many_lines, clippy::similar_names)]
     fn pads(self) -> Vec<Pad> {
         let angle = self.position.angle();
@@ -166,13 +173,13 @@ impl From<FfcConnector> for Footprint {
         let angle = ffc_connector.position.angle();
 
         Self(
-            "ffc_connector_dual_side",
+            "ffc_connector_dual_side".to_owned(),
             FootprintSettings {
                 layer: "F.Cu",
-                uuid: Uuid::new(),
+                uuid: Uuid::default(),
                 at: ffc_connector.position,
-                descr: "FFC connector dual side",
-                tags: "FFC connector dual side",
+                descr: "FFC connector dual side".to_owned(),
+                tags: "FFC connector dual side".to_owned(),
                 properties: vec![
                     Property::new(
                         "Reference",
@@ -188,6 +195,7 @@ impl From<FfcConnector> for Footprint {
                     Property::new("Datasheet", String::new(), position!(0, 0, angle)),
                     Property::new("Description", String::new(), position!(0, 0, angle)),
                 ],
+                path: None,
                 attr: Attribute::ThroughHole,
                 fp_lines: lines_front_back![
                     (point!(-9.8, -1.2), point!(-9.8, 6.6), "CrtYd"),
@@ -200,7 +208,7 @@ impl From<FfcConnector> for Footprint {
                     (point!(9.5, 6.3), point!(-9.5, 6.3), "Fab"),
                     (point!(9.5, 6.3), point!(9.5, 0.484), "Fab"),
                 ],
-                fp_texts: Vec::new(),
+                fp_texts: Text::silkscreen("1", position!(-5.5, -2.0, angle)).into(),
                 pads: ffc_connector.pads(),
             },
         )
@@ -1,42 +1,232 @@
+mod battery_pads;
 mod ffc_connector;
+mod mouse_bites;
+mod pro_micro;
+mod resistor;
+mod rp2040_zero;
+mod slide_switch;
 mod switch;
 mod tab;
+mod trrs_jack;
+mod zener_diode;
 
 use serde::Serialize;
 
 use crate::{
-    kicad_pcb::Net,
-    primitives::{Point, Position, Size, Uuid},
+    drc::{AddShapes, DesignRuleChecker, ObjectKind, Shape},
+    gerber::{Aperture, Plot, Plotter},
+    kicad_pcb::{Expression, Net, Parse, ParseError},
+    primitives::{Point, Position, Size, Uuid, UuidGenerator},
     size,
     unit::Length,
 };
 
+pub use battery_pads::BatteryPads;
 pub use ffc_connector::FfcConnector;
-pub use switch::{
-    Switch, ABOVE_ROW_PAD, BELOW_ROW_PAD, LEFT_OF_ROW_PAD, LOWER_COLUMN_PAD, ROW_PAD,
-    UPPER_COLUMN_PAD,
-};
+pub use mouse_bites::MouseBites;
+pub use pro_micro::ProMicro;
+pub use resistor::Resistor;
+pub use rp2040_zero::Rp2040Zero;
+pub use slide_switch::SlideSwitch;
+pub use switch::{Routing, Switch, ROW_PAD};
 pub use tab::Tab;
+pub use trrs_jack::TrrsJack;
+pub use zener_diode::ZenerDiode;
 
 #[derive(Serialize)]
-pub struct Footprint(&'static str, FootprintSettings);
+pub struct Footprint(String, FootprintSettings);
+
+impl Footprint {
+    /// Returns the name of the footprint.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the position of the footprint.
+    pub fn position(&self) -> Position {
+        self.1.at
+    }
+
+    /// Links the footprint to the schematic symbol with the given path.
+    pub fn set_path(&mut self, path: String) {
+        self.1.path = Some(path);
+    }
+
+    /// Replaces the UUIDs of the footprint and its elements with ones created by the given
+    /// generator.
+    pub fn assign_uuids(&mut self, uuids: &mut UuidGenerator) {
+        let Self(name, settings) = self;
+        let uuid = uuids.generate("footprint", &(name.as_str(), settings.at));
+
+        settings.uuid = uuid;
+        for Property(key, _, settings) in &mut settings.properties {
+            settings.uuid = uuids.generate("property", &(uuid, key.as_str()));
+        }
+        for line in &mut settings.fp_lines {
+            line.uuid = uuids.generate("fp_line", &(uuid, line.start, line.end, line.layer));
+        }
+        for Text(_, text, settings) in &mut settings.fp_texts {
+            settings.uuid = uuids.generate("fp_text", &(uuid, text.as_str(), settings.at));
+        }
+        for Pad(name, _, _, settings, _, _, pad_uuid) in &mut settings.pads {
+            pad_uuid.uuid = uuids.generate("pad", &(uuid, name.as_str(), settings.at));
+        }
+    }
+
+    /// Returns a copy of the footprint moved by the given offset.
+    ///
+    /// The nets of the pads are replaced using the given function and the link to the schematic
+    /// symbol is removed.
+    pub fn moved(&self, offset: Point, map_net: impl Fn(&Net) -> Net) -> Self {
+        let Self(name, settings) = self;
+        let at = settings.at;
+
+        Self(
+            name.clone(),
+            FootprintSettings {
+                layer: settings.layer,
+                uuid: Uuid::default(),
+                at: Position::new(at.x() + offset.x(), at.y() + offset.y(), at.angle()),
+                descr: settings.descr.clone(),
+                tags: settings.tags.clone(),
+                properties: settings
+                    .properties
+                    .iter()
+                    .map(|Property(key, value, settings)| {
+                        Property::new(key, value.clone(), settings.at)
+                    })
+                    .collect(),
+                path: None,
+                attr: settings.attr,
+                fp_lines: settings
+                    .fp_lines
+                    .iter()
+                    .map(|line| Line::new(line.start, line.end, line.layer))
+                    .collect(),
+                fp_texts: settings
+                    .fp_texts
+                    .iter()
+                    .map(|Text(_, text, settings)| Text::new(text, settings.at, settings.layer))
+                    .collect(),
+                pads: settings
+                    .pads
+                    .iter()
+                    .map(|pad @ Pad(.., net_settings, _)| {
+                        pad.with_net(
+                            net_settings
+                                .as_ref()
+                                .map(|net_settings| map_net(&net_settings.net)),
+                        )
+                    })
+                    .collect(),
+            },
+        )
+    }
+}
+
+impl Parse for Footprint {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let attr = expression.child("attr")?;
+        let attr = match attr.values().collect::<Vec<_>>().join(" ").as_str() {
+            "through_hole" => Attribute::ThroughHole,
+            "board_only exclude_from_pos_files exclude_from_bom allow_missing_courtyard" => {
+                Attribute::Marker
+            }
+            value => return Err(attr.invalid_value(value)),
+        };
+        let optional_value = |name| {
+            expression
+                .optional_child(name)
+                .map(|child| child.value(0).map(str::to_owned))
+                .transpose()
+        };
+
+        Ok(Self(
+            expression.value(0)?.to_owned(),
+            FootprintSettings {
+                layer: expression.layer()?,
+                uuid: expression.uuid()?,
+                at: expression.position()?,
+                descr: optional_value("descr")?.unwrap_or_default(),
+                tags: optional_value("tags")?.unwrap_or_default(),
+                properties: expression.parse_children("property")?,
+                path: optional_value("path")?,
+                attr,
+                fp_lines: expression.parse_children("fp_line")?,
+                fp_texts: expression.parse_children("fp_text")?,
+                pads: expression.parse_children("pad")?,
+            },
+        ))
+    }
+}
+
+impl Plot for Footprint {
+    fn plot(&self, plotter: &mut Plotter) {
+        let Self(_, settings) = self;
+        let position = settings.at;
+
+        for line in &settings.fp_lines {
+            plotter.line(
+                line.layer,
+                position + line.start,
+                position + line.end,
+                line.stroke.width,
+            );
+        }
+
+        // The angle of footprint texts is absolute, while their position is relative
+        for Text(_, text, text_settings) in &settings.fp_texts {
+            let font = &text_settings.effects.font;
+            let text_position = position + text_settings.at.point();
+
+            plotter.text(
+                text_settings.layer,
+                text,
+                Position::new(
+                    text_position.x(),
+                    text_position.y(),
+                    text_settings.at.angle(),
+                ),
+                font.size,
+                font.thickness,
+                text_settings.effects.justify.is_some(),
+            );
+        }
+
+        for pad in &settings.pads {
+            pad.plot(position, plotter);
+        }
+    }
+}
+
+impl AddShapes for Footprint {
+    fn add_shapes(&self, checker: &mut DesignRuleChecker) {
+        let Self(_, settings) = self;
+
+        for pad in &settings.pads {
+            pad.add_shapes(settings.at, checker);
+        }
+    }
+}
 
 #[derive(Serialize)]
 struct FootprintSettings {
     layer: &'static str,
     uuid: Uuid,
     at: Position,
-    descr: &'static str,
-    tags: &'static str,
+    descr: String,
+    tags: String,
     #[serde(rename = "property_")]
     properties: Vec<Property>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
     attr: Attribute,
     fp_lines: Vec<Line>,
     fp_texts: Vec<Text>,
     pads: Vec<Pad>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Copy)]
 enum Attribute {
     #[serde(rename = "through_hole")]
     ThroughHole,
@@ -45,24 +235,39 @@ enum Attribute {
 }
 
 #[derive(Serialize)]
-struct Property(&'static str, String, PropertySettings);
+struct Property(String, String, PropertySettings);
 
 impl Property {
     /// Creates a new property at the given position.
-    fn new(key: &'static str, value: String, position: Position) -> Self {
+    fn new(key: &str, value: String, position: Position) -> Self {
         Self(
-            key,
+            key.to_owned(),
             value,
             PropertySettings {
                 at: position,
                 layer: "F.Fab",
-                uuid: Uuid::new(),
+                uuid: Uuid::default(),
                 effects: Effects::default(),
             },
         )
     }
 }
 
+impl Parse for Property {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self(
+            expression.value(0)?.to_owned(),
+            expression.value(1)?.to_owned(),
+            PropertySettings {
+                at: expression.position()?,
+                layer: expression.layer()?,
+                uuid: expression.uuid()?,
+                effects: Effects::parse(expression.child("effects")?)?,
+            },
+        ))
+    }
+}
+
 #[derive(Serialize)]
 struct PropertySettings {
     at: Position,
@@ -74,6 +279,29 @@ struct PropertySettings {
 #[derive(Serialize, Default)]
 struct Effects {
     font: Font,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justify: Option<Justify>,
+}
+
+impl Parse for Effects {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let font = expression.child("font")?;
+        let justify = match expression.optional_child("justify") {
+            Some(justify) => match justify.value(0)? {
+                "mirror" => Some(Justify::Mirror),
+                value => return Err(justify.invalid_value(value)),
+            },
+            None => None,
+        };
+
+        Ok(Self {
+            font: Font {
+                size: font.size()?,
+                thickness: font.child("thickness")?.length(0)?,
+            },
+            justify,
+        })
+    }
 }
 
 #[derive(Serialize)]
@@ -91,6 +319,12 @@ impl Default for Font {
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Justify {
+    Mirror,
+}
+
 #[derive(Serialize)]
 struct Line {
     start: Point,
@@ -118,11 +352,32 @@ impl Line {
                 stroke_type: StrokeType::Solid,
             },
             layer,
-            uuid: Uuid::new(),
+            uuid: Uuid::default(),
         }
     }
 }
 
+impl Parse for Line {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let stroke = expression.child("stroke")?;
+        let stroke_type = stroke.child("type")?;
+
+        Ok(Self {
+            start: expression.point("start")?,
+            end: expression.point("end")?,
+            stroke: Stroke {
+                width: stroke.child("width")?.length(0)?,
+                stroke_type: match stroke_type.value(0)? {
+                    "solid" => StrokeType::Solid,
+                    value => return Err(stroke_type.invalid_value(value)),
+                },
+            },
+            layer: expression.layer()?,
+            uuid: expression.uuid()?,
+        })
+    }
+}
+
 #[derive(Serialize)]
 struct Stroke {
     width: Length,
@@ -137,22 +392,52 @@ enum StrokeType {
 }
 
 #[derive(Serialize)]
-struct Text(TextType, &'static str, TextSettings);
+struct Text(TextType, String, TextSettings);
 
 impl Text {
-    /// Creates a new user text at the given position.
-    fn new(text: &'static str, position: Position) -> Self {
+    /// Creates a new user text at the given position on the given layer.
+    ///
+    /// Texts on the bottom layers are mirrored.
+    fn new(text: &str, position: Position, layer: &'static str) -> Self {
         Self(
             TextType::User,
-            text,
+            text.to_owned(),
             TextSettings {
                 at: position,
-                layer: "Dwgs.User",
-                uuid: Uuid::new(),
-                effects: Effects::default(),
+                layer,
+                uuid: Uuid::default(),
+                effects: Effects {
+                    font: Font::default(),
+                    justify: layer.starts_with("B.").then_some(Justify::Mirror),
+                },
             },
         )
     }
+
+    /// Creates a user text at the given position on both the front and back silkscreen.
+    fn silkscreen(text: &str, position: Position) -> [Self; 2] {
+        ["F.SilkS", "B.SilkS"].map(|layer| Self::new(text, position, layer))
+    }
+}
+
+impl Parse for Text {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let text_type = match expression.value(0)? {
+            "user" => TextType::User,
+            value => return Err(expression.invalid_value(value)),
+        };
+
+        Ok(Self(
+            text_type,
+            expression.value(1)?.to_owned(),
+            TextSettings {
+                at: expression.position()?,
+                layer: expression.layer()?,
+                uuid: expression.uuid()?,
+                effects: Effects::parse(expression.child("effects")?)?,
+            },
+        ))
+    }
 }
 
 #[derive(Serialize)]
@@ -171,7 +456,7 @@ struct TextSettings {
 
 #[derive(Serialize)]
 struct Pad(
-    &'static str,
+    String,
     PadType,
     PadShape,
     PadSettings,
@@ -183,7 +468,7 @@ struct Pad(
 impl Pad {
     /// Creates a new pad.
     fn new(
-        name: &'static str,
+        name: &str,
         pad_type: PadType,
         shape: PadShape,
         position: Position,
@@ -200,31 +485,233 @@ impl Pad {
         };
 
         Self(
-            name,
+            name.to_owned(),
             pad_type,
             shape,
             PadSettings {
                 at: position,
                 size,
-                drill,
+                drill: Some(drill),
                 layers: ("*.Cu", "*.Mask"),
                 remove_unused_layers: false,
             },
             roundrect_settings,
             net.map(|net| NetSettings { net }),
-            PadUuid { uuid: Uuid::new() },
+            PadUuid {
+                uuid: Uuid::default(),
+            },
+        )
+    }
+
+    /// Creates a new surface mount pad on the given copper layer.
+    fn smd(
+        name: &str,
+        position: Position,
+        size: Size,
+        layer: &'static str,
+        net: Option<Net>,
+    ) -> Self {
+        let mask_layer = if layer == "F.Cu" { "F.Mask" } else { "B.Mask" };
+
+        Self(
+            name.to_owned(),
+            PadType::Smd,
+            PadShape::Roundrect,
+            PadSettings {
+                at: position,
+                size,
+                drill: None,
+                layers: (layer, mask_layer),
+                remove_unused_layers: false,
+            },
+            Some(RoundRectSettings {
+                roundrect_rratio: 0.25.into(),
+            }),
+            net.map(|net| NetSettings { net }),
+            PadUuid {
+                uuid: Uuid::default(),
+            },
+        )
+    }
+
+    /// Returns a copy of the pad connected to the given net.
+    fn with_net(&self, net: Option<Net>) -> Self {
+        let Self(name, pad_type, shape, settings, roundrect_settings, ..) = self;
+
+        Self(
+            name.clone(),
+            *pad_type,
+            *shape,
+            PadSettings {
+                at: settings.at,
+                size: settings.size,
+                drill: settings.drill,
+                layers: settings.layers,
+                remove_unused_layers: settings.remove_unused_layers,
+            },
+            roundrect_settings
+                .as_ref()
+                .map(|settings| RoundRectSettings {
+                    roundrect_rratio: settings.roundrect_rratio,
+                }),
+            net.map(|net| NetSettings { net }),
+            PadUuid {
+                uuid: Uuid::default(),
+            },
         )
     }
+
+    /// Plots the pad of a footprint at the given position.
+    fn plot(&self, footprint_position: Position, plotter: &mut Plotter) {
+        let Self(_, pad_type, shape, settings, roundrect_settings, ..) = self;
+
+        let position = footprint_position + settings.at.point();
+        let width = settings.size.width();
+        let height = settings.size.height();
+        let angle = settings.at.angle().unwrap_or_default();
+
+        let radius = match shape {
+            PadShape::Circle => None,
+            PadShape::Oval => Some(width.min(height) / 2),
+            PadShape::Roundrect => Some(
+                roundrect_settings
+                    .as_ref()
+                    .map(|settings| f64::from(settings.roundrect_rratio) * width.min(height))
+                    .unwrap_or_default(),
+            ),
+        };
+        let aperture = match radius {
+            Some(radius) => Aperture::RoundedRectangle {
+                width,
+                height,
+                radius,
+                angle,
+            },
+            None => Aperture::Circle(width),
+        };
+
+        match pad_type {
+            PadType::NpThruHole | PadType::ThruHole => {
+                let plated = matches!(pad_type, PadType::ThruHole);
+
+                if plated {
+                    plotter.flash("F.Cu", position, aperture);
+                    plotter.flash("B.Cu", position, aperture);
+                }
+                plotter.flash("F.Mask", position, aperture);
+                plotter.flash("B.Mask", position, aperture);
+                if let Some(drill) = settings.drill {
+                    plotter.drill(position, drill, plated);
+                }
+            }
+            PadType::Smd => {
+                plotter.flash(settings.layers.0, position, aperture);
+                plotter.flash(settings.layers.1, position, aperture);
+            }
+        }
+    }
+
+    /// Adds the shape of the pad of a footprint at the given position to the design rule checker.
+    fn add_shapes(&self, footprint_position: Position, checker: &mut DesignRuleChecker) {
+        let Self(_, pad_type, shape, settings, roundrect_settings, net_settings, _) = self;
+
+        let position = footprint_position + settings.at.point();
+        let position = Position::new(position.x(), position.y(), settings.at.angle());
+        let width = settings.size.width();
+        let height = settings.size.height();
+
+        let shape = match shape {
+            PadShape::Circle => Shape::circle(position.point(), width),
+            PadShape::Oval => {
+                Shape::rounded_rectangle(position, width, height, width.min(height) / 2)
+            }
+            PadShape::Roundrect => Shape::rounded_rectangle(
+                position,
+                width,
+                height,
+                roundrect_settings
+                    .as_ref()
+                    .map(|settings| f64::from(settings.roundrect_rratio) * width.min(height))
+                    .unwrap_or_default(),
+            ),
+        };
+        let kind = match pad_type {
+            PadType::ThruHole | PadType::Smd => ObjectKind::Pad,
+            PadType::NpThruHole => ObjectKind::Hole,
+        };
+        let net = net_settings
+            .as_ref()
+            .map(|net_settings| net_settings.net.id());
+
+        checker.add_object(kind, settings.layers.0, shape, net);
+    }
 }
 
-#[derive(Serialize)]
+impl Parse for Pad {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let pad_type = match expression.value(1)? {
+            "thru_hole" => PadType::ThruHole,
+            "np_thru_hole" => PadType::NpThruHole,
+            "smd" => PadType::Smd,
+            value => return Err(expression.invalid_value(value)),
+        };
+        let shape = match expression.value(2)? {
+            "circle" => PadShape::Circle,
+            "oval" => PadShape::Oval,
+            "roundrect" => PadShape::Roundrect,
+            value => return Err(expression.invalid_value(value)),
+        };
+        let layers = expression.child("layers")?;
+        let remove_unused_layers = expression
+            .optional_child("remove_unused_layers")
+            .map(|child| child.bool(0))
+            .transpose()?
+            .unwrap_or_default();
+        let roundrect_settings = expression
+            .optional_child("roundrect_rratio")
+            .map(|child| {
+                child
+                    .length(0)
+                    .map(|roundrect_rratio| RoundRectSettings { roundrect_rratio })
+            })
+            .transpose()?;
+        let net_settings = expression
+            .optional_child("net")
+            .map(|child| Net::parse(child).map(|net| NetSettings { net }))
+            .transpose()?;
+
+        Ok(Self(
+            expression.value(0)?.to_owned(),
+            pad_type,
+            shape,
+            PadSettings {
+                at: expression.position()?,
+                size: expression.size()?,
+                drill: expression
+                    .optional_child("drill")
+                    .map(|child| child.length(0))
+                    .transpose()?,
+                layers: (layers.layer_value(0)?, layers.layer_value(1)?),
+                remove_unused_layers,
+            },
+            roundrect_settings,
+            net_settings,
+            PadUuid {
+                uuid: expression.uuid()?,
+            },
+        ))
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 enum PadType {
     NpThruHole,
+    Smd,
     ThruHole,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 enum PadShape {
     Circle,
@@ -236,7 +723,8 @@ enum PadShape {
 struct PadSettings {
     at: Position,
     size: Size,
-    drill: Length,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drill: Option<Length>,
     layers: (&'static str, &'static str),
     remove_unused_layers: bool,
 }
@@ -0,0 +1,78 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    position,
+    primitives::{Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// A row of non-plated holes along the X axis for breaking off a tab.
+pub struct MouseBites {
+    position: Position,
+    count: usize,
+}
+
+impl MouseBites {
+    /// The diameter of the holes.
+    pub const DIAMETER: Length = Length::new(0.5);
+    /// The distance between the centers of adjacent holes.
+    pub const SPACING: Length = Length::new(0.8);
+
+    /// Creates new mouse bites centered at the given position spanning the given width.
+    pub fn new(position: Position, width: Length) -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = (f32::from(width) / f32::from(Self::SPACING)) as usize + 1;
+
+        Self { position, count }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+
+        (0..self.count)
+            .map(|index| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = (index as f32 - (self.count - 1) as f32 / 2.0) * Self::SPACING;
+
+                Pad::new(
+                    "",
+                    PadType::NpThruHole,
+                    PadShape::Circle,
+                    position!(x, 0, angle),
+                    size!(Self::DIAMETER, Self::DIAMETER),
+                    Self::DIAMETER,
+                    None,
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<MouseBites> for Footprint {
+    fn from(mouse_bites: MouseBites) -> Self {
+        let angle = mouse_bites.position.angle();
+
+        Self(
+            "mouse_bites".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: mouse_bites.position,
+                descr: "Mouse bites for breaking off a tab".to_owned(),
+                tags: "mouse bites tab".to_owned(),
+                properties: vec![
+                    Property::new("Reference", String::new(), position!(0, 0, angle)),
+                    Property::new("Value", "Mouse_Bites".to_owned(), position!(0, 0, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::Marker,
+                fp_lines: Vec::new(),
+                fp_texts: Vec::new(),
+                pads: mouse_bites.pads(),
+            },
+        )
+    }
+}
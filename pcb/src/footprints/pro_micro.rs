@@ -0,0 +1,141 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// The names of the pins in the left column from the top to the bottom.
+const LEFT_PINS: [&str; 12] = [
+    "1", "0", "GND", "GND", "2", "3", "4", "5", "6", "7", "8", "9",
+];
+/// The names of the pins in the right column from the top to the bottom.
+const RIGHT_PINS: [&str; 12] = [
+    "RAW", "GND", "RST", "VCC", "21", "20", "19", "18", "15", "14", "16", "10",
+];
+
+/// A controller with the Pro Micro footprint.
+pub struct ProMicro {
+    reference: String,
+    position: Position,
+    nets: Vec<(&'static str, Net)>,
+}
+
+impl ProMicro {
+    /// The distance between adjacent pins in a column.
+    const PITCH: Length = Length::new(2.54);
+    /// The distance between the two columns of pins.
+    const COLUMN_DISTANCE: Length = Length::new(15.24);
+
+    /// Creates a new controller at the given position with the pins connected to the given nets.
+    ///
+    /// Pins without a net are left unconnected.
+    pub fn new(reference: String, position: Position, nets: Vec<(&'static str, Net)>) -> Self {
+        Self {
+            reference,
+            position,
+            nets,
+        }
+    }
+
+    /// Returns the position of the first pin with the given name relative to the controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no pin with the given name.
+    pub fn pin_position(name: &str) -> Point {
+        Self::pins()
+            .find_map(|(pin, position)| (pin == name).then_some(position))
+            .expect("pin should exist on a Pro Micro")
+    }
+
+    /// Returns the positions of all pins with the given name relative to the controller.
+    pub fn pin_positions(name: &str) -> impl Iterator<Item = Point> + '_ {
+        Self::pins().filter_map(move |(pin, position)| (pin == name).then_some(position))
+    }
+
+    /// Returns the names and positions of all pins, starting with the left column.
+    fn pins() -> impl Iterator<Item = (&'static str, Point)> {
+        let column = |pins: [&'static str; 12], x: Length| {
+            pins.into_iter().enumerate().map(move |(index, pin)| {
+                #[allow(clippy::cast_precision_loss)]
+                let y = (index as f32 - 5.5) * Self::PITCH;
+
+                (pin, Point::new(x, y))
+            })
+        };
+
+        column(LEFT_PINS, -Self::COLUMN_DISTANCE / 2)
+            .chain(column(RIGHT_PINS, Self::COLUMN_DISTANCE / 2))
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+
+        Self::pins()
+            .map(|(pin, position)| {
+                let net = self
+                    .nets
+                    .iter()
+                    .find_map(|(name, net)| (*name == pin).then(|| net.clone()));
+
+                Pad::new(
+                    pin,
+                    PadType::ThruHole,
+                    PadShape::Circle,
+                    position!(position.x(), position.y(), angle),
+                    size!(1.6, 1.6),
+                    1.into(),
+                    net,
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<ProMicro> for Footprint {
+    fn from(pro_micro: ProMicro) -> Self {
+        let angle = pro_micro.position.angle();
+
+        Self(
+            "pro_micro_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: pro_micro.position,
+                descr: "Pro Micro footprint dual side".to_owned(),
+                tags: "Pro Micro KB2040 nice!nano dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        pro_micro.reference.clone(),
+                        position!(0, -8.0, angle),
+                    ),
+                    Property::new("Value", "Controller".to_owned(), position!(0, 8.0, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-9.25, -16.75), point!(9.25, -16.75), "CrtYd"),
+                    (point!(9.25, -16.75), point!(9.25, 16.75), "CrtYd"),
+                    (point!(9.25, 16.75), point!(-9.25, 16.75), "CrtYd"),
+                    (point!(-9.25, 16.75), point!(-9.25, -16.75), "CrtYd"),
+                    (point!(-9, -16.5), point!(9, -16.5), "SilkS"),
+                    (point!(9, -16.5), point!(9, 16.5), "SilkS"),
+                    (point!(9, 16.5), point!(-9, 16.5), "SilkS"),
+                    (point!(-9, 16.5), point!(-9, -16.5), "SilkS"),
+                    (point!(-4.5, -16.5), point!(-4.5, -11.0), "Fab"),
+                    (point!(-4.5, -11.0), point!(4.5, -11.0), "Fab"),
+                    (point!(4.5, -11.0), point!(4.5, -16.5), "Fab"),
+                ],
+                fp_texts: Vec::new(),
+                pads: pro_micro.pads(),
+            },
+        )
+    }
+}
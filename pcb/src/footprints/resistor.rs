@@ -0,0 +1,103 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// A resistor in a 1206 package.
+pub struct Resistor {
+    reference: String,
+    position: Position,
+    nets: [Net; 2],
+}
+
+impl Resistor {
+    /// The position of the first pad within the resistor.
+    pub const FIRST_PAD: Point = Point::new(Length::new(-1.475), Length::new(0.0));
+    /// The position of the second pad within the resistor.
+    pub const SECOND_PAD: Point = Point::new(Length::new(1.475), Length::new(0.0));
+
+    /// Creates a new resistor at the given position connecting the given nets.
+    pub fn new(reference: String, position: Position, nets: [Net; 2]) -> Self {
+        Self {
+            reference,
+            position,
+            nets,
+        }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let via_drill_diameter = 0.3.into();
+
+        let [first_net, second_net] = self.nets;
+
+        vec![
+            Pad::new(
+                "1",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::FIRST_PAD.x(), Self::FIRST_PAD.y(), angle),
+                size!(1.125, 1.75),
+                via_drill_diameter,
+                Some(first_net),
+            ),
+            Pad::new(
+                "2",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::SECOND_PAD.x(), Self::SECOND_PAD.y(), angle),
+                size!(1.125, 1.75),
+                via_drill_diameter,
+                Some(second_net),
+            ),
+        ]
+    }
+}
+
+impl From<Resistor> for Footprint {
+    fn from(resistor: Resistor) -> Self {
+        let angle = resistor.position.angle();
+
+        Self(
+            "resistor_1206_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: resistor.position,
+                descr: "Resistor 1206 dual side".to_owned(),
+                tags: "resistor 1206 dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        resistor.reference.clone(),
+                        position!(0, -1.9, angle),
+                    ),
+                    Property::new("Value", "10k".to_owned(), position!(0, 1.9, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-2.28, -1.12), point!(2.28, -1.12), "CrtYd"),
+                    (point!(2.28, -1.12), point!(2.28, 1.12), "CrtYd"),
+                    (point!(2.28, 1.12), point!(-2.28, 1.12), "CrtYd"),
+                    (point!(-2.28, 1.12), point!(-2.28, -1.12), "CrtYd"),
+                    (point!(-1.6, -0.8), point!(1.6, -0.8), "Fab"),
+                    (point!(1.6, -0.8), point!(1.6, 0.8), "Fab"),
+                    (point!(1.6, 0.8), point!(-1.6, 0.8), "Fab"),
+                    (point!(-1.6, 0.8), point!(-1.6, -0.8), "Fab"),
+                    (point!(-0.73, -0.91), point!(0.73, -0.91), "SilkS"),
+                    (point!(-0.73, 0.91), point!(0.73, 0.91), "SilkS"),
+                ],
+                fp_texts: Vec::new(),
+                pads: resistor.pads(),
+            },
+        )
+    }
+}
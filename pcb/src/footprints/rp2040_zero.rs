@@ -0,0 +1,150 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// The names of the pins in the left column from the top to the bottom.
+const LEFT_PINS: [&str; 9] = [
+    "5V", "GND", "3V3", "GP29", "GP28", "GP27", "GP26", "GP15", "GP14",
+];
+/// The names of the pins in the right column from the top to the bottom.
+const RIGHT_PINS: [&str; 9] = [
+    "GP0", "GP1", "GP2", "GP3", "GP4", "GP5", "GP6", "GP7", "GP8",
+];
+/// The names of the pins in the bottom row between the columns from the left to the right.
+const BOTTOM_PINS: [&str; 5] = ["GP13", "GP12", "GP11", "GP10", "GP9"];
+
+/// A Waveshare RP2040-Zero controller.
+pub struct Rp2040Zero {
+    reference: String,
+    position: Position,
+    nets: Vec<(&'static str, Net)>,
+}
+
+impl Rp2040Zero {
+    /// The distance between adjacent pins.
+    const PITCH: Length = Length::new(2.54);
+    /// The distance between the two columns of pins.
+    const COLUMN_DISTANCE: Length = Length::new(15.24);
+
+    /// Creates a new controller at the given position with the pins connected to the given nets.
+    ///
+    /// Pins without a net are left unconnected.
+    pub fn new(reference: String, position: Position, nets: Vec<(&'static str, Net)>) -> Self {
+        Self {
+            reference,
+            position,
+            nets,
+        }
+    }
+
+    /// Returns the position of the pin with the given name relative to the controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no pin with the given name.
+    pub fn pin_position(name: &str) -> Point {
+        Self::pins()
+            .find_map(|(pin, position)| (pin == name).then_some(position))
+            .expect("pin should exist on an RP2040-Zero")
+    }
+
+    /// Returns the y coordinate of the bottom row of pins relative to the controller.
+    pub fn bottom_row_y() -> Length {
+        4 * Self::PITCH
+    }
+
+    /// Returns the names and positions of all pins, starting with the left column.
+    fn pins() -> impl Iterator<Item = (&'static str, Point)> {
+        let column = |pins: [&'static str; 9], x: Length| {
+            pins.into_iter().enumerate().map(move |(index, pin)| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let y = (index as i32 - 4) * Self::PITCH;
+
+                (pin, Point::new(x, y))
+            })
+        };
+        let bottom_row = BOTTOM_PINS.into_iter().enumerate().map(|(index, pin)| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            let x = (index as i32 - 2) * Self::PITCH;
+
+            (pin, Point::new(x, Self::bottom_row_y()))
+        });
+
+        column(LEFT_PINS, -Self::COLUMN_DISTANCE / 2)
+            .chain(column(RIGHT_PINS, Self::COLUMN_DISTANCE / 2))
+            .chain(bottom_row)
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+
+        Self::pins()
+            .map(|(pin, position)| {
+                let net = self
+                    .nets
+                    .iter()
+                    .find_map(|(name, net)| (*name == pin).then(|| net.clone()));
+
+                Pad::new(
+                    pin,
+                    PadType::ThruHole,
+                    PadShape::Circle,
+                    position!(position.x(), position.y(), angle),
+                    size!(1.6, 1.6),
+                    1.into(),
+                    net,
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<Rp2040Zero> for Footprint {
+    fn from(rp2040_zero: Rp2040Zero) -> Self {
+        let angle = rp2040_zero.position.angle();
+
+        Self(
+            "rp2040_zero_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: rp2040_zero.position,
+                descr: "RP2040-Zero footprint dual side".to_owned(),
+                tags: "RP2040-Zero dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        rp2040_zero.reference.clone(),
+                        position!(0, -6.0, angle),
+                    ),
+                    Property::new("Value", "Controller".to_owned(), position!(0, 6.0, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-9.25, -12.0), point!(9.25, -12.0), "CrtYd"),
+                    (point!(9.25, -12.0), point!(9.25, 12.0), "CrtYd"),
+                    (point!(9.25, 12.0), point!(-9.25, 12.0), "CrtYd"),
+                    (point!(-9.25, 12.0), point!(-9.25, -12.0), "CrtYd"),
+                    (point!(-9, -11.75), point!(9, -11.75), "SilkS"),
+                    (point!(9, -11.75), point!(9, 11.75), "SilkS"),
+                    (point!(9, 11.75), point!(-9, 11.75), "SilkS"),
+                    (point!(-9, 11.75), point!(-9, -11.75), "SilkS"),
+                    (point!(-4.5, -11.75), point!(-4.5, -6.0), "Fab"),
+                    (point!(-4.5, -6.0), point!(4.5, -6.0), "Fab"),
+                    (point!(4.5, -6.0), point!(4.5, -11.75), "Fab"),
+                ],
+                fp_texts: Vec::new(),
+                pads: rp2040_zero.pads(),
+            },
+        )
+    }
+}
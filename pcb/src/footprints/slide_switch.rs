@@ -0,0 +1,143 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// A right angle SPDT slide switch with the actuator facing upwards.
+pub struct SlideSwitch {
+    reference: String,
+    position: Position,
+    common_net: Net,
+    output_net: Net,
+}
+
+impl SlideSwitch {
+    /// The position of the common pad within the switch.
+    pub const COMMON_PAD: Point = Point::new(Length::new(0.75), Length::new(3.0));
+    /// The position of the pad connected to the common pad when switched on.
+    pub const OUTPUT_PAD: Point = Point::new(Length::new(2.25), Length::new(3.0));
+
+    /// Creates a new slide switch at the given position using the given nets.
+    ///
+    /// The remaining throw of the switch is left unconnected.
+    pub fn new(reference: String, position: Position, common_net: Net, output_net: Net) -> Self {
+        Self {
+            reference,
+            position,
+            common_net,
+            output_net,
+        }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let via_drill_diameter = 0.3.into();
+
+        let mut pads: Vec<_> = [-1.5, 1.5]
+            .into_iter()
+            .map(|x| {
+                Pad::new(
+                    "",
+                    PadType::NpThruHole,
+                    PadShape::Circle,
+                    position!(x, 1.1, angle),
+                    size!(0.9, 0.9),
+                    0.9.into(),
+                    None,
+                )
+            })
+            .chain([-3.65, 3.65].into_iter().map(|x| {
+                Pad::new(
+                    "",
+                    PadType::ThruHole,
+                    PadShape::Roundrect,
+                    position!(x, 1.1, angle),
+                    size!(1.0, 0.8),
+                    via_drill_diameter,
+                    None,
+                )
+            }))
+            .collect();
+
+        pads.extend([
+            Pad::new(
+                "1",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(-2.25, 3.0, angle),
+                size!(0.7, 1.5),
+                via_drill_diameter,
+                None,
+            ),
+            Pad::new(
+                "2",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::COMMON_PAD.x(), Self::COMMON_PAD.y(), angle),
+                size!(0.7, 1.5),
+                via_drill_diameter,
+                Some(self.common_net),
+            ),
+            Pad::new(
+                "3",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::OUTPUT_PAD.x(), Self::OUTPUT_PAD.y(), angle),
+                size!(0.7, 1.5),
+                via_drill_diameter,
+                Some(self.output_net),
+            ),
+        ]);
+
+        pads
+    }
+}
+
+impl From<SlideSwitch> for Footprint {
+    fn from(slide_switch: SlideSwitch) -> Self {
+        let angle = slide_switch.position.angle();
+
+        Self(
+            "slide_switch_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: slide_switch.position,
+                descr: "Right angle SPDT slide switch dual side".to_owned(),
+                tags: "slide switch SPDT right angle dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        slide_switch.reference.clone(),
+                        position!(0, 5.0, angle),
+                    ),
+                    Property::new("Value", "Slide_Switch".to_owned(), position!(0, 6.5, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-4.4, -1.6), point!(4.4, -1.6), "CrtYd"),
+                    (point!(4.4, -1.6), point!(4.4, 4.0), "CrtYd"),
+                    (point!(4.4, 4.0), point!(-4.4, 4.0), "CrtYd"),
+                    (point!(-4.4, 4.0), point!(-4.4, -1.6), "CrtYd"),
+                    (point!(-3.35, 0), point!(3.35, 0), "Fab"),
+                    (point!(3.35, 0), point!(3.35, 2.2), "Fab"),
+                    (point!(3.35, 2.2), point!(-3.35, 2.2), "Fab"),
+                    (point!(-3.35, 2.2), point!(-3.35, 0), "Fab"),
+                    (point!(-0.5, 0), point!(-0.5, -1.4), "Fab"),
+                    (point!(-0.5, -1.4), point!(0.5, -1.4), "Fab"),
+                    (point!(0.5, -1.4), point!(0.5, 0), "Fab"),
+                ],
+                fp_texts: Vec::new(),
+                pads: slide_switch.pads(),
+            },
+        )
+    }
+}
@@ -1,7 +1,13 @@
+use std::iter::once;
+
+use config::SwitchType;
+
 use crate::{
-    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property, Text},
     kicad_pcb::Net,
-    lines_front_back, point, position,
+    lines_front_back,
+    path::Path,
+    point, position,
     primitives::{Point, Position, Uuid},
     size,
     unit::Length,
@@ -9,150 +15,843 @@ use crate::{
 
 /// The position of the row pad within a switch.
 pub const ROW_PAD: Point = Point::new(Length::new(1.65), Length::new(3.4));
-/// The position above the row pad within a switch.
-pub const ABOVE_ROW_PAD: Point = Point::new(ROW_PAD.x(), Length::new(2.4));
-/// The position below the row pad within a switch.
-pub const BELOW_ROW_PAD: Point = Point::new(ROW_PAD.x(), Length::new(4.4));
-/// The position left of the row pad within a switch.
-pub const LEFT_OF_ROW_PAD: Point = Point::new(Length::new(0.0), ROW_PAD.y());
 /// The position of the lower column pad within a switch.
-pub const LOWER_COLUMN_PAD: Point = Point::new(Length::new(3.81), Length::new(-2.54));
-/// The position of the upper column pad within a switch.
-pub const UPPER_COLUMN_PAD: Point = Point::new(Length::new(2.54), Length::new(-5.08));
+const LOWER_COLUMN_PAD: Point = Point::new(Length::new(3.81), Length::new(-2.54));
+/// The position above the row pad within a Cherry MX switch.
+const ABOVE_ROW_PAD: Point = Point::new(ROW_PAD.x(), Length::new(2.4));
+/// The position below the row pad within a Cherry MX switch.
+const BELOW_ROW_PAD: Point = Point::new(ROW_PAD.x(), Length::new(4.4));
+/// The position left of the row pad within a Cherry MX switch.
+const LEFT_OF_ROW_PAD: Point = Point::new(Length::new(0.0), ROW_PAD.y());
+/// The position of the upper column pad within a Cherry MX switch.
+const UPPER_COLUMN_PAD: Point = Point::new(Length::new(2.54), Length::new(-5.08));
+
+/// Returns the point with the given coordinates within a switch.
+const fn point_at(x: f64, y: f64) -> Point {
+    Point::new(Length::new(x), Length::new(y))
+}
+
+/// The points within a switch the tracks of the matrix PCB are routed along to clear its holes.
+#[derive(Clone, Copy)]
+pub struct Routing {
+    /// The column pad the tracks coming from the top edge connect to.
+    pub upper_column_pad: Point,
+    /// The column pad or via the tracks coming from the bottom edge connect to.
+    pub lower_column_pad: Point,
+    /// The column pin the column track coming from the top of the left edge connects to.
+    pub top_column_pin: Point,
+    /// The position above the row pad.
+    pub above_row_pad: Point,
+    /// The position below the row pad.
+    pub below_row_pad: Point,
+    /// The position left of the row pad.
+    pub left_of_row_pad: Point,
+    /// The point on the lower column track the column track coming from the center of the right
+    /// edge connects to.
+    pub center_column_point: Point,
+    /// The Y offset of the row track passing above the pins.
+    pub upper_row_y_offset: Length,
+    /// The Y offset at which the row tracks along the channel of an outer column attached at the
+    /// center of its side edge turn towards the switches above.
+    pub outer_row_y_offset: Length,
+    /// The Y offset of the column tracks passing the thumb switches along their top.
+    pub thumb_column_y_offset: Length,
+    /// The depth of the chamfer of the column tracks turning from the thumb connector towards
+    /// the top of the thumb switches.
+    pub thumb_column_chamfer_depth: Length,
+    /// The position below the internal pad the tracks coming from the left pass before reaching
+    /// the position left of the row pad.
+    pub below_internal_pad: Point,
+    /// The point the home row track coming from the center of the right edge passes before
+    /// reaching the position above the row pad.
+    pub center_right_home_row_point: Point,
+    /// The points the home row track coming from the top of the left edge passes.
+    pub top_left_home_row_points: &'static [Point],
+    /// The points the home row track coming from the top of the right edge passes.
+    pub top_right_home_row_points: &'static [Point],
+    /// The Y offset of the FFC connector below the switch it is placed on, far enough down for
+    /// the tracks fanning out to it to pass the holes.
+    pub ffc_connector_y_offset: Length,
+    /// The points the track from the lower column pad to the FFC connector below passes.
+    pub ffc_column_points: &'static [Point],
+    /// The points the track of the second column to the FFC connector below passes between the
+    /// center hole and the row pad.
+    pub ffc_second_column_points: [Point; 2],
+    /// The points the innermost track of the right columns to the FFC connector below passes after
+    /// running along the channel past pads blocking it, with the other tracks offset from it.
+    pub ffc_right_column_points: Option<[Point; 2]>,
+    /// Whether the column tracks passing a switch dip below its column pin, with the track ending
+    /// at the switch connecting to the via at the lower column pad instead.
+    dip_below_column_pin: bool,
+    /// Whether the pins or holes for pegs beside the center post leave no room for tracks to
+    /// turn away from the side edges before passing them.
+    narrow_sides: bool,
+}
+
+impl Routing {
+    /// The depth of the chamfer of paths along the channels beside narrow sides.
+    const CHANNEL_CHAMFER_DEPTH: Length = Length::new(0.8);
+    /// The depth of the dip of column tracks passing below the column pin.
+    const DIP_DEPTH: Length = Length::new(3.0);
+    /// The X offset of the start of the dip of column tracks passing below the column pin.
+    const DIP_X_OFFSET: Length = Length::new(4.76);
+
+    /// Returns the path from the given point at a side edge to the given point.
+    ///
+    /// With narrow sides, the path turns away from the edge right away instead of running along
+    /// the center of the switch.
+    pub fn side_path(self, start: Point, end: Point) -> Path {
+        if self.narrow_sides {
+            Path::angled_end(start, end)
+        } else {
+            Path::angled_start(start, end)
+        }
+    }
+
+    /// Returns the path from the given point in a channel beside the pins to the given point.
+    ///
+    /// With narrow sides, the path only turns away from the channel after passing them.
+    pub fn channel_path(self, start: Point, end: Point) -> Path {
+        if self.narrow_sides {
+            let difference = end - start;
+            let right = difference.x.signum() == difference.y.signum();
+
+            Path::chamfered(start, end, Self::CHANNEL_CHAMFER_DEPTH, right)
+        } else {
+            Path::angled_start(start, end)
+        }
+    }
+
+    /// Returns the path of a column track from the given point at the left edge of a switch to its
+    /// column pin.
+    pub fn ending_column_path(self, start: Point) -> Path {
+        if self.dip_below_column_pin {
+            let dip_start = point!(-Self::DIP_X_OFFSET, start.y());
+            let dip_end = point!(
+                Self::DIP_DEPTH - Self::DIP_X_OFFSET,
+                start.y() + Self::DIP_DEPTH
+            );
+
+            Path::new([start, dip_start]).join(&Path::angled_start(dip_end, self.lower_column_pad))
+        } else {
+            Path::angled_start(start, self.top_column_pin)
+        }
+    }
+
+    /// Returns the path of the column track from the switch to the given point at the left edge of
+    /// an FFC connector placed below it, on the bottom layer.
+    pub fn ffc_column_path(self, end: Point) -> Path {
+        if self.dip_below_column_pin {
+            // Leave the via at the lower column pad towards the gap between column pin and center hole
+            let above_center_hole = point_at(2.6, -3.75);
+
+            Path::new([self.lower_column_pad]).join(&Path::chamfered(
+                above_center_hole,
+                end,
+                Length::new(5.35),
+                true,
+            ))
+        } else {
+            Path::chamfered(self.upper_column_pad, end, Length::new(6.6), true)
+        }
+    }
+
+    /// Returns the path of a column track passing a switch from the given point at its left edge
+    /// to the given point at its right edge, which is empty if the track passes straight through.
+    pub fn passing_column_path(self, start: Point, end: Point) -> Path {
+        if self.dip_below_column_pin {
+            let dip_x_offset = Self::DIP_X_OFFSET - Self::DIP_DEPTH;
+
+            Path::new([
+                start,
+                point!(-Self::DIP_X_OFFSET, start.y()),
+                point!(-dip_x_offset, start.y() + Self::DIP_DEPTH),
+                point!(dip_x_offset, end.y() + Self::DIP_DEPTH),
+                point!(Self::DIP_X_OFFSET, end.y()),
+                end,
+            ])
+        } else {
+            Path::new([])
+        }
+    }
+
+    /// Returns the routing for switches of the given type, plugged into hot-swap sockets if
+    /// enabled and available for the switch type.
+    #[allow(clippy::too_many_lines)]
+    #[must_use]
+    pub fn new(switch_type: SwitchType, hot_swap: bool) -> Self {
+        const MX: Routing = Routing {
+            upper_column_pad: UPPER_COLUMN_PAD,
+            lower_column_pad: LOWER_COLUMN_PAD,
+            top_column_pin: UPPER_COLUMN_PAD,
+            above_row_pad: ABOVE_ROW_PAD,
+            below_row_pad: BELOW_ROW_PAD,
+            left_of_row_pad: LEFT_OF_ROW_PAD,
+            center_column_point: Point::new(LOWER_COLUMN_PAD.x(), Length::new(0.0)),
+            upper_row_y_offset: Length::new(-6.6),
+            outer_row_y_offset: UPPER_COLUMN_PAD.y(),
+            thumb_column_y_offset: Length::new(-6.38),
+            thumb_column_chamfer_depth: Length::new(1.0),
+            below_internal_pad: LEFT_OF_ROW_PAD,
+            center_right_home_row_point: ABOVE_ROW_PAD,
+            top_left_home_row_points: &[point_at(0.7, -5.6), point_at(2.4, -1.6)],
+            top_right_home_row_points: &[point_at(0.7, -5.6), point_at(2.4, -1.6)],
+            ffc_connector_y_offset: Length::new(5.5),
+            ffc_column_points: &[point_at(0.5, 3.0)],
+            ffc_second_column_points: [point_at(0.5, 3.0), point_at(0.5, 3.0)],
+            ffc_right_column_points: None,
+            dip_below_column_pin: false,
+            narrow_sides: false,
+        };
 
-/// A Cherry MX switch.
+        // The tracks pass the shared column pin of Choc switches above and the pegs below,
+        // while the row pad is approached from below the internal pad
+        const CHOC: Routing = Routing {
+            upper_column_pad: point_at(4.4, -5.08),
+            lower_column_pad: LOWER_COLUMN_PAD,
+            top_column_pin: point_at(0.0, -5.9),
+            above_row_pad: ABOVE_ROW_PAD,
+            below_row_pad: point_at(1.65, 5.0),
+            left_of_row_pad: point_at(0.3, 3.4),
+            center_column_point: point_at(3.81, 1.5),
+            upper_row_y_offset: Length::new(-7.4),
+            outer_row_y_offset: Length::new(-5.08),
+            thumb_column_y_offset: Length::new(-6.38),
+            thumb_column_chamfer_depth: Length::new(1.0),
+            below_internal_pad: point_at(-2.8, 4.4),
+            center_right_home_row_point: point_at(4.8, 2.4),
+            top_left_home_row_points: &[point_at(-1.4, -3.7), point_at(3.1, -2.6)],
+            top_right_home_row_points: &[point_at(2.0, -3.7), point_at(3.1, -2.6)],
+            ffc_connector_y_offset: Length::new(6.05),
+            ffc_column_points: &[point_at(2.3, 4.25), point_at(0.5, 5.15)],
+            ffc_second_column_points: [point_at(1.36, 2.56), point_at(0.97, 2.56)],
+            ffc_right_column_points: None,
+            dip_below_column_pin: true,
+            narrow_sides: true,
+        };
+
+        // The pins of Gateron low profile switches leave no room for tracks between them, so the
+        // tracks pass them above and the center hole on the right
+        const GATERON_LOW_PROFILE: Routing = Routing {
+            upper_column_pad: point_at(4.4, -5.2),
+            top_column_pin: point_at(2.6, -5.75),
+            outer_row_y_offset: Length::new(-5.2),
+            below_internal_pad: point_at(-1.95, 4.4),
+            center_right_home_row_point: point_at(3.95, 2.4),
+            top_left_home_row_points: &[point_at(0.7, -5.6), point_at(2.9, -2.5)],
+            top_right_home_row_points: &[point_at(0.7, -5.6), point_at(2.9, -2.5)],
+            thumb_column_y_offset: Length::new(-6.92),
+            thumb_column_chamfer_depth: Length::new(0.5),
+            dip_below_column_pin: false,
+            ..CHOC
+        };
+
+        // The column tracks of Cherry MX switches in hot-swap sockets end at the via between the
+        // column pads, while the tracks along the top pass above the larger socket holes and the
+        // socket pads beside the pins leave no room for tracks to turn away from the side edges.
+        // The tracks to the FFC connector pass the column pad on the top layer below it.
+        const MX_HOT_SWAP: Routing = Routing {
+            upper_column_pad: point_at(6.46, -3.81),
+            lower_column_pad: point_at(7.085, -2.54),
+            top_column_pin: point_at(6.46, -3.81),
+            center_column_point: point_at(7.085, 0.0),
+            upper_row_y_offset: Length::new(-7.2),
+            outer_row_y_offset: Length::new(-5.4),
+            thumb_column_y_offset: Length::new(-6.92),
+            thumb_column_chamfer_depth: Length::new(0.5),
+            below_internal_pad: point_at(-3.4, 0.0),
+            top_left_home_row_points: &[point_at(0.5, -6.0), point_at(2.4, -0.9)],
+            top_right_home_row_points: &[point_at(0.5, -6.0), point_at(2.4, -0.9)],
+            ffc_column_points: &[point_at(3.1, 2.4), point_at(0.5, 3.0)],
+            ffc_right_column_points: Some([point_at(5.685, 3.4), point_at(5.2, 3.4)]),
+            narrow_sides: true,
+            ..MX
+        };
+
+        // The column tracks of Choc switches in hot-swap sockets run along the right edge, where
+        // only the internal pad on the bottom layer blocks the channel, while the tracks along the
+        // top pass between the socket holes and the top edge instead of dipping below them. The
+        // home row track coming from the top of the left edge passes the column pad on the left,
+        // and the column track to the FFC connector passes the peg above.
+        const CHOC_HOT_SWAP: Routing = Routing {
+            upper_column_pad: point_at(8.235, -5.6),
+            lower_column_pad: point_at(8.235, -1.0),
+            top_column_pin: point_at(-4.7, -6.3),
+            center_column_point: point_at(8.235, 0.0),
+            upper_row_y_offset: Length::new(-7.65),
+            outer_row_y_offset: Length::new(-5.6),
+            thumb_column_y_offset: Length::new(-7.575),
+            thumb_column_chamfer_depth: Length::new(0.5),
+            top_left_home_row_points: &[
+                point_at(-7.3, -7.5),
+                point_at(-7.3, -1.6),
+                point_at(-1.7, -3.4),
+                point_at(3.1, -2.6),
+            ],
+            ffc_column_points: &[
+                point_at(7.935, -1.3),
+                point_at(4.0, -0.1),
+                point_at(2.3, 4.3),
+                point_at(0.5, 5.2),
+            ],
+            ffc_right_column_points: Some([point_at(7.7, 1.5), point_at(7.65, 1.5)]),
+            dip_below_column_pin: false,
+            ..CHOC
+        };
+
+        let hot_swap = hot_swap && switch_type.has_hot_swap_socket();
+
+        match switch_type {
+            SwitchType::Mx if hot_swap => MX_HOT_SWAP,
+            SwitchType::Mx => MX,
+            SwitchType::ChocV1 | SwitchType::ChocV2 if hot_swap => CHOC_HOT_SWAP,
+            SwitchType::ChocV1 | SwitchType::ChocV2 => CHOC,
+            SwitchType::GateronLowProfile => GATERON_LOW_PROFILE,
+        }
+    }
+}
+
+/// The pinout and holes of a key switch.
+struct Pinout {
+    /// The value of the footprint.
+    value: &'static str,
+    /// The name of the switch used in the description of the footprint.
+    name: &'static str,
+    /// The half size of the switch outline on the fabrication layer.
+    outline_half_size: f64,
+    /// The diameter of the hole for the center post.
+    center_hole_diameter: f64,
+    /// The diameter of the holes for the pegs beside the center post.
+    peg_hole_diameter: f64,
+    /// The positions of the holes for the pegs beside the center post.
+    peg_holes: &'static [Point],
+    /// The drill diameter of the pins.
+    pin_drill_diameter: f64,
+    /// The diameter of the pads around the pins.
+    pin_pad_diameter: f64,
+    /// The positions of the vias connecting the column tracks to the bottom layer.
+    column_vias: &'static [Point],
+    /// The positions of the pins connected to the column for the switch on the front and back.
+    column_pins: &'static [Point],
+    /// The positions of the pins connected to the diode for the switch on the front and back.
+    internal_pins: &'static [Point],
+    /// The top layer tracks connecting the column pins to the column pads.
+    column_tracks: &'static [&'static [Point]],
+    /// The top layer tracks connecting the internal pins to the diode.
+    internal_tracks: &'static [&'static [Point]],
+    /// The Kailh hot-swap sockets fitting the switch, if there are any.
+    sockets: Option<Sockets>,
+}
+
+/// A surface mount pad of a Kailh hot-swap socket.
+#[derive(Clone, Copy)]
+struct SocketPad {
+    /// The position of the pad.
+    position: Point,
+    /// The width and height of the pad.
+    size: (f64, f64),
+    /// The copper layer of the pad, opposite of the switch plugged into the socket.
+    layer: &'static str,
+}
+
+/// The holes and pads of the Kailh hot-swap sockets for the switch on the front and back.
+#[derive(Clone, Copy)]
+struct Sockets {
+    /// The diameter of the holes the sockets hold the pins of the switch in.
+    hole_diameter: f64,
+    /// The pads connected to the column.
+    column_pads: &'static [SocketPad],
+    /// The pads connected to the diode.
+    internal_pads: &'static [SocketPad],
+    /// The positions of the vias connecting the column pads on both layers.
+    column_vias: &'static [Point],
+    /// The positions of the vias connecting the internal pads on both layers.
+    internal_vias: &'static [Point],
+    /// The top layer tracks connecting the column vias to the column pads.
+    column_tracks: &'static [&'static [Point]],
+    /// The top layer tracks connecting the internal vias to the diode.
+    internal_tracks: &'static [&'static [Point]],
+}
+
+/// Returns a pad of a Kailh hot-swap socket with the given position and size on the given layer.
+const fn socket_pad(x: f64, y: f64, width: f64, height: f64, layer: &'static str) -> SocketPad {
+    SocketPad {
+        position: point_at(x, y),
+        size: (width, height),
+        layer,
+    }
+}
+
+impl From<SwitchType> for Pinout {
+    #[allow(clippy::too_many_lines)]
+    fn from(switch_type: SwitchType) -> Self {
+        const MX: Pinout = Pinout {
+            value: "SW_Cherry_MX",
+            name: "Cherry MX",
+            outline_half_size: 6.35,
+            center_hole_diameter: 4.0,
+            peg_hole_diameter: 0.0,
+            peg_holes: &[],
+            pin_drill_diameter: 1.5,
+            pin_pad_diameter: 2.2,
+            column_vias: &[],
+            column_pins: &[UPPER_COLUMN_PAD, LOWER_COLUMN_PAD],
+            internal_pins: &[point_at(-2.54, -5.08), point_at(-3.81, -2.54)],
+            column_tracks: &[&[UPPER_COLUMN_PAD, LOWER_COLUMN_PAD]],
+            internal_tracks: &[&[
+                point_at(-2.54, -5.08),
+                point_at(-3.81, -2.54),
+                point_at(-3.81, 0.24),
+                point_at(-1.65, 2.4),
+                point_at(-1.65, 3.4),
+            ]],
+            sockets: Some(Sockets {
+                hole_diameter: 3.0,
+                column_pads: &[
+                    socket_pad(5.842, -5.08, 2.55, 2.5, "B.Cu"),
+                    socket_pad(7.085, -2.54, 2.55, 2.5, "F.Cu"),
+                ],
+                internal_pads: &[
+                    socket_pad(-7.085, -2.54, 2.55, 2.5, "B.Cu"),
+                    socket_pad(-5.842, -5.08, 2.55, 2.5, "F.Cu"),
+                ],
+                column_vias: &[point_at(6.46, -3.81)],
+                internal_vias: &[point_at(-6.46, -3.81)],
+                column_tracks: &[],
+                internal_tracks: &[&[
+                    point_at(-6.46, -3.81),
+                    point_at(-6.46, -2.41),
+                    point_at(-3.81, 0.24),
+                    point_at(-1.65, 2.4),
+                    point_at(-1.65, 3.4),
+                ]],
+            }),
+        };
+
+        // Choc switches share the column pin between both sides, so the internal pins on both
+        // sides are connected below it while the column track goes around the right internal pin
+        const CHOC_V1: Pinout = Pinout {
+            value: "SW_Kailh_Choc_V1",
+            name: "Kailh Choc V1",
+            outline_half_size: 6.9,
+            center_hole_diameter: 3.4,
+            peg_hole_diameter: 1.7,
+            peg_holes: &[point_at(-5.5, 0.0), point_at(5.5, 0.0)],
+            pin_drill_diameter: 1.2,
+            pin_pad_diameter: 2.0,
+            column_vias: &[LOWER_COLUMN_PAD],
+            column_pins: &[point_at(0.0, -5.9)],
+            internal_pins: &[point_at(5.0, -3.8), point_at(-5.0, -3.8)],
+            column_tracks: &[
+                &[
+                    point_at(0.0, -5.9),
+                    point_at(0.82, -5.08),
+                    point_at(4.4, -5.08),
+                ],
+                &[
+                    point_at(4.4, -5.08),
+                    point_at(5.76, -5.08),
+                    point_at(6.3, -4.54),
+                    point_at(6.3, -3.08),
+                    point_at(5.76, -2.54),
+                    LOWER_COLUMN_PAD,
+                ],
+            ],
+            internal_tracks: &[
+                &[
+                    point_at(-5.0, -3.8),
+                    point_at(-3.81, -2.61),
+                    point_at(-3.81, 0.24),
+                    point_at(-1.65, 2.4),
+                    point_at(-1.65, 3.4),
+                ],
+                &[point_at(-5.0, -3.8), point_at(5.0, -3.8)],
+            ],
+            // The column pads are shortened to clear the socket holes of the other side
+            sockets: Some(Sockets {
+                hole_diameter: 3.0,
+                column_pads: &[
+                    socket_pad(-3.275, -6.3, 2.6, 1.8, "B.Cu"),
+                    socket_pad(3.275, -6.3, 2.6, 1.8, "F.Cu"),
+                ],
+                internal_pads: &[
+                    socket_pad(8.275, -3.8, 2.6, 2.6, "B.Cu"),
+                    socket_pad(-8.275, -3.8, 2.6, 2.6, "F.Cu"),
+                ],
+                column_vias: &[point_at(-4.7, -6.3)],
+                internal_vias: &[point_at(7.6, -2.2)],
+                column_tracks: &[
+                    &[
+                        point_at(-4.7, -6.3),
+                        point_at(-4.7, -6.95),
+                        point_at(-4.0, -7.65),
+                        point_at(2.925, -7.65),
+                        point_at(3.275, -7.3),
+                        point_at(3.275, -6.3),
+                    ],
+                    &[
+                        point_at(3.275, -6.3),
+                        point_at(7.535, -6.3),
+                        point_at(8.235, -5.6),
+                        point_at(8.235, -1.0),
+                    ],
+                ],
+                internal_tracks: &[
+                    &[
+                        point_at(-8.275, -3.8),
+                        point_at(-6.075, -1.6),
+                        point_at(-4.51, -1.6),
+                        point_at(-3.81, -0.9),
+                        point_at(-3.81, 0.24),
+                        point_at(-1.65, 2.4),
+                        point_at(-1.65, 3.4),
+                    ],
+                    &[
+                        point_at(7.6, -2.2),
+                        point_at(7.4, -2.0),
+                        point_at(4.0, -2.0),
+                        point_at(2.55, -3.45),
+                        point_at(-2.55, -3.45),
+                        point_at(-3.81, -2.19),
+                        point_at(-3.81, -0.9),
+                    ],
+                ],
+            }),
+        };
+
+        const GATERON_LOW_PROFILE: Pinout = Pinout {
+            value: "SW_Gateron_Low_Profile",
+            name: "Gateron low profile",
+            outline_half_size: 6.95,
+            center_hole_diameter: 5.0,
+            peg_hole_diameter: 0.0,
+            peg_holes: &[],
+            pin_drill_diameter: 1.2,
+            pin_pad_diameter: 2.0,
+            column_vias: &[],
+            column_pins: &[point_at(2.6, -5.75), point_at(4.4, -4.7)],
+            internal_pins: &[point_at(-2.6, -5.75), point_at(-4.4, -4.7)],
+            column_tracks: &[
+                &[point_at(2.6, -5.75), UPPER_COLUMN_PAD, LOWER_COLUMN_PAD],
+                &[LOWER_COLUMN_PAD, point_at(4.4, -3.13), point_at(4.4, -4.7)],
+            ],
+            internal_tracks: &[
+                &[
+                    point_at(-2.6, -5.75),
+                    point_at(-2.54, -5.08),
+                    point_at(-3.81, -2.54),
+                    point_at(-3.81, 0.24),
+                    point_at(-1.65, 2.4),
+                    point_at(-1.65, 3.4),
+                ],
+                &[
+                    point_at(-3.81, -2.54),
+                    point_at(-4.4, -3.13),
+                    point_at(-4.4, -4.7),
+                ],
+            ],
+            sockets: None,
+        };
+
+        match switch_type {
+            SwitchType::Mx => MX,
+            SwitchType::ChocV1 => CHOC_V1,
+            SwitchType::ChocV2 => Self {
+                value: "SW_Kailh_Choc_V2",
+                name: "Kailh Choc V2",
+                center_hole_diameter: 5.0,
+                ..CHOC_V1
+            },
+            SwitchType::GateronLowProfile => GATERON_LOW_PROFILE,
+        }
+    }
+}
+
+/// A key switch of the given type together with its diode.
 pub struct Switch {
     reference: String,
     position: Position,
+    pins: Pinout,
+    sockets: Option<Sockets>,
     column_net: Net,
     row_net: Net,
     internal_net: Net,
 }
 
 impl Switch {
-    /// Creates a new switch at the given position using the given nets.
+    /// Creates a new switch of the given type at the given position using the given nets.
+    ///
+    /// With hot-swap enabled, the switch is plugged into Kailh hot-swap sockets if there are any
+    /// fitting it.
     pub fn new(
         reference: String,
         position: Position,
+        switch_type: SwitchType,
+        hot_swap: bool,
         row_net: Net,
         column_net: Net,
         internal_net: Net,
     ) -> Self {
+        let pins = Pinout::from(switch_type);
+
         Self {
             reference,
             position,
+            sockets: pins.sockets.filter(|_| hot_swap),
+            pins,
             column_net,
             row_net,
             internal_net,
         }
     }
 
+    /// Returns the value of the footprint of a switch of the given type.
+    pub fn value(switch_type: SwitchType) -> &'static str {
+        Pinout::from(switch_type).value
+    }
+
+    /// Returns the tracks connecting the pins to the column pads and the diode.
+    ///
+    /// Each track is given by its path, its layer and its net.
+    pub fn tracks(&self) -> Vec<(Path, &'static str, &Net)> {
+        let tracks = |tracks: &[&[Point]], layer, net| {
+            tracks
+                .iter()
+                .map(|&points| {
+                    (
+                        Path::new(points.iter().copied()).at(self.position),
+                        layer,
+                        net,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (column_tracks, internal_tracks) = match self.sockets {
+            Some(sockets) => (sockets.column_tracks, sockets.internal_tracks),
+            None => (self.pins.column_tracks, self.pins.internal_tracks),
+        };
+
+        tracks(column_tracks, "F.Cu", &self.column_net)
+            .into_iter()
+            .chain(tracks(internal_tracks, "F.Cu", &self.internal_net))
+            .collect()
+    }
+
     fn pads(self) -> Vec<Pad> {
         let angle = self.position.angle();
+        let pins = &self.pins;
         let via_drill_diameter = 0.3.into();
-        let pin_drill_diameter = 1.5.into();
-
-        vec![
+        let center_hole = Pad::new(
+            "",
+            PadType::NpThruHole,
+            PadShape::Circle,
+            position!(0, 0, angle),
+            size!(pins.center_hole_diameter, pins.center_hole_diameter),
+            pins.center_hole_diameter.into(),
+            None,
+        );
+        let peg_holes = pins.peg_holes.iter().map(|&hole| {
             Pad::new(
                 "",
                 PadType::NpThruHole,
                 PadShape::Circle,
-                position!(0, 0, angle),
-                size!(4, 4),
-                4.into(),
+                position!(hole.x(), hole.y(), angle),
+                size!(pins.peg_hole_diameter, pins.peg_hole_diameter),
+                pins.peg_hole_diameter.into(),
                 None,
-            ),
+            )
+        });
+        let pin_pad = |name, pin: Point, net: &Net| {
             Pad::new(
+                name,
+                PadType::ThruHole,
+                PadShape::Circle,
+                position!(pin.x(), pin.y(), angle),
+                size!(pins.pin_pad_diameter, pins.pin_pad_diameter),
+                pins.pin_drill_diameter.into(),
+                Some(net.clone()),
+            )
+        };
+        let pin_and_via_pads: Vec<_> = if let Some(sockets) = self.sockets {
+            self.socket_pads(sockets)
+        } else {
+            pins.column_pins
+                .iter()
+                .map(|&pin| pin_pad("2", pin, &self.column_net))
+                .chain(
+                    pins.column_vias
+                        .iter()
+                        .map(|&via| self.via_pad("2", via, &self.column_net)),
+                )
+                .chain(
+                    pins.internal_pins
+                        .iter()
+                        .map(|&pin| pin_pad("3", pin, &self.internal_net)),
+                )
+                .collect()
+        };
+
+        once(center_hole)
+            .chain(peg_holes)
+            .chain(once(Pad::new(
                 "1",
                 PadType::ThruHole,
                 PadShape::Roundrect,
                 position!(ROW_PAD.x(), ROW_PAD.y(), angle),
                 size!(0.9, 1.2),
                 via_drill_diameter,
-                Some(self.row_net),
-            ),
-            Pad::new(
-                "2",
-                PadType::ThruHole,
-                PadShape::Circle,
-                position!(UPPER_COLUMN_PAD.x(), UPPER_COLUMN_PAD.y(), angle),
-                size!(2.2, 2.2),
-                pin_drill_diameter,
-                Some(self.column_net.clone()),
-            ),
-            Pad::new(
-                "2",
-                PadType::ThruHole,
-                PadShape::Circle,
-                position!(LOWER_COLUMN_PAD.x(), LOWER_COLUMN_PAD.y(), angle),
-                size!(2.2, 2.2),
-                pin_drill_diameter,
-                Some(self.column_net),
-            ),
-            Pad::new(
-                "3",
-                PadType::ThruHole,
-                PadShape::Circle,
-                position!(-UPPER_COLUMN_PAD.x(), UPPER_COLUMN_PAD.y(), angle),
-                size!(2.2, 2.2),
-                pin_drill_diameter,
-                Some(self.internal_net.clone()),
-            ),
-            Pad::new(
-                "3",
-                PadType::ThruHole,
-                PadShape::Circle,
-                position!(-LOWER_COLUMN_PAD.x(), LOWER_COLUMN_PAD.y(), angle),
-                size!(2.2, 2.2),
-                pin_drill_diameter,
-                Some(self.internal_net.clone()),
-            ),
-            Pad::new(
+                Some(self.row_net.clone()),
+            )))
+            .chain(pin_and_via_pads)
+            .chain(once(Pad::new(
                 "3",
                 PadType::ThruHole,
                 PadShape::Roundrect,
                 position!(-ROW_PAD.x(), ROW_PAD.y(), angle),
                 size!(0.9, 1.2),
                 via_drill_diameter,
-                Some(self.internal_net),
-            ),
-        ]
+                Some(self.internal_net.clone()),
+            )))
+            .collect()
+    }
+
+    /// Returns the holes and pads of the given hot-swap sockets together with their vias.
+    fn socket_pads(&self, sockets: Sockets) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let pins = &self.pins;
+
+        // The pins are held by the sockets, which are soldered to pads on the opposite side
+        let socket_holes = pins
+            .column_pins
+            .iter()
+            .chain(pins.internal_pins)
+            .map(|&pin| {
+                Pad::new(
+                    "",
+                    PadType::NpThruHole,
+                    PadShape::Circle,
+                    position!(pin.x(), pin.y(), angle),
+                    size!(sockets.hole_diameter, sockets.hole_diameter),
+                    sockets.hole_diameter.into(),
+                    None,
+                )
+            });
+        let socket_pad = |name, pad: &SocketPad, net: &Net| {
+            Pad::smd(
+                name,
+                position!(pad.position.x(), pad.position.y(), angle),
+                size!(pad.size.0, pad.size.1),
+                pad.layer,
+                Some(net.clone()),
+            )
+        };
+
+        socket_holes
+            .chain(
+                sockets
+                    .column_pads
+                    .iter()
+                    .map(|pad| socket_pad("2", pad, &self.column_net)),
+            )
+            .chain(
+                sockets
+                    .column_vias
+                    .iter()
+                    .map(|&via| self.via_pad("2", via, &self.column_net)),
+            )
+            .chain(
+                sockets
+                    .internal_pads
+                    .iter()
+                    .map(|pad| socket_pad("3", pad, &self.internal_net)),
+            )
+            .chain(
+                sockets
+                    .internal_vias
+                    .iter()
+                    .map(|&via| self.via_pad("3", via, &self.internal_net)),
+            )
+            .collect()
+    }
+
+    /// Returns the pad of a via at the given position connecting both layers.
+    fn via_pad(&self, name: &str, via: Point, net: &Net) -> Pad {
+        Pad::new(
+            name,
+            PadType::ThruHole,
+            PadShape::Circle,
+            position!(via.x(), via.y(), self.position.angle()),
+            size!(0.6, 0.6),
+            0.3.into(),
+            Some(net.clone()),
+        )
     }
 }
 
 impl From<Switch> for Footprint {
     fn from(switch: Switch) -> Self {
         let angle = switch.position.angle();
+        let value = switch.pins.value;
+        let name = if switch.sockets.is_some() {
+            format!("{} hot-swap", switch.pins.name)
+        } else {
+            switch.pins.name.to_owned()
+        };
+        let outline = switch.pins.outline_half_size;
+        let courtyard = outline + 0.25;
+        let nets = format!("{}/{}", switch.row_net.name(), switch.column_net.name());
+        let fp_texts = Text::silkscreen(&switch.reference, position!(0, -3.2, angle))
+            .into_iter()
+            .chain(Text::silkscreen(&nets, position!(0, 5.6, angle)))
+            .collect();
 
         Self(
-            "switch_dual_side",
+            "switch_dual_side".to_owned(),
             FootprintSettings {
                 layer: "F.Cu",
-                uuid: Uuid::new(),
+                uuid: Uuid::default(),
                 at: switch.position,
-                descr: "Cherry MX switch with diode dual side",
-                tags: "Cherry MX switch with diode dual side",
+                descr: format!("{name} switch with diode dual side"),
+                tags: format!("{name} switch with diode dual side"),
                 properties: vec![
                     Property::new(
                         "Reference",
                         switch.reference.clone(),
                         position!(0, -7.1, angle),
                     ),
-                    Property::new("Value", "SW_Cherry_MX".to_owned(), position!(0, 7.1, angle)),
+                    Property::new("Value", value.to_owned(), position!(0, 7.1, angle)),
                     Property::new("Footprint", String::new(), position!(0, 0, angle)),
                     Property::new("Datasheet", String::new(), position!(0, 0, angle)),
                     Property::new("Description", String::new(), position!(0, 0, angle)),
                 ],
+                path: None,
                 attr: Attribute::ThroughHole,
                 fp_lines: lines_front_back![
                     (point!(2.25, 2.4), point!(2.25, 4.4), "SilkS"),
                     (point!(2.25, 2.4), point!(-1.65, 2.4), "SilkS"),
                     (point!(2.25, 4.4), point!(-1.65, 4.4), "SilkS"),
                     (point!(1.05, 2.4), point!(1.05, 4.4), "SilkS"),
-                    (point!(6.6, -6.6), point!(-6.6, -6.6), "CrtYd"),
-                    (point!(6.6, 6.6), point!(6.6, -6.6), "CrtYd"),
-                    (point!(-6.6, -6.6), point!(-6.6, 6.6), "CrtYd"),
-                    (point!(-6.6, 6.6), point!(6.6, 6.6), "CrtYd"),
+                    (
+                        point!(courtyard, -courtyard),
+                        point!(-courtyard, -courtyard),
+                        "CrtYd"
+                    ),
+                    (
+                        point!(courtyard, courtyard),
+                        point!(courtyard, -courtyard),
+                        "CrtYd"
+                    ),
+                    (
+                        point!(-courtyard, -courtyard),
+                        point!(-courtyard, courtyard),
+                        "CrtYd"
+                    ),
+                    (
+                        point!(-courtyard, courtyard),
+                        point!(courtyard, courtyard),
+                        "CrtYd"
+                    ),
                     (point!(1.4, 2.5), point!(1.4, 2.5), "Fab"),
                     (point!(1.4, 4.3), point!(1.4, 2.5), "Fab"),
                     (point!(0.75, 3.4), point!(0.35, 3.4), "Fab"),
@@ -165,12 +864,12 @@ impl From<Switch> for Footprint {
                     (point!(-0.25, 3.8), point!(0.35, 3.4), "Fab"),
                     (point!(-1.4, 2.5), point!(-1.4, 4.3), "Fab"),
                     (point!(-1.4, 4.3), point!(1.4, 4.3), "Fab"),
-                    (point!(6.35, -6.35), point!(-6.35, -6.35), "Fab"),
-                    (point!(6.35, 6.35), point!(6.35, -6.35), "Fab"),
-                    (point!(-6.35, -6.35), point!(-6.35, 6.35), "Fab"),
-                    (point!(-6.35, 6.35), point!(6.35, 6.35), "Fab"),
+                    (point!(outline, -outline), point!(-outline, -outline), "Fab"),
+                    (point!(outline, outline), point!(outline, -outline), "Fab"),
+                    (point!(-outline, -outline), point!(-outline, outline), "Fab"),
+                    (point!(-outline, outline), point!(outline, outline), "Fab"),
                 ],
-                fp_texts: Vec::new(),
+                fp_texts,
                 pads: switch.pads(),
             },
         )
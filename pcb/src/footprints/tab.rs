@@ -11,6 +11,8 @@ pub struct Tab {
 }
 
 impl Tab {
+    /// The name of the tab marker footprint.
+    pub const NAME: &'static str = "kikit:Tab";
     /// The width of the tab.
     pub const WIDTH: Length = Length::new(10.0);
 
@@ -25,13 +27,13 @@ impl From<Tab> for Footprint {
         let angle = tab.position.angle();
 
         Self(
-            "kikit:Tab",
+            Tab::NAME.to_owned(),
             FootprintSettings {
                 layer: "F.Cu",
-                uuid: Uuid::new(),
+                uuid: Uuid::default(),
                 at: tab.position,
-                descr: "A marker for placing a tab",
-                tags: "",
+                descr: "A marker for placing a tab".to_owned(),
+                tags: String::new(),
                 properties: vec![
                     Property::new("Reference", String::new(), position!(0, 0, angle)),
                     Property::new("Value", "Tab".to_owned(), position!(-3, -1, angle)),
@@ -39,6 +41,7 @@ impl From<Tab> for Footprint {
                     Property::new("Datasheet", String::new(), position!(0, 0, angle)),
                     Property::new("Description", String::new(), position!(0, 0, angle)),
                 ],
+                path: None,
                 attr: Attribute::Marker,
                 fp_lines: vec![
                     Line::new(point!(0, 0), point!(-3, 0), "Dwgs.User"),
@@ -49,6 +52,7 @@ impl From<Tab> for Footprint {
                 fp_texts: vec![Text::new(
                     "KIKIT: width: 10mm",
                     position!(-5.5, 0, Some(90.deg() + angle.unwrap_or_default())),
+                    "Dwgs.User",
                 )],
                 pads: Vec::new(),
             },
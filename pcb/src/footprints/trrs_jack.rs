@@ -0,0 +1,125 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// A TRRS jack facing upwards.
+pub struct TrrsJack {
+    reference: String,
+    position: Position,
+    nets: [Net; 4],
+}
+
+impl TrrsJack {
+    /// The position of the sleeve pad within the jack.
+    pub const SLEEVE_PAD: Point = Point::new(Length::new(3.6), Length::new(2.2));
+    /// The position of the tip pad within the jack.
+    pub const TIP_PAD: Point = Point::new(Length::new(-3.6), Length::new(4.2));
+    /// The position of the first ring pad within the jack.
+    pub const FIRST_RING_PAD: Point = Point::new(Length::new(-3.6), Length::new(8.7));
+    /// The position of the second ring pad within the jack.
+    pub const SECOND_RING_PAD: Point = Point::new(Length::new(3.6), Length::new(11.2));
+
+    /// Creates a new TRRS jack at the given position.
+    ///
+    /// The nets are given in the order sleeve, tip, first ring and second ring.
+    pub fn new(reference: String, position: Position, nets: [Net; 4]) -> Self {
+        Self {
+            reference,
+            position,
+            nets,
+        }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let via_drill_diameter = 0.3.into();
+
+        let [sleeve_net, tip_net, first_ring_net, second_ring_net] = self.nets;
+        let pad = |name, position: Point, net| {
+            Pad::new(
+                name,
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(position.x(), position.y(), angle),
+                size!(1.4, 2.4),
+                via_drill_diameter,
+                Some(net),
+            )
+        };
+
+        vec![
+            Pad::new(
+                "",
+                PadType::NpThruHole,
+                PadShape::Circle,
+                position!(0, 3.0, angle),
+                size!(1.2, 1.2),
+                1.2.into(),
+                None,
+            ),
+            Pad::new(
+                "",
+                PadType::NpThruHole,
+                PadShape::Circle,
+                position!(0, 10.0, angle),
+                size!(1.2, 1.2),
+                1.2.into(),
+                None,
+            ),
+            pad("S", Self::SLEEVE_PAD, sleeve_net),
+            pad("T", Self::TIP_PAD, tip_net),
+            pad("R1", Self::FIRST_RING_PAD, first_ring_net),
+            pad("R2", Self::SECOND_RING_PAD, second_ring_net),
+        ]
+    }
+}
+
+impl From<TrrsJack> for Footprint {
+    fn from(trrs_jack: TrrsJack) -> Self {
+        let angle = trrs_jack.position.angle();
+
+        Self(
+            "trrs_jack_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: trrs_jack.position,
+                descr: "TRRS jack 3.5mm dual side".to_owned(),
+                tags: "TRRS jack 3.5mm dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        trrs_jack.reference.clone(),
+                        position!(0, 6.5, angle),
+                    ),
+                    Property::new("Value", "TRRS_Jack".to_owned(), position!(0, 15.5, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-4.6, -0.5), point!(4.6, -0.5), "CrtYd"),
+                    (point!(4.6, -0.5), point!(4.6, 14.5), "CrtYd"),
+                    (point!(4.6, 14.5), point!(-4.6, 14.5), "CrtYd"),
+                    (point!(-4.6, 14.5), point!(-4.6, -0.5), "CrtYd"),
+                    (point!(-3, 0), point!(3, 0), "Fab"),
+                    (point!(3, 0), point!(3, 14), "Fab"),
+                    (point!(3, 14), point!(-3, 14), "Fab"),
+                    (point!(-3, 14), point!(-3, 0), "Fab"),
+                    (point!(-3, 12.6), point!(-3, 14), "SilkS"),
+                    (point!(-3, 14), point!(3, 14), "SilkS"),
+                    (point!(3, 14), point!(3, 12.6), "SilkS"),
+                ],
+                fp_texts: Vec::new(),
+                pads: trrs_jack.pads(),
+            },
+        )
+    }
+}
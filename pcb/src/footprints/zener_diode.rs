@@ -0,0 +1,105 @@
+use crate::{
+    footprints::{Attribute, Footprint, FootprintSettings, Pad, PadShape, PadType, Property},
+    kicad_pcb::Net,
+    lines_front_back, point, position,
+    primitives::{Point, Position, Uuid},
+    size,
+    unit::Length,
+};
+
+/// A zener diode in a SOD-80 package.
+pub struct ZenerDiode {
+    reference: String,
+    position: Position,
+    cathode_net: Net,
+    anode_net: Net,
+}
+
+impl ZenerDiode {
+    /// The position of the cathode pad within the diode.
+    pub const CATHODE_PAD: Point = Point::new(Length::new(-1.75), Length::new(0.0));
+    /// The position of the anode pad within the diode.
+    pub const ANODE_PAD: Point = Point::new(Length::new(1.75), Length::new(0.0));
+
+    /// Creates a new zener diode at the given position using the given nets.
+    pub fn new(reference: String, position: Position, cathode_net: Net, anode_net: Net) -> Self {
+        Self {
+            reference,
+            position,
+            cathode_net,
+            anode_net,
+        }
+    }
+
+    fn pads(self) -> Vec<Pad> {
+        let angle = self.position.angle();
+        let via_drill_diameter = 0.3.into();
+
+        vec![
+            Pad::new(
+                "1",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::CATHODE_PAD.x(), Self::CATHODE_PAD.y(), angle),
+                size!(1.3, 1.8),
+                via_drill_diameter,
+                Some(self.cathode_net),
+            ),
+            Pad::new(
+                "2",
+                PadType::ThruHole,
+                PadShape::Roundrect,
+                position!(Self::ANODE_PAD.x(), Self::ANODE_PAD.y(), angle),
+                size!(1.3, 1.8),
+                via_drill_diameter,
+                Some(self.anode_net),
+            ),
+        ]
+    }
+}
+
+impl From<ZenerDiode> for Footprint {
+    fn from(zener_diode: ZenerDiode) -> Self {
+        let angle = zener_diode.position.angle();
+
+        Self(
+            "zener_diode_sod80_dual_side".to_owned(),
+            FootprintSettings {
+                layer: "F.Cu",
+                uuid: Uuid::default(),
+                at: zener_diode.position,
+                descr: "Zener diode SOD-80 dual side".to_owned(),
+                tags: "zener diode SOD-80 MiniMELF dual side".to_owned(),
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        zener_diode.reference.clone(),
+                        position!(0, -1.9, angle),
+                    ),
+                    Property::new("Value", "3.6V".to_owned(), position!(0, 1.9, angle)),
+                    Property::new("Footprint", String::new(), position!(0, 0, angle)),
+                    Property::new("Datasheet", String::new(), position!(0, 0, angle)),
+                    Property::new("Description", String::new(), position!(0, 0, angle)),
+                ],
+                path: None,
+                attr: Attribute::ThroughHole,
+                fp_lines: lines_front_back![
+                    (point!(-2.65, -1.15), point!(2.65, -1.15), "CrtYd"),
+                    (point!(2.65, -1.15), point!(2.65, 1.15), "CrtYd"),
+                    (point!(2.65, 1.15), point!(-2.65, 1.15), "CrtYd"),
+                    (point!(-2.65, 1.15), point!(-2.65, -1.15), "CrtYd"),
+                    (point!(-1.75, -0.8), point!(1.75, -0.8), "Fab"),
+                    (point!(1.75, -0.8), point!(1.75, 0.8), "Fab"),
+                    (point!(1.75, 0.8), point!(-1.75, 0.8), "Fab"),
+                    (point!(-1.75, 0.8), point!(-1.75, -0.8), "Fab"),
+                    (point!(-1.0, -0.8), point!(-1.0, 0.8), "Fab"),
+                    (point!(-2.6, -1.1), point!(1.0, -1.1), "SilkS"),
+                    (point!(-2.6, -1.1), point!(-2.6, 1.1), "SilkS"),
+                    (point!(-2.6, 1.1), point!(1.0, 1.1), "SilkS"),
+                ],
+                fp_texts: Vec::new(),
+                pads: zener_diode.pads(),
+            },
+        )
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::Write;
+
+use glam::{dvec2, DVec2};
+
+use crate::unit::{Angle, Length};
+
+/// A shape used for drawing and flashing objects.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Aperture {
+    /// A circle with the given diameter.
+    Circle(Length),
+    /// A rectangle with rounded corners, rotated counterclockwise by the given angle.
+    RoundedRectangle {
+        /// The width of the rectangle.
+        width: Length,
+        /// The height of the rectangle.
+        height: Length,
+        /// The radius of the corners.
+        radius: Length,
+        /// The rotation angle.
+        angle: Angle,
+    },
+}
+
+impl Aperture {
+    /// Returns the Gerber definition of the aperture using the given D code.
+    pub fn definition(self, code: usize) -> String {
+        match self {
+            Aperture::Circle(diameter) => {
+                format!("%ADD{code}C,{:.6}*%\n", f64::from(diameter))
+            }
+            Aperture::RoundedRectangle {
+                width,
+                height,
+                radius,
+                angle,
+            } => {
+                let inner_width = width - 2 * radius;
+                let inner_height = height - 2 * radius;
+                let angle = f64::from(angle);
+
+                let mut definition = format!("%AMRoundRect{code}*\n");
+                for (width, height) in [(width, inner_height), (inner_width, height)] {
+                    if width > Length::default() && height > Length::default() {
+                        writeln!(
+                            definition,
+                            "21,1,{:.6},{:.6},0,0,{angle:.6}*",
+                            f64::from(width),
+                            f64::from(height),
+                        )
+                        .expect("writing to a string should never fail");
+                    }
+                }
+
+                // The corners are rounded by circles, which are rotated manually
+                if radius > Length::default() {
+                    let (sin, cos) = angle.to_radians().sin_cos();
+                    let corner = dvec2(f64::from(inner_width), f64::from(inner_height)) / 2.0;
+
+                    for sign in [
+                        dvec2(1.0, 1.0),
+                        dvec2(-1.0, 1.0),
+                        dvec2(-1.0, -1.0),
+                        dvec2(1.0, -1.0),
+                    ] {
+                        let DVec2 { x, y } = corner * sign;
+                        writeln!(
+                            definition,
+                            "1,1,{:.6},{:.6},{:.6}*",
+                            2.0 * f64::from(radius),
+                            cos * x - sin * y,
+                            sin * x + cos * y,
+                        )
+                        .expect("writing to a string should never fail");
+                    }
+                }
+
+                definition.push_str("%\n");
+                writeln!(definition, "%ADD{code}RoundRect{code}*%")
+                    .expect("writing to a string should never fail");
+
+                definition
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::Write;
+
+use crate::{
+    gerber::{to_vector, FabricationFile, GENERATION_SOFTWARE},
+    primitives::Point,
+    unit::Length,
+};
+
+/// An Excellon drill file containing either the plated or non-plated holes of a PCB.
+pub struct DrillFile {
+    plated: bool,
+    holes: Vec<(Point, Length)>,
+}
+
+impl DrillFile {
+    /// Creates a new empty drill file for plated or non-plated holes.
+    pub fn new(plated: bool) -> Self {
+        Self {
+            plated,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Adds a hole with the given diameter at the given position.
+    pub fn add_hole(&mut self, position: Point, diameter: Length) {
+        self.holes.push((position, diameter));
+    }
+
+    /// Converts the holes into an Excellon drill file, using one tool per diameter.
+    pub fn into_file(self) -> FabricationFile {
+        let (file_function, name) = if self.plated {
+            ("Plated,1,2,PTH", "PTH")
+        } else {
+            ("NonPlated,1,2,NPTH", "NPTH")
+        };
+
+        let mut diameters: Vec<_> = self.holes.iter().map(|&(_, diameter)| diameter).collect();
+        diameters.sort();
+        diameters.dedup();
+
+        let mut content = format!(
+            "M48\n\
+             ; #@! TF.GenerationSoftware,{GENERATION_SOFTWARE}\n\
+             ; #@! TF.FileFunction,{file_function}\n\
+             FMAT,2\n\
+             METRIC\n"
+        );
+
+        for (index, &diameter) in diameters.iter().enumerate() {
+            writeln!(content, "T{}C{:.3}", index + 1, f64::from(diameter))
+                .expect("writing to a string should never fail");
+        }
+
+        content.push_str("%\nG90\nG05\n");
+
+        for (index, &diameter) in diameters.iter().enumerate() {
+            writeln!(content, "T{}", index + 1).expect("writing to a string should never fail");
+
+            for &(position, _) in self.holes.iter().filter(|&&(_, hole)| hole == diameter) {
+                let vector = to_vector(position);
+
+                writeln!(content, "X{:.3}Y{:.3}", vector.x, vector.y)
+                    .expect("writing to a string should never fail");
+            }
+        }
+
+        content.push_str("M30\n");
+
+        FabricationFile {
+            name: format!("{name}.drl"),
+            content,
+        }
+    }
+}
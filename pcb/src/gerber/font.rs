@@ -0,0 +1,336 @@
+/// The height of capital letters in font units.
+const CAP_HEIGHT: i8 = 6;
+/// The spacing between adjacent glyphs in font units.
+const SPACING: i8 = 2;
+/// The width of a space in font units.
+const SPACE_WIDTH: i8 = 2;
+
+/// A glyph given by polylines in font units, with the baseline at zero and the Y axis pointing
+/// upwards.
+///
+/// Polylines consisting of a single point are dots.
+type Glyph = &'static [&'static [(i8, i8)]];
+
+/// Returns the polylines of the given text in units of its height, centered at the origin.
+///
+/// The Y axis points upwards and characters without a glyph are replaced with a question mark.
+pub fn polylines(text: &str) -> Vec<Vec<(f32, f32)>> {
+    let mut polylines = Vec::new();
+    let mut x = 0_i16;
+
+    for character in text.chars() {
+        let glyph = glyph(character).unwrap_or_else(|| glyph('?').expect("glyph should exist"));
+        let width = glyph
+            .iter()
+            .flat_map(|polyline| polyline.iter().map(|&(x, _)| x))
+            .max()
+            .unwrap_or(SPACE_WIDTH);
+
+        polylines.extend(glyph.iter().map(|polyline| {
+            polyline
+                .iter()
+                .map(|&(glyph_x, glyph_y)| (f32::from(x + i16::from(glyph_x)), f32::from(glyph_y)))
+                .collect::<Vec<_>>()
+        }));
+
+        x += i16::from(width + SPACING);
+    }
+
+    let center_x = f32::from((x - i16::from(SPACING)).max(0)) / 2.0;
+    let center_y = f32::from(CAP_HEIGHT) / 2.0;
+    let scale = f32::from(CAP_HEIGHT);
+
+    for polyline in &mut polylines {
+        for (x, y) in polyline {
+            *x = (*x - center_x) / scale;
+            *y = (*y - center_y) / scale;
+        }
+    }
+
+    polylines
+}
+
+/// Returns the glyph of the given character if there is one.
+#[allow(clippy::too_many_lines)]
+fn glyph(character: char) -> Option<Glyph> {
+    let glyph: Glyph = match character {
+        ' ' => &[],
+        '!' => &[&[(0, 2), (0, 6)], &[(0, 0)]],
+        '"' => &[&[(0, 6), (0, 4)], &[(2, 6), (2, 4)]],
+        '#' => &[
+            &[(1, 0), (1, 6)],
+            &[(3, 0), (3, 6)],
+            &[(0, 2), (4, 2)],
+            &[(0, 4), (4, 4)],
+        ],
+        '%' => &[&[(0, 0), (4, 6)], &[(0, 5)], &[(4, 1)]],
+        '\'' => &[&[(0, 6), (0, 4)]],
+        '(' => &[&[(1, 6), (0, 5), (0, 1), (1, 0)]],
+        ')' => &[&[(0, 6), (1, 5), (1, 1), (0, 0)]],
+        '*' => &[&[(2, 1), (2, 5)], &[(0, 2), (4, 4)], &[(0, 4), (4, 2)]],
+        '+' => &[&[(0, 3), (4, 3)], &[(2, 1), (2, 5)]],
+        ',' => &[&[(1, 0), (0, -1)]],
+        '-' => &[&[(0, 3), (3, 3)]],
+        '.' => &[&[(0, 0)]],
+        '/' => &[&[(0, 0), (4, 6)]],
+        '0' => &[
+            &[
+                (1, 0),
+                (0, 1),
+                (0, 5),
+                (1, 6),
+                (3, 6),
+                (4, 5),
+                (4, 1),
+                (3, 0),
+                (1, 0),
+            ],
+            &[(0, 1), (4, 5)],
+        ],
+        '1' => &[&[(0, 5), (1, 6), (1, 0)], &[(0, 0), (2, 0)]],
+        '2' => &[&[(0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (0, 0), (4, 0)]],
+        '3' => &[
+            &[
+                (0, 5),
+                (1, 6),
+                (3, 6),
+                (4, 5),
+                (4, 4),
+                (3, 3),
+                (4, 2),
+                (4, 1),
+                (3, 0),
+                (1, 0),
+                (0, 1),
+            ],
+            &[(1, 3), (3, 3)],
+        ],
+        '4' => &[&[(3, 0), (3, 6), (0, 2), (4, 2)]],
+        '5' => &[&[
+            (4, 6),
+            (0, 6),
+            (0, 3),
+            (3, 3),
+            (4, 2),
+            (4, 1),
+            (3, 0),
+            (1, 0),
+            (0, 1),
+        ]],
+        '6' => &[&[
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 2),
+            (3, 3),
+            (0, 3),
+        ]],
+        '7' => &[&[(0, 6), (4, 6), (1, 0)]],
+        '8' => &[&[
+            (1, 3),
+            (0, 4),
+            (0, 5),
+            (1, 6),
+            (3, 6),
+            (4, 5),
+            (4, 4),
+            (3, 3),
+            (1, 3),
+            (0, 2),
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 2),
+            (3, 3),
+        ]],
+        '9' => &[&[
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 4),
+            (1, 3),
+            (4, 3),
+        ]],
+        ':' => &[&[(0, 0)], &[(0, 4)]],
+        ';' => &[&[(1, 4)], &[(1, 0), (0, -1)]],
+        '<' => &[&[(4, 5), (0, 3), (4, 1)]],
+        '=' => &[&[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+        '>' => &[&[(0, 5), (4, 3), (0, 1)]],
+        '?' => &[
+            &[(0, 5), (1, 6), (3, 6), (4, 5), (4, 4), (2, 3), (2, 2)],
+            &[(2, 0)],
+        ],
+        'A' => &[&[(0, 0), (0, 4), (2, 6), (4, 4), (4, 0)], &[(0, 3), (4, 3)]],
+        'B' => &[
+            &[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)],
+            &[(3, 3), (4, 2), (4, 1), (3, 0), (0, 0)],
+        ],
+        'C' => &[&[
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+        ]],
+        'D' => &[&[(0, 0), (0, 6), (2, 6), (4, 4), (4, 2), (2, 0), (0, 0)]],
+        'E' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)], &[(0, 3), (3, 3)]],
+        'F' => &[&[(4, 6), (0, 6), (0, 0)], &[(0, 3), (3, 3)]],
+        'G' => &[&[
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 3),
+            (2, 3),
+        ]],
+        'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+        'I' => &[&[(0, 6), (2, 6)], &[(1, 6), (1, 0)], &[(0, 0), (2, 0)]],
+        'J' => &[&[(4, 6), (4, 1), (3, 0), (1, 0), (0, 1)]],
+        'K' => &[&[(0, 0), (0, 6)], &[(4, 6), (0, 2)], &[(1, 3), (4, 0)]],
+        'L' => &[&[(0, 6), (0, 0), (4, 0)]],
+        'M' => &[&[(0, 0), (0, 6), (2, 3), (4, 6), (4, 0)]],
+        'N' => &[&[(0, 0), (0, 6), (4, 0), (4, 6)]],
+        'O' => &[&[
+            (1, 0),
+            (0, 1),
+            (0, 5),
+            (1, 6),
+            (3, 6),
+            (4, 5),
+            (4, 1),
+            (3, 0),
+            (1, 0),
+        ]],
+        'P' => &[&[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)]],
+        'Q' => &[
+            &[
+                (1, 0),
+                (0, 1),
+                (0, 5),
+                (1, 6),
+                (3, 6),
+                (4, 5),
+                (4, 1),
+                (3, 0),
+                (1, 0),
+            ],
+            &[(2, 2), (4, 0)],
+        ],
+        'R' => &[
+            &[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)],
+            &[(2, 3), (4, 0)],
+        ],
+        'S' => &[&[
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 4),
+            (1, 3),
+            (3, 3),
+            (4, 2),
+            (4, 1),
+            (3, 0),
+            (1, 0),
+            (0, 1),
+        ]],
+        'T' => &[&[(0, 6), (4, 6)], &[(2, 6), (2, 0)]],
+        'U' => &[&[(0, 6), (0, 1), (1, 0), (3, 0), (4, 1), (4, 6)]],
+        'V' => &[&[(0, 6), (2, 0), (4, 6)]],
+        'W' => &[&[(0, 6), (1, 0), (2, 4), (3, 0), (4, 6)]],
+        'X' => &[&[(0, 6), (4, 0)], &[(0, 0), (4, 6)]],
+        'Y' => &[&[(0, 6), (2, 3), (4, 6)], &[(2, 3), (2, 0)]],
+        'Z' => &[&[(0, 6), (4, 6), (0, 0), (4, 0)]],
+        '[' => &[&[(1, 6), (0, 6), (0, 0), (1, 0)]],
+        '\\' => &[&[(0, 6), (4, 0)]],
+        ']' => &[&[(0, 6), (1, 6), (1, 0), (0, 0)]],
+        '_' => &[&[(0, -1), (4, -1)]],
+        'a' => &[
+            &[(1, 4), (3, 4), (4, 3), (4, 0)],
+            &[(4, 2), (1, 2), (0, 1), (1, 0), (4, 0)],
+        ],
+        'b' => &[&[(0, 6), (0, 0), (3, 0), (4, 1), (4, 3), (3, 4), (0, 4)]],
+        'c' => &[&[(4, 4), (1, 4), (0, 3), (0, 1), (1, 0), (4, 0)]],
+        'd' => &[&[(4, 6), (4, 0), (1, 0), (0, 1), (0, 3), (1, 4), (4, 4)]],
+        'e' => &[&[
+            (0, 2),
+            (4, 2),
+            (4, 3),
+            (3, 4),
+            (1, 4),
+            (0, 3),
+            (0, 1),
+            (1, 0),
+            (4, 0),
+        ]],
+        'f' => &[&[(1, 0), (1, 5), (2, 6), (3, 6)], &[(0, 4), (3, 4)]],
+        'g' => &[
+            &[(4, 4), (4, -1), (3, -2), (0, -2)],
+            &[(4, 4), (1, 4), (0, 3), (0, 1), (1, 0), (4, 0)],
+        ],
+        'h' => &[&[(0, 6), (0, 0)], &[(0, 4), (3, 4), (4, 3), (4, 0)]],
+        'i' => &[&[(0, 0), (0, 4)], &[(0, 6)]],
+        'j' => &[&[(2, 4), (2, -1), (1, -2), (0, -2)], &[(2, 6)]],
+        'k' => &[&[(0, 6), (0, 0)], &[(3, 4), (0, 1)], &[(1, 2), (3, 0)]],
+        'l' => &[&[(0, 6), (0, 1), (1, 0)]],
+        'm' => &[
+            &[(0, 0), (0, 4)],
+            &[(0, 3), (1, 4), (2, 3), (2, 0)],
+            &[(2, 3), (3, 4), (4, 3), (4, 0)],
+        ],
+        'n' => &[&[(0, 0), (0, 4)], &[(0, 3), (1, 4), (3, 4), (4, 3), (4, 0)]],
+        'o' => &[&[
+            (1, 0),
+            (0, 1),
+            (0, 3),
+            (1, 4),
+            (3, 4),
+            (4, 3),
+            (4, 1),
+            (3, 0),
+            (1, 0),
+        ]],
+        'p' => &[&[(0, -2), (0, 4), (3, 4), (4, 3), (4, 1), (3, 0), (0, 0)]],
+        'q' => &[&[(4, -2), (4, 4), (1, 4), (0, 3), (0, 1), (1, 0), (4, 0)]],
+        'r' => &[&[(0, 0), (0, 4)], &[(0, 2), (2, 4), (3, 4)]],
+        's' => &[&[
+            (4, 4),
+            (1, 4),
+            (0, 3),
+            (1, 2),
+            (3, 2),
+            (4, 1),
+            (3, 0),
+            (0, 0),
+        ]],
+        't' => &[&[(1, 6), (1, 1), (2, 0), (3, 0)], &[(0, 4), (3, 4)]],
+        'u' => &[&[(0, 4), (0, 1), (1, 0), (3, 0), (4, 1)], &[(4, 4), (4, 0)]],
+        'v' => &[&[(0, 4), (2, 0), (4, 4)]],
+        'w' => &[&[(0, 4), (1, 0), (2, 3), (3, 0), (4, 4)]],
+        'x' => &[&[(0, 4), (4, 0)], &[(0, 0), (4, 4)]],
+        'y' => &[&[(0, 4), (2, 0)], &[(4, 4), (1, -2)]],
+        'z' => &[&[(0, 4), (4, 4), (0, 0), (4, 0)]],
+        '|' => &[&[(0, -2), (0, 6)]],
+        _ => return None,
+    };
+
+    Some(glyph)
+}
@@ -0,0 +1,162 @@
+use std::fmt::Write;
+
+use glam::DVec2;
+
+use crate::{
+    gerber::{
+        circle_center, to_integer, to_vector, Aperture, FabricationFile, GENERATION_SOFTWARE,
+    },
+    primitives::Point,
+    unit::Length,
+};
+
+/// The polarity of a Gerber file.
+#[derive(Clone, Copy)]
+pub enum Polarity {
+    /// The plotted objects represent material.
+    Positive,
+    /// The plotted objects represent the absence of material.
+    Negative,
+}
+
+/// A Gerber file containing a single layer of a PCB.
+pub struct Layer {
+    name: &'static str,
+    file_function: &'static str,
+    polarity: Polarity,
+    apertures: Vec<Aperture>,
+    current_aperture: Option<usize>,
+    current_point: Option<Point>,
+    commands: String,
+}
+
+impl Layer {
+    /// The first D code available for apertures.
+    const FIRST_APERTURE_CODE: usize = 10;
+
+    /// Creates a new empty layer with the given KiCAD name and Gerber file function.
+    pub fn new(name: &'static str, file_function: &'static str, polarity: Polarity) -> Self {
+        Self {
+            name,
+            file_function,
+            polarity,
+            apertures: Vec::new(),
+            current_aperture: None,
+            current_point: None,
+            commands: String::new(),
+        }
+    }
+
+    /// Returns the KiCAD name of the layer.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Draws a line with the given width.
+    pub fn line(&mut self, start: Point, end: Point, width: Length) {
+        self.move_to(start, Aperture::Circle(width));
+        self.command(&format!("G01{}D01", coordinates(to_vector(end))));
+        self.current_point = Some(end);
+    }
+
+    /// Draws an arc through the given points with the given width.
+    ///
+    /// Falls back to a line if the points are collinear.
+    pub fn arc(&mut self, start: Point, mid: Point, end: Point, width: Length) {
+        let start_vector = to_vector(start);
+        let mid_vector = to_vector(mid);
+        let end_vector = to_vector(end);
+
+        let Some(center) = circle_center(start_vector, mid_vector, end_vector) else {
+            self.line(start, end, width);
+            return;
+        };
+
+        let counterclockwise = (mid_vector - start_vector).perp_dot(end_vector - mid_vector) > 0.0;
+        let mode = if counterclockwise { "G03" } else { "G02" };
+        let offset = center - start_vector;
+
+        self.move_to(start, Aperture::Circle(width));
+        self.command(&format!(
+            "{mode}{}I{}J{}D01",
+            coordinates(end_vector),
+            to_integer(offset.x),
+            to_integer(offset.y),
+        ));
+        self.current_point = Some(end);
+    }
+
+    /// Flashes the given aperture at the given position.
+    pub fn flash(&mut self, position: Point, aperture: Aperture) {
+        self.select_aperture(aperture);
+        self.command(&format!("{}D03", coordinates(to_vector(position))));
+        self.current_point = Some(position);
+    }
+
+    /// Converts the layer into a Gerber file.
+    pub fn into_file(self) -> FabricationFile {
+        let polarity = match self.polarity {
+            Polarity::Positive => "Positive",
+            Polarity::Negative => "Negative",
+        };
+
+        let mut content = format!(
+            "%TF.GenerationSoftware,{GENERATION_SOFTWARE}*%\n\
+             %TF.FileFunction,{}*%\n\
+             %TF.FilePolarity,{polarity}*%\n\
+             %FSLAX46Y46*%\n\
+             %MOMM*%\n\
+             %LPD*%\n\
+             G75*\n",
+            self.file_function,
+        );
+
+        for (index, aperture) in self.apertures.into_iter().enumerate() {
+            content.push_str(&aperture.definition(index + Self::FIRST_APERTURE_CODE));
+        }
+
+        content.push_str(&self.commands);
+        content.push_str("M02*\n");
+
+        FabricationFile {
+            name: format!("{}.gbr", self.name.replace('.', "_")),
+            content,
+        }
+    }
+
+    /// Moves to the given point using the given aperture without drawing.
+    fn move_to(&mut self, point: Point, aperture: Aperture) {
+        self.select_aperture(aperture);
+
+        if self.current_point != Some(point) {
+            self.command(&format!("{}D02", coordinates(to_vector(point))));
+        }
+    }
+
+    /// Selects the given aperture, defining it if required.
+    fn select_aperture(&mut self, aperture: Aperture) {
+        let index = self
+            .apertures
+            .iter()
+            .position(|&defined_aperture| defined_aperture == aperture)
+            .unwrap_or_else(|| {
+                self.apertures.push(aperture);
+                self.apertures.len() - 1
+            });
+
+        if self.current_aperture != Some(index) {
+            self.command(&format!("D{}", index + Self::FIRST_APERTURE_CODE));
+            self.current_aperture = Some(index);
+        }
+    }
+
+    /// Appends the given command to the layer.
+    fn command(&mut self, command: &str) {
+        writeln!(self.commands, "{command}*").expect("writing to a string should never fail");
+    }
+}
+
+/// Formats the given vector as Gerber coordinates.
+fn coordinates(vector: DVec2) -> String {
+    format!("X{}Y{}", to_integer(vector.x), to_integer(vector.y))
+}
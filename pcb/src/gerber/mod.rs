@@ -0,0 +1,184 @@
+mod aperture;
+mod excellon;
+mod font;
+mod layer;
+
+use glam::{dvec2, DVec2};
+
+use crate::{
+    primitives::{Point, Position, Size},
+    unit::{Length, VALUE_TO_UNIT},
+};
+
+pub use aperture::Aperture;
+
+use excellon::DrillFile;
+use layer::{Layer, Polarity};
+
+/// The name of the software generating the fabrication files.
+const GENERATION_SOFTWARE: &str = "concavum_customizer";
+
+/// A file required for manufacturing a PCB.
+pub struct FabricationFile {
+    /// The name of the file.
+    pub name: String,
+    /// The content of the file.
+    pub content: String,
+}
+
+/// A trait for plotting objects onto the fabrication layers.
+pub trait Plot {
+    /// Plots `self` using the given plotter.
+    fn plot(&self, plotter: &mut Plotter);
+}
+
+/// A plotter turning the objects of a PCB into Gerber and Excellon drill files.
+pub struct Plotter {
+    layers: Vec<Layer>,
+    plated_holes: DrillFile,
+    non_plated_holes: DrillFile,
+}
+
+impl Default for Plotter {
+    fn default() -> Self {
+        Self {
+            layers: vec![
+                Layer::new("F.Cu", "Copper,L1,Top", Polarity::Positive),
+                Layer::new("B.Cu", "Copper,L2,Bot", Polarity::Positive),
+                Layer::new("F.Mask", "Soldermask,Top", Polarity::Negative),
+                Layer::new("B.Mask", "Soldermask,Bot", Polarity::Negative),
+                Layer::new("F.SilkS", "Legend,Top", Polarity::Positive),
+                Layer::new("B.SilkS", "Legend,Bot", Polarity::Positive),
+                Layer::new("Edge.Cuts", "Profile,NP", Polarity::Positive),
+            ],
+            plated_holes: DrillFile::new(true),
+            non_plated_holes: DrillFile::new(false),
+        }
+    }
+}
+
+impl Plotter {
+    /// Draws a line with the given width onto the given layer.
+    ///
+    /// Layers not required for manufacturing are ignored.
+    pub fn line(&mut self, layer: &str, start: Point, end: Point, width: Length) {
+        if let Some(layer) = self.layer(layer) {
+            layer.line(start, end, width);
+        }
+    }
+
+    /// Draws an arc through the given points with the given width onto the given layer.
+    ///
+    /// Layers not required for manufacturing are ignored.
+    pub fn arc(&mut self, layer: &str, start: Point, mid: Point, end: Point, width: Length) {
+        if let Some(layer) = self.layer(layer) {
+            layer.arc(start, mid, end, width);
+        }
+    }
+
+    /// Draws the given text with the given font size and stroke width centered at the given
+    /// position onto the given layer, using a stroke font.
+    ///
+    /// Mirrored texts are readable from the bottom. Layers not required for manufacturing are
+    /// ignored.
+    pub fn text(
+        &mut self,
+        layer: &str,
+        text: &str,
+        position: Position,
+        size: Size,
+        width: Length,
+        mirrored: bool,
+    ) {
+        let Some(layer) = self.layer(layer) else {
+            return;
+        };
+        let direction = if mirrored { -1.0 } else { 1.0 };
+
+        for polyline in font::polylines(text) {
+            let points: Vec<_> = polyline
+                .into_iter()
+                .map(|(x, y)| {
+                    position + Point::new(direction * x * size.width(), -y * size.height())
+                })
+                .collect();
+
+            if let [point] = points[..] {
+                layer.line(point, point, width);
+            }
+            for segment in points.windows(2) {
+                layer.line(segment[0], segment[1], width);
+            }
+        }
+    }
+
+    /// Flashes the given aperture at the given position onto the given layer.
+    ///
+    /// Layers not required for manufacturing are ignored.
+    pub fn flash(&mut self, layer: &str, position: Point, aperture: Aperture) {
+        if let Some(layer) = self.layer(layer) {
+            layer.flash(position, aperture);
+        }
+    }
+
+    /// Adds a hole with the given diameter at the given position.
+    pub fn drill(&mut self, position: Point, diameter: Length, plated: bool) {
+        if plated {
+            self.plated_holes.add_hole(position, diameter);
+        } else {
+            self.non_plated_holes.add_hole(position, diameter);
+        }
+    }
+
+    /// Returns the Gerber and drill files containing everything plotted so far.
+    pub fn finish(self) -> Vec<FabricationFile> {
+        self.layers
+            .into_iter()
+            .map(Layer::into_file)
+            .chain([
+                self.plated_holes.into_file(),
+                self.non_plated_holes.into_file(),
+            ])
+            .collect()
+    }
+
+    /// Returns the layer with the given name if it is required for manufacturing.
+    fn layer(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.name() == name)
+    }
+}
+
+/// Converts the given point to the coordinate system of the fabrication files.
+///
+/// In contrast to KiCAD, the Y axis of the fabrication files points upwards.
+fn to_vector(point: Point) -> DVec2 {
+    dvec2(f64::from(point.x()), -f64::from(point.y()))
+}
+
+/// Converts the given value in millimeters to an integer with six decimal places.
+#[allow(clippy::cast_possible_truncation)]
+fn to_integer(value: f64) -> i64 {
+    (value * f64::from(VALUE_TO_UNIT)).round() as i64
+}
+
+/// Calculates the center of the circle through the given points.
+///
+/// Returns `None` if the points are collinear.
+pub fn circle_center(first: DVec2, second: DVec2, third: DVec2) -> Option<DVec2> {
+    let determinant = 2.0 * (second - first).perp_dot(third - first);
+
+    (determinant.abs() > f64::EPSILON).then(|| {
+        let first_square = first.length_squared();
+        let second_square = second.length_squared();
+        let third_square = third.length_squared();
+
+        let x = first_square * (second.y - third.y)
+            + second_square * (third.y - first.y)
+            + third_square * (first.y - second.y);
+        let y = first_square * (third.x - second.x)
+            + second_square * (first.x - third.x)
+            + third_square * (second.x - first.x);
+
+        DVec2::new(x, y) / determinant
+    })
+}
@@ -0,0 +1,85 @@
+mod pro_micro;
+mod rp2040_zero;
+
+use config::{Config, Controller};
+
+use crate::{
+    interface_pcb::{ORIGIN_POSITION, OUTLINE_WIDTH, SIDE_PORT_OFFSET, THICKNESS},
+    kicad_pcb::KicadPcb,
+    matrix_pcb::AddPath,
+    point, position,
+    primitives::{Position, UuidMode},
+    unit::{IntoAngle, Length},
+};
+
+/// The nets of the FFC connector pads and the Pro Micro pins they are connected to.
+///
+/// The pins match the wiring used by the firmware.
+const FFC_CONNECTOR_PINS: [(&str, &str); 12] = [
+    ("COL1", "19"),
+    ("ROW6", "18"),
+    ("ROW5", "15"),
+    ("ROW4", "14"),
+    ("ROW3", "16"),
+    ("ROW2", "10"),
+    ("COL2", "4"),
+    ("COL3", "5"),
+    ("COL4", "6"),
+    ("COL5", "7"),
+    ("COL6", "8"),
+    ("ROW1", "9"),
+];
+
+/// A builder for the interface PCB.
+pub struct Builder {
+    pcb: KicadPcb,
+    controller: Controller,
+}
+
+impl Builder {
+    /// Creates a new builder from the given config, creating UUIDs using the given mode.
+    pub fn from_config(config: &Config, uuid_mode: UuidMode) -> Self {
+        Self {
+            pcb: KicadPcb::new(THICKNESS, uuid_mode),
+            controller: config.keyboard.controller,
+        }
+    }
+
+    /// Builds the interface PCB.
+    pub fn build(mut self) -> KicadPcb {
+        let ground_net = self.pcb.create_net("GND".to_owned());
+
+        // The RP2040-Zero is smaller than the Pro Micro footprint and has a different pinout
+        if self.controller == Controller::Rp2040Zero {
+            self.add_rp2040_zero(&ground_net);
+        } else {
+            self.add_pro_micro(&ground_net);
+        }
+
+        self.pcb
+    }
+
+    /// Adds the outline with the given size to the PCB.
+    fn add_outline(&mut self, width: Length, height: Length) {
+        self.pcb.add_outline_polygon(
+            &[
+                ORIGIN_POSITION + point!(0, 0),
+                ORIGIN_POSITION + point!(width, 0),
+                ORIGIN_POSITION + point!(width, height),
+                ORIGIN_POSITION + point!(0, height),
+            ],
+            OUTLINE_WIDTH,
+        );
+    }
+}
+
+/// Returns the position of the FFC connector at the inner edge of a PCB with the given height,
+/// centered at the given offset from the outer corner.
+fn ffc_connector_position(offset: Length, height: Length) -> Position {
+    ORIGIN_POSITION + position!(offset, height - 1.5.into(), Some(180.deg()))
+}
+
+/// Returns the position of the TRRS jack or power switch.
+fn side_port_position() -> Position {
+    ORIGIN_POSITION + position!(SIDE_PORT_OFFSET, 0, None)
+}
@@ -0,0 +1,321 @@
+use config::Controller;
+
+use crate::{
+    footprints::{
+        BatteryPads, FfcConnector, ProMicro, Resistor, SlideSwitch, TrrsJack, ZenerDiode,
+    },
+    interface_pcb::{
+        builder::{ffc_connector_position, side_port_position, Builder, FFC_CONNECTOR_PINS},
+        CHAMFER_DEPTH, ORIGIN_POSITION, TRACK_CLEARANCE, TRACK_WIDTH,
+    },
+    kicad_pcb::Net,
+    matrix_pcb::{AddPath, BOTTOM_LAYER, TOP_LAYER},
+    path::Path,
+    position,
+    primitives::{Point, Position},
+    unit::{IntoAngle, Length},
+};
+
+/// The width of the interface PCB.
+const WIDTH: Length = Length::new(36.0);
+/// The height of the interface PCB.
+const HEIGHT: Length = Length::new(42.0);
+/// The offset of the USB port from the outer corner, matching the cutout in the case.
+const USB_PORT_OFFSET: Length = Length::new(24.9);
+/// The clearance between the controller pins and the tracks routed around them.
+const PIN_CLEARANCE: Length = Length::new(1.15);
+
+impl Builder {
+    /// Adds the controller with the Pro Micro footprint, its connections and the outline to the
+    /// PCB.
+    pub(super) fn add_pro_micro(&mut self, ground_net: &Net) {
+        let mut controller_nets = vec![("GND", ground_net.clone())];
+
+        controller_nets.extend(self.add_pro_micro_ffc_connector());
+        if self.controller.is_wireless() {
+            controller_nets.extend(self.add_power_switch(ground_net));
+        } else {
+            controller_nets.extend(self.add_pro_micro_jack(ground_net));
+        }
+
+        // The ATmega32U4 and nRF52840 detect the USB connection by themselves
+        if self.controller == Controller::Kb2040 {
+            controller_nets.extend(self.add_pro_micro_usb_detection(ground_net));
+        }
+
+        self.add_ground_tracks(ground_net);
+        self.pcb.add_footprint(
+            ProMicro::new("U1".to_owned(), controller_position(), controller_nets).into(),
+        );
+        self.add_outline(WIDTH, HEIGHT);
+    }
+
+    /// Adds the FFC connector and the tracks to the controller to the PCB.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_pro_micro_ffc_connector(&mut self) -> Vec<(&'static str, Net)> {
+        let position = ffc_connector_position(USB_PORT_OFFSET, HEIGHT);
+        let bottom_pin = controller_pin("10");
+        let nets = FFC_CONNECTOR_PINS.map(|(name, _)| self.pcb.create_net(name.to_owned()));
+
+        for (index, ((_, pin), net)) in FFC_CONNECTOR_PINS.iter().zip(&nets).enumerate() {
+            let pad = position + FfcConnector::pad_position(index);
+            let pin = controller_pin(pin);
+
+            let points = if pin.x() < pad.x() {
+                vec![pin, Point::new(pad.x(), pin.y()), pad]
+            } else {
+                // Pins of the right column are connected around the outside of the controller,
+                // nesting the tracks to keep them from crossing
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let track_index = (5 - index) as i32;
+                let offset = PIN_CLEARANCE + track_index * (TRACK_WIDTH + TRACK_CLEARANCE);
+                let x = pin.x() + offset;
+                let y = bottom_pin.y() + offset;
+
+                vec![
+                    pin,
+                    Point::new(x, pin.y()),
+                    Point::new(x, y),
+                    Point::new(pad.x(), y),
+                    pad,
+                ]
+            };
+
+            self.pcb.add_track(
+                &Path::new(points).chamfer(CHAMFER_DEPTH),
+                TRACK_WIDTH,
+                TOP_LAYER,
+                net,
+            );
+        }
+
+        let ffc_connector = FfcConnector::new("J1".to_owned(), position, nets.clone());
+        self.pcb.add_footprint(ffc_connector.into());
+
+        FFC_CONNECTOR_PINS
+            .iter()
+            .map(|(_, pin)| *pin)
+            .zip(nets)
+            .collect()
+    }
+
+    /// Adds the TRRS jack for connecting the halves and its tracks to the PCB.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_pro_micro_jack(&mut self, ground_net: &Net) -> Vec<(&'static str, Net)> {
+        let position = side_port_position();
+        let power_net = self.pcb.create_net("VCC".to_owned());
+        let transmit_net = self.pcb.create_net("SERIAL_TX".to_owned());
+        let receive_net = self.pcb.create_net("SERIAL_RX".to_owned());
+
+        let sleeve_pad = position + TrrsJack::SLEEVE_PAD;
+        let tip_pad = position + TrrsJack::TIP_PAD;
+        let first_ring_pad = position + TrrsJack::FIRST_RING_PAD;
+        let second_ring_pad = position + TrrsJack::SECOND_RING_PAD;
+
+        // The power track crosses the left column on the bottom between the first two pins
+        let power_pin = controller_pin("VCC");
+        let power_waypoint = Point::new(
+            ORIGIN_POSITION.x() + 14.0.into(),
+            (controller_pin("0").y() + controller_pin("GND").y()) / 2,
+        );
+
+        self.pcb.add_track(
+            &Path::angled_end(sleeve_pad, controller_pin("GND")),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            ground_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(tip_pad, power_waypoint)
+                .join(&Path::angled_start(power_waypoint, power_pin)),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &power_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(first_ring_pad, controller_pin("2")),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &transmit_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(second_ring_pad, controller_pin("3")),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &receive_net,
+        );
+
+        let nets = [
+            ground_net.clone(),
+            power_net.clone(),
+            transmit_net.clone(),
+            receive_net.clone(),
+        ];
+        self.pcb
+            .add_footprint(TrrsJack::new("J2".to_owned(), position, nets).into());
+
+        vec![("VCC", power_net), ("2", transmit_net), ("3", receive_net)]
+    }
+
+    /// Adds the power switch, the battery pads and their tracks to the PCB.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_power_switch(&mut self, ground_net: &Net) -> Vec<(&'static str, Net)> {
+        let position = side_port_position();
+        let battery_net = self.pcb.create_net("BAT".to_owned());
+        let raw_net = self.pcb.create_net("RAW".to_owned());
+
+        let common_pad = position + SlideSwitch::COMMON_PAD;
+        let output_pad = position + SlideSwitch::OUTPUT_PAD;
+        let ground_pin = controller_pin("GND");
+
+        // The negative battery pad is placed level with the ground pin of the controller
+        let battery_position = Position::new(
+            common_pad.x(),
+            ground_pin.y() - BatteryPads::NEGATIVE_PAD.y(),
+            None,
+        );
+        let positive_pad = battery_position + BatteryPads::POSITIVE_PAD;
+        let negative_pad = battery_position + BatteryPads::NEGATIVE_PAD;
+
+        // The RAW track crosses the left column between the first two pins
+        let raw_waypoint = Point::new(
+            output_pad.x(),
+            (controller_pin("1").y() + controller_pin("0").y()) / 2,
+        );
+
+        self.pcb.add_track(
+            &Path::new([common_pad, positive_pad]),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &battery_net,
+        );
+        self.pcb.add_track(
+            &Path::new([negative_pad, ground_pin]),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            ground_net,
+        );
+        self.pcb.add_track(
+            &Path::new([output_pad]).join(&Path::angled_start(raw_waypoint, controller_pin("RAW"))),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &raw_net,
+        );
+
+        let slide_switch = SlideSwitch::new(
+            "SW1".to_owned(),
+            position,
+            battery_net.clone(),
+            raw_net.clone(),
+        );
+        let battery_pads = BatteryPads::new(
+            "BT1".to_owned(),
+            battery_position,
+            battery_net,
+            ground_net.clone(),
+        );
+        self.pcb.add_footprint(slide_switch.into());
+        self.pcb.add_footprint(battery_pads.into());
+
+        vec![("RAW", raw_net)]
+    }
+
+    /// Adds the circuit for detecting the USB connection and its tracks to the PCB.
+    ///
+    /// The zener diode clamps the USB voltage applied to the detection pin through the resistor.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_pro_micro_usb_detection(&mut self, ground_net: &Net) -> Vec<(&'static str, Net)> {
+        let raw_net = self.pcb.create_net("RAW".to_owned());
+        let detection_net = self.pcb.create_net("USB_DETECT".to_owned());
+
+        let raw_pin = controller_pin("RAW");
+        let detection_pin = controller_pin("20");
+
+        // Both parts are placed between the pins of the controller
+        let resistor_position = ORIGIN_POSITION + position!(29.0, 13.96, None);
+        let diode_position = ORIGIN_POSITION + position!(28.5, 16.8, Some(180.deg()));
+
+        let first_resistor_pad = resistor_position + Resistor::FIRST_PAD;
+        let second_resistor_pad = resistor_position + Resistor::SECOND_PAD;
+        let cathode_pad = diode_position + ZenerDiode::CATHODE_PAD;
+        let anode_pad = diode_position + ZenerDiode::ANODE_PAD;
+
+        // The RAW track is routed around the outside of the right column
+        let raw_channel_x = raw_pin.x() + 1.5.into();
+        let raw_track = Path::new([
+            raw_pin,
+            Point::new(raw_channel_x, raw_pin.y()),
+            Point::new(raw_channel_x, second_resistor_pad.y()),
+            second_resistor_pad,
+        ]);
+
+        self.pcb.add_track(
+            &raw_track.chamfer(CHAMFER_DEPTH),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &raw_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_end(first_resistor_pad, detection_pin),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &detection_net,
+        );
+        self.pcb.add_track(
+            &Path::new([cathode_pad, Point::new(cathode_pad.x(), detection_pin.y())]),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &detection_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(anode_pad, controller_pin("GND")),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            ground_net,
+        );
+
+        let resistor = Resistor::new(
+            "R1".to_owned(),
+            resistor_position,
+            [detection_net.clone(), raw_net.clone()],
+        );
+        let zener_diode = ZenerDiode::new(
+            "D1".to_owned(),
+            diode_position,
+            detection_net.clone(),
+            ground_net.clone(),
+        );
+        self.pcb.add_footprint(resistor.into());
+        self.pcb.add_footprint(zener_diode.into());
+
+        vec![("RAW", raw_net), ("20", detection_net)]
+    }
+
+    /// Adds the tracks connecting the other ground pins of the controller to the first one.
+    fn add_ground_tracks(&mut self, ground_net: &Net) {
+        let first_pin = controller_pin("GND");
+
+        for pin in ProMicro::pin_positions("GND").skip(1) {
+            self.pcb.add_track(
+                &Path::angled_start(controller_position() + pin, first_pin),
+                TRACK_WIDTH,
+                TOP_LAYER,
+                ground_net,
+            );
+        }
+    }
+}
+
+/// Returns the position of the controller, aligning its USB port with the cutout in the case.
+fn controller_position() -> Position {
+    ORIGIN_POSITION + position!(USB_PORT_OFFSET, 16.5, None)
+}
+
+/// Returns the position of the controller pin with the given name.
+fn controller_pin(name: &str) -> Point {
+    controller_position() + ProMicro::pin_position(name)
+}
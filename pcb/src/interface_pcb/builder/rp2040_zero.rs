@@ -0,0 +1,240 @@
+use matrix::RP2040_ZERO_PINS;
+
+use crate::{
+    footprints::{FfcConnector, Resistor, Rp2040Zero, TrrsJack, ZenerDiode},
+    interface_pcb::{
+        builder::{ffc_connector_position, side_port_position, Builder, FFC_CONNECTOR_PINS},
+        ORIGIN_POSITION, TRACK_WIDTH,
+    },
+    kicad_pcb::Net,
+    matrix_pcb::{AddPath, BOTTOM_LAYER, TOP_LAYER},
+    path::Path,
+    position,
+    primitives::{Point, Position},
+    unit::{IntoAngle, Length},
+};
+
+/// The width of the interface PCB.
+const WIDTH: Length = Length::new(30.0);
+/// The height of the interface PCB.
+const HEIGHT: Length = Length::new(36.0);
+/// The offset of the USB port from the outer corner, matching the cutout in the case.
+const USB_PORT_OFFSET: Length = Length::new(20.6);
+/// The offset of the FFC connector from the outer corner, keeping its mounting pads clear of the
+/// edge.
+const FFC_CONNECTOR_OFFSET: Length = Length::new(20.0);
+/// The distance below the bottom row of pins at which the FFC tracks fan out to the pads.
+const FAN_OUT_OFFSET: Length = Length::new(1.6);
+
+impl Builder {
+    /// Adds the RP2040-Zero, its connections and the outline to the PCB.
+    pub(super) fn add_rp2040_zero(&mut self, ground_net: &Net) {
+        let mut controller_nets = vec![("GND", ground_net.clone())];
+
+        controller_nets.extend(self.add_rp2040_zero_ffc_connector());
+        controller_nets.extend(self.add_rp2040_zero_jack(ground_net));
+        controller_nets.extend(self.add_rp2040_zero_usb_detection(ground_net));
+
+        self.pcb.add_footprint(
+            Rp2040Zero::new("U1".to_owned(), controller_position(), controller_nets).into(),
+        );
+        self.add_outline(WIDTH, HEIGHT);
+    }
+
+    /// Adds the FFC connector and the tracks to the controller to the PCB.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_rp2040_zero_ffc_connector(&mut self) -> Vec<(&'static str, Net)> {
+        let position = ffc_connector_position(FFC_CONNECTOR_OFFSET, HEIGHT);
+        let pins = FFC_CONNECTOR_PINS.map(|(_, pin)| rp2040_zero_pin(pin));
+        let nets = FFC_CONNECTOR_PINS.map(|(name, _)| self.pcb.create_net(name.to_owned()));
+        let bottom_row_y = controller_position().y() + Rp2040Zero::bottom_row_y();
+
+        for (index, (pin, net)) in pins.iter().zip(&nets).enumerate() {
+            let pad = position + FfcConnector::pad_position(index);
+            let pin = controller_pin(pin);
+
+            // Pins above the bottom row are connected through the gap between the bottom row pins
+            // connected to the neighboring pads, the first and last pad are always connected to
+            // bottom row pins
+            let track = if pin.y() == bottom_row_y {
+                Path::new([pin])
+            } else {
+                let gap_x =
+                    (controller_pin(pins[index - 1]).x() + controller_pin(pins[index + 1]).x()) / 2;
+
+                Path::angled_end(pin, Point::new(gap_x, bottom_row_y))
+            };
+            let fan_out_point = Point::new(
+                track.last().expect("track should not be empty").x(),
+                bottom_row_y + FAN_OUT_OFFSET,
+            );
+
+            self.pcb.add_track(
+                &track.join(&Path::angled_end(fan_out_point, pad)),
+                TRACK_WIDTH,
+                TOP_LAYER,
+                net,
+            );
+        }
+
+        let ffc_connector = FfcConnector::new("J1".to_owned(), position, nets.clone());
+        self.pcb.add_footprint(ffc_connector.into());
+
+        pins.into_iter().zip(nets).collect()
+    }
+
+    /// Adds the TRRS jack for connecting the halves and its tracks to the PCB.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_rp2040_zero_jack(&mut self, ground_net: &Net) -> Vec<(&'static str, Net)> {
+        let position = side_port_position();
+        let power_net = self.pcb.create_net("VCC".to_owned());
+        let transmit_net = self.pcb.create_net("SERIAL_TX".to_owned());
+        let receive_net = self.pcb.create_net("SERIAL_RX".to_owned());
+
+        let transmit_pin = rp2040_zero_pin("2");
+        let receive_pin = rp2040_zero_pin("3");
+
+        self.pcb.add_track(
+            &Path::angled_end(position + TrrsJack::SLEEVE_PAD, controller_pin("GND")),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            ground_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_end(position + TrrsJack::TIP_PAD, controller_pin("3V3")),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &power_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(
+                position + TrrsJack::FIRST_RING_PAD,
+                controller_pin(transmit_pin),
+            ),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &transmit_net,
+        );
+        self.pcb.add_track(
+            &Path::angled_start(
+                position + TrrsJack::SECOND_RING_PAD,
+                controller_pin(receive_pin),
+            ),
+            TRACK_WIDTH,
+            TOP_LAYER,
+            &receive_net,
+        );
+
+        let nets = [
+            ground_net.clone(),
+            power_net.clone(),
+            transmit_net.clone(),
+            receive_net.clone(),
+        ];
+        self.pcb
+            .add_footprint(TrrsJack::new("J2".to_owned(), position, nets).into());
+
+        vec![
+            ("3V3", power_net),
+            (transmit_pin, transmit_net),
+            (receive_pin, receive_net),
+        ]
+    }
+
+    /// Adds the circuit for detecting the USB connection and its tracks to the PCB.
+    ///
+    /// The zener diode clamps the USB voltage applied to the detection pin through the resistor.
+    ///
+    /// Returns the connected controller pins and their nets.
+    fn add_rp2040_zero_usb_detection(&mut self, ground_net: &Net) -> Vec<(&'static str, Net)> {
+        let raw_net = self.pcb.create_net("RAW".to_owned());
+        let detection_net = self.pcb.create_net("USB_DETECT".to_owned());
+
+        let raw_pin = controller_pin("5V");
+        let ground_pin = controller_pin("GND");
+        let detection_pin_name = rp2040_zero_pin("20");
+        let detection_pin = controller_pin(detection_pin_name);
+
+        // Both parts are placed between the top pins of the controller, level with the pins
+        let resistor_position = Position::new(
+            ORIGIN_POSITION.x() + 17.5.into(),
+            raw_pin.y(),
+            Some(180.deg()),
+        );
+        let diode_position = Position::new(
+            ORIGIN_POSITION.x() + 20.0.into(),
+            ground_pin.y(),
+            Some(180.deg()),
+        );
+
+        let first_resistor_pad = resistor_position + Resistor::FIRST_PAD;
+        let second_resistor_pad = resistor_position + Resistor::SECOND_PAD;
+        let cathode_pad = diode_position + ZenerDiode::CATHODE_PAD;
+        let anode_pad = diode_position + ZenerDiode::ANODE_PAD;
+
+        self.pcb.add_track(
+            &Path::new([raw_pin, second_resistor_pad]),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &raw_net,
+        );
+        self.pcb.add_track(
+            &Path::new([first_resistor_pad, detection_pin]),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &detection_net,
+        );
+        self.pcb.add_track(
+            &Path::new([cathode_pad, Point::new(cathode_pad.x(), detection_pin.y())]),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            &detection_net,
+        );
+        self.pcb.add_track(
+            &Path::new([anode_pad, ground_pin]),
+            TRACK_WIDTH,
+            BOTTOM_LAYER,
+            ground_net,
+        );
+
+        let resistor = Resistor::new(
+            "R1".to_owned(),
+            resistor_position,
+            [detection_net.clone(), raw_net.clone()],
+        );
+        let zener_diode = ZenerDiode::new(
+            "D1".to_owned(),
+            diode_position,
+            detection_net.clone(),
+            ground_net.clone(),
+        );
+        self.pcb.add_footprint(resistor.into());
+        self.pcb.add_footprint(zener_diode.into());
+
+        vec![("5V", raw_net), (detection_pin_name, detection_net)]
+    }
+}
+
+/// Returns the position of the controller, aligning its USB port with the cutout in the case.
+fn controller_position() -> Position {
+    ORIGIN_POSITION + position!(USB_PORT_OFFSET, 11.75, None)
+}
+
+/// Returns the position of the controller pin with the given name.
+fn controller_pin(name: &str) -> Point {
+    controller_position() + Rp2040Zero::pin_position(name)
+}
+
+/// Returns the name of the RP2040-Zero pin replacing the Pro Micro pin with the given name.
+///
+/// # Panics
+///
+/// Panics if the Pro Micro pin is not replaced by any pin.
+fn rp2040_zero_pin(pro_micro_pin: &str) -> &'static str {
+    RP2040_ZERO_PINS
+        .iter()
+        .find_map(|&(pin, name)| (pin.to_string() == pro_micro_pin).then_some(name))
+        .expect("Pro Micro pin should be replaced by an RP2040-Zero pin")
+}
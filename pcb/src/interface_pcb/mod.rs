@@ -0,0 +1,77 @@
+mod builder;
+
+use config::Config;
+use model::Diagnostic;
+
+use crate::{
+    kicad_pcb::KicadPcb,
+    primitives::{Position, UuidMode},
+    unit::Length,
+};
+
+use builder::Builder;
+
+/// The position of the outer top corner of the interface PCB.
+const ORIGIN_POSITION: Position = Position::new(Length::new(100.0), Length::new(100.0), None);
+/// The thickness of the interface PCB.
+const THICKNESS: Length = Length::new(1.6);
+/// The offset of the TRRS jack or power switch from the outer corner.
+const SIDE_PORT_OFFSET: Length = Length::new(5.4);
+/// The depth of the chamfers at the corners of tracks.
+const CHAMFER_DEPTH: Length = Length::new(0.5);
+/// The track width.
+const TRACK_WIDTH: Length = Length::new(0.15);
+/// The track clearance.
+const TRACK_CLEARANCE: Length = Length::new(0.15);
+/// The width of the outline.
+const OUTLINE_WIDTH: Length = Length::new(0.05);
+/// The clearance between copper and the outline.
+const EDGE_CLEARANCE: Length = Length::new(0.25);
+
+/// A PCB connecting the controller to the matrix PCB and the other half.
+pub struct InterfacePcb(KicadPcb);
+
+impl InterfacePcb {
+    /// Creates a new interface PCB from the given configuration.
+    ///
+    /// The UUIDs are derived from the elements, so the same configuration results in the same
+    /// board file.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        Self::from_config_with_uuid_mode(config, UuidMode::default())
+    }
+
+    /// Creates a new interface PCB from the given configuration, creating UUIDs using the given
+    /// mode.
+    #[must_use]
+    pub fn from_config_with_uuid_mode(config: &Config, uuid_mode: UuidMode) -> Self {
+        Self(Builder::from_config(config, uuid_mode).build())
+    }
+
+    /// Serializes the interface PCB to the KiCAD board file format.
+    #[must_use]
+    pub fn to_kicad_board(&self) -> String {
+        self.0.to_board_file()
+    }
+
+    /// Checks the clearances of the interface PCB and returns the violations as diagnostics.
+    #[must_use]
+    pub fn check_design_rules(&self) -> Vec<Diagnostic> {
+        self.0
+            .check_design_rules(TRACK_CLEARANCE, EDGE_CLEARANCE)
+            .into_iter()
+            .map(|violation| violation.into_diagnostic("interface PCB"))
+            .collect()
+    }
+
+    /// Checks the connectivity of the tracks and pads of the interface PCB and returns the
+    /// opens, shorts and unrouted pads as diagnostics.
+    #[must_use]
+    pub fn check_connectivity(&self) -> Vec<Diagnostic> {
+        self.0
+            .check_connectivity()
+            .into_iter()
+            .map(|violation| violation.into_diagnostic("interface PCB"))
+            .collect()
+    }
+}
@@ -1,14 +1,31 @@
+mod parser;
 mod serializer;
 
+use std::collections::HashMap;
+
 use serde::Serialize;
 
 use crate::{
+    drc::{AddShapes, DesignRuleChecker, ObjectKind, Shape, Violation},
     footprints::Footprint,
-    primitives::{Point, Uuid},
+    gerber::{FabricationFile, Plot, Plotter},
+    primitives::{Point, Position, Size, Uuid, UuidGenerator, UuidMode},
     unit::Length,
 };
 
-use serializer::Serializer;
+pub use parser::{Error as ParseError, Expression, Parse};
+pub use serializer::Serializer;
+
+/// The name of the outline layer.
+const OUTLINE_LAYER: &str = "Edge.Cuts";
+/// The name of the front copper layer.
+const FRONT_COPPER_LAYER: &str = "F.Cu";
+/// The name of the back copper layer.
+const BACK_COPPER_LAYER: &str = "B.Cu";
+/// The name of the front silkscreen layer.
+const FRONT_SILKSCREEN_LAYER: &str = "F.SilkS";
+/// The name of the back silkscreen layer.
+const BACK_SILKSCREEN_LAYER: &str = "B.SilkS";
 
 /// A KiCAD PCB.
 #[derive(Serialize)]
@@ -23,20 +40,24 @@ pub struct KicadPcb {
     footprints: Vec<Footprint>,
     gr_lines: Vec<GrLine>,
     gr_arcs: Vec<GrArc>,
+    gr_texts: Vec<GrText>,
     segments: Vec<Segment>,
     arcs: Vec<Arc>,
+    zones: Vec<Zone>,
+    #[serde(skip)]
+    uuids: UuidGenerator,
 }
 
 impl Default for KicadPcb {
     fn default() -> Self {
-        Self::new(1.6.into())
+        Self::new(1.6.into(), UuidMode::default())
     }
 }
 
 impl KicadPcb {
-    /// Creates an empty KiCAD PCB with the given thickness.
+    /// Creates an empty KiCAD PCB with the given thickness, creating UUIDs using the given mode.
     #[must_use]
-    pub fn new(thickness: Length) -> Self {
+    pub fn new(thickness: Length, uuid_mode: UuidMode) -> Self {
         Self {
             #[allow(clippy::unreadable_literal)]
             version: 20240108,
@@ -52,8 +73,11 @@ impl KicadPcb {
             footprints: Vec::default(),
             gr_lines: Vec::default(),
             gr_arcs: Vec::default(),
+            gr_texts: Vec::default(),
             segments: Vec::default(),
             arcs: Vec::default(),
+            zones: Vec::default(),
+            uuids: UuidGenerator::new(uuid_mode),
         }
     }
 
@@ -72,6 +96,47 @@ impl KicadPcb {
         serializer.finish()
     }
 
+    /// Parses a PCB from the KiCAD board file format.
+    ///
+    /// Only the elements the PCBs are generated with are read, others like vias are ignored.
+    /// The layers and setup are replaced with the defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the board file is malformed or contains unsupported values.
+    pub fn from_board_file(board_file: &str) -> Result<Self, ParseError> {
+        let node = parser::parse(board_file)?;
+        let expression = node
+            .expression()
+            .filter(|expression| expression.name() == "kicad_pcb")
+            .ok_or_else(|| ParseError::MissingElement("kicad_pcb".to_owned()))?;
+
+        Self::parse(expression)
+    }
+
+    /// Plots the PCB to Gerber and Excellon drill files.
+    #[must_use]
+    pub fn to_fabrication_files(&self) -> Vec<FabricationFile> {
+        let mut plotter = Plotter::default();
+
+        self.plot(&mut plotter);
+
+        plotter.finish()
+    }
+
+    /// Checks the clearances between the objects of the PCB and returns the violations.
+    #[must_use]
+    pub fn check_design_rules(&self, clearance: Length, edge_clearance: Length) -> Vec<Violation> {
+        self.design_rule_checker(clearance, edge_clearance).finish()
+    }
+
+    /// Checks the connectivity of the copper of the PCB and returns the violations.
+    #[must_use]
+    pub fn check_connectivity(&self) -> Vec<Violation> {
+        self.design_rule_checker(Length::default(), Length::default())
+            .check_connectivity()
+    }
+
     /// Creates a new net with the given name, adds it to the PCB and returns it.
     pub fn create_net(&mut self, name: String) -> Net {
         let id = self
@@ -96,13 +161,17 @@ impl KicadPcb {
         layer: &'static str,
         net: &Net,
     ) {
+        let uuid = self
+            .uuids
+            .generate("segment", &(start, end, layer, net.id()));
+
         self.segments.push(Segment {
             start,
             end,
             width,
             layer,
             net: net.id(),
-            uuid: Uuid::new(),
+            uuid,
         });
     }
 
@@ -116,6 +185,10 @@ impl KicadPcb {
         layer: &'static str,
         net: &Net,
     ) {
+        let uuid = self
+            .uuids
+            .generate("arc", &(start, mid, end, layer, net.id()));
+
         self.arcs.push(Arc {
             start,
             mid,
@@ -123,7 +196,7 @@ impl KicadPcb {
             width,
             layer,
             net: net.id(),
-            uuid: Uuid::new(),
+            uuid,
         });
     }
 
@@ -135,6 +208,8 @@ impl KicadPcb {
         width: Length,
         layer: &'static str,
     ) {
+        let uuid = self.uuids.generate("gr_line", &(start, end, layer));
+
         self.gr_lines.push(GrLine {
             start,
             end,
@@ -143,7 +218,7 @@ impl KicadPcb {
                 stroke_type: StrokeType::Solid,
             },
             layer,
-            uuid: Uuid::new(),
+            uuid,
         });
     }
 
@@ -156,6 +231,8 @@ impl KicadPcb {
         width: Length,
         layer: &'static str,
     ) {
+        let uuid = self.uuids.generate("gr_arc", &(start, mid, end, layer));
+
         self.gr_arcs.push(GrArc {
             start,
             mid,
@@ -165,14 +242,386 @@ impl KicadPcb {
                 stroke_type: StrokeType::Solid,
             },
             layer,
-            uuid: Uuid::new(),
+            uuid,
         });
     }
 
+    /// Adds a graphical text with the given height centered at the given position to the PCB.
+    ///
+    /// Texts on the bottom layers are mirrored.
+    pub fn add_graphical_text(
+        &mut self,
+        text: String,
+        position: Position,
+        height: Length,
+        layer: &'static str,
+    ) {
+        let justify = layer.starts_with("B.").then_some(Justify::Mirror);
+        let uuid = self.uuids.generate("gr_text", &(&text, position, layer));
+
+        self.gr_texts.push(GrText(
+            text,
+            GrTextSettings {
+                at: position,
+                layer,
+                uuid,
+                effects: TextEffects {
+                    font: Font {
+                        size: Size::new(height, height),
+                        thickness: height / 5,
+                    },
+                    justify,
+                },
+            },
+        ));
+    }
+
+    /// Adds a graphical text with the given height centered at the given position to the front
+    /// and back silkscreen of the PCB.
+    pub fn add_silkscreen_text(&mut self, text: &str, position: Position, height: Length) {
+        for layer in [FRONT_SILKSCREEN_LAYER, BACK_SILKSCREEN_LAYER] {
+            self.add_graphical_text(text.to_owned(), position, height, layer);
+        }
+    }
+
+    /// Adds a zone without net filling the rectangle between the given corners on the front and
+    /// back copper layer, keeping the given clearance to the edges of the rectangle.
+    ///
+    /// The zone is only written to the board file and neither plotted nor checked.
+    pub fn add_copper_fill(&mut self, minimum: Point, maximum: Point, clearance: Length) {
+        let rectangle = |minimum: Point, maximum: Point| Points {
+            xys: vec![
+                minimum,
+                Point::new(maximum.x(), minimum.y()),
+                maximum,
+                Point::new(minimum.x(), maximum.y()),
+            ],
+        };
+        let filled_rectangle = || {
+            rectangle(
+                Point::new(minimum.x() + clearance, minimum.y() + clearance),
+                Point::new(maximum.x() - clearance, maximum.y() - clearance),
+            )
+        };
+
+        self.add_zone(
+            0,
+            rectangle(minimum, maximum),
+            [FRONT_COPPER_LAYER, BACK_COPPER_LAYER]
+                .into_iter()
+                .map(|layer| FilledPolygon {
+                    layer,
+                    pts: filled_rectangle(),
+                })
+                .collect(),
+        );
+    }
+
     /// Adds the given footprint to the PCB.
-    pub fn add_footprint(&mut self, footprint: Footprint) {
+    ///
+    /// The UUIDs of the footprint are replaced with ones created by the PCB.
+    pub fn add_footprint(&mut self, mut footprint: Footprint) {
+        footprint.assign_uuids(&mut self.uuids);
+
         self.footprints.push(footprint);
     }
+
+    /// Adds a copy of the given PCB moved by the given offset.
+    ///
+    /// The nets of the copy are merged with existing nets of the same name.
+    pub fn add_pcb(&mut self, other: &KicadPcb, offset: Point) {
+        let nets: HashMap<_, _> = other
+            .nets
+            .iter()
+            .map(|Net(id, name)| (*id, self.net_by_name(name)))
+            .collect();
+        let translation = Position::new(offset.x(), offset.y(), None);
+
+        for footprint in &other.footprints {
+            self.add_footprint(footprint.moved(offset, |net| nets[&net.id()].clone()));
+        }
+        for line in &other.gr_lines {
+            self.add_graphical_line(
+                translation + line.start,
+                translation + line.end,
+                line.stroke.width,
+                line.layer,
+            );
+        }
+        for arc in &other.gr_arcs {
+            self.add_graphical_arc(
+                translation + arc.start,
+                translation + arc.mid,
+                translation + arc.end,
+                arc.stroke.width,
+                arc.layer,
+            );
+        }
+        for GrText(text, settings) in &other.gr_texts {
+            self.add_graphical_text(
+                text.clone(),
+                translation + settings.at,
+                settings.effects.font.size.height(),
+                settings.layer,
+            );
+        }
+        for segment in &other.segments {
+            self.add_segment(
+                translation + segment.start,
+                translation + segment.end,
+                segment.width,
+                segment.layer,
+                &nets[&segment.net],
+            );
+        }
+        for arc in &other.arcs {
+            self.add_arc(
+                translation + arc.start,
+                translation + arc.mid,
+                translation + arc.end,
+                arc.width,
+                arc.layer,
+                &nets[&arc.net],
+            );
+        }
+        for zone in &other.zones {
+            let moved = |points: &Points| Points {
+                xys: points
+                    .xys
+                    .iter()
+                    .map(|&point| translation + point)
+                    .collect(),
+            };
+
+            self.add_zone(
+                nets[&zone.net].id(),
+                moved(&zone.polygon.pts),
+                zone.filled_polygons
+                    .iter()
+                    .map(|filled_polygon| FilledPolygon {
+                        layer: filled_polygon.layer,
+                        pts: moved(&filled_polygon.pts),
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    /// Returns the thickness of the PCB.
+    #[must_use]
+    pub fn thickness(&self) -> Length {
+        self.general.thickness
+    }
+
+    /// Returns the mode the UUIDs of the PCB are created with.
+    #[must_use]
+    pub fn uuid_mode(&self) -> UuidMode {
+        self.uuids.mode()
+    }
+
+    /// Returns the footprints of the PCB.
+    #[must_use]
+    pub fn footprints(&self) -> &[Footprint] {
+        &self.footprints
+    }
+
+    /// Returns the minimum and maximum corner of the bounding box of the outline.
+    ///
+    /// Arcs are approximated by their start, mid and end points.
+    #[must_use]
+    pub fn outline_bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self
+            .gr_lines
+            .iter()
+            .filter(|line| line.layer == OUTLINE_LAYER)
+            .flat_map(|line| [line.start, line.end])
+            .chain(
+                self.gr_arcs
+                    .iter()
+                    .filter(|arc| arc.layer == OUTLINE_LAYER)
+                    .flat_map(|arc| [arc.start, arc.mid, arc.end]),
+            );
+
+        let first = points.next()?;
+
+        Some(points.fold((first, first), |(minimum, maximum), point| {
+            (
+                Point::new(minimum.x().min(point.x()), minimum.y().min(point.y())),
+                Point::new(maximum.x().max(point.x()), maximum.y().max(point.y())),
+            )
+        }))
+    }
+
+    /// Removes the part between the given points from the outline line containing both of them.
+    ///
+    /// Returns `false` if there is no such line.
+    pub fn cut_outline_line(&mut self, first: Point, second: Point) -> bool {
+        let Some(index) = self.gr_lines.iter().position(|line| {
+            line.layer == OUTLINE_LAYER && line.contains(first) && line.contains(second)
+        }) else {
+            return false;
+        };
+
+        let line = self.gr_lines.remove(index);
+        let (near, far) = if (first - line.start).length() < (second - line.start).length() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        for (start, end) in [(line.start, near), (far, line.end)] {
+            if start != end {
+                self.add_graphical_line(start, end, line.stroke.width, line.layer);
+            }
+        }
+
+        true
+    }
+
+    /// Adds a zone with the given net, outline and filled polygons on the front and back copper
+    /// layer.
+    fn add_zone(&mut self, net: u32, outline: Points, filled_polygons: Vec<FilledPolygon>) {
+        let net_name = self.nets[net as usize].name().to_owned();
+        let uuid = self.uuids.generate("zone", &(net, &outline.xys));
+
+        self.zones.push(Zone {
+            net,
+            net_name,
+            layers: (FRONT_COPPER_LAYER, BACK_COPPER_LAYER),
+            uuid,
+            hatch: Hatch(HatchStyle::Edge, Length::new(0.5)),
+            min_thickness: Length::new(0.25),
+            fill: true,
+            polygon: Polygon { pts: outline },
+            filled_polygons,
+        });
+    }
+
+    /// Creates a design rule checker containing the shapes of the PCB.
+    fn design_rule_checker(&self, clearance: Length, edge_clearance: Length) -> DesignRuleChecker {
+        let net_names = self
+            .nets
+            .iter()
+            .map(|Net(id, name)| (*id, name.clone()))
+            .collect();
+        let mut checker = DesignRuleChecker::new(clearance, edge_clearance, net_names);
+
+        self.add_shapes(&mut checker);
+
+        checker
+    }
+
+    /// Returns the net with the given name, creating it if it does not exist yet.
+    fn net_by_name(&mut self, name: &str) -> Net {
+        self.nets
+            .iter()
+            .find(|Net(_, net_name)| net_name == name)
+            .cloned()
+            .unwrap_or_else(|| self.create_net(name.to_owned()))
+    }
+}
+
+impl Parse for KicadPcb {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let general = expression.child("general")?;
+        let mut pcb = Self::new(general.child("thickness")?.length(0)?, UuidMode::default());
+
+        pcb.version = expression.child("version")?.parse_value(0)?;
+        expression
+            .child("generator")?
+            .value(0)?
+            .clone_into(&mut pcb.generator);
+        pcb.general.legacy_teardrops = general
+            .optional_child("legacy_teardrops")
+            .map(|child| child.bool(0))
+            .transpose()?
+            .unwrap_or_default();
+        expression
+            .child("paper")?
+            .value(0)?
+            .clone_into(&mut pcb.paper);
+        pcb.nets = expression.parse_children("net")?;
+        pcb.footprints = expression.parse_children("footprint")?;
+        pcb.gr_lines = expression.parse_children("gr_line")?;
+        pcb.gr_arcs = expression.parse_children("gr_arc")?;
+        pcb.gr_texts = expression.parse_children("gr_text")?;
+        pcb.segments = expression.parse_children("segment")?;
+        pcb.arcs = expression.parse_children("arc")?;
+        pcb.zones = expression.parse_children("zone")?;
+
+        Ok(pcb)
+    }
+}
+
+impl Plot for KicadPcb {
+    fn plot(&self, plotter: &mut Plotter) {
+        for footprint in &self.footprints {
+            footprint.plot(plotter);
+        }
+
+        for line in &self.gr_lines {
+            plotter.line(line.layer, line.start, line.end, line.stroke.width);
+        }
+
+        for arc in &self.gr_arcs {
+            plotter.arc(arc.layer, arc.start, arc.mid, arc.end, arc.stroke.width);
+        }
+
+        for GrText(text, settings) in &self.gr_texts {
+            let font = &settings.effects.font;
+
+            plotter.text(
+                settings.layer,
+                text,
+                settings.at,
+                font.size,
+                font.thickness,
+                settings.effects.justify.is_some(),
+            );
+        }
+
+        for segment in &self.segments {
+            plotter.line(segment.layer, segment.start, segment.end, segment.width);
+        }
+
+        for arc in &self.arcs {
+            plotter.arc(arc.layer, arc.start, arc.mid, arc.end, arc.width);
+        }
+    }
+}
+
+impl AddShapes for KicadPcb {
+    fn add_shapes(&self, checker: &mut DesignRuleChecker) {
+        for footprint in &self.footprints {
+            footprint.add_shapes(checker);
+        }
+
+        for line in self
+            .gr_lines
+            .iter()
+            .filter(|line| line.layer == OUTLINE_LAYER)
+        {
+            checker.add_edge(Shape::segment(line.start, line.end, Length::default()));
+        }
+
+        for arc in self.gr_arcs.iter().filter(|arc| arc.layer == OUTLINE_LAYER) {
+            for shape in Shape::arc(arc.start, arc.mid, arc.end, Length::default()) {
+                checker.add_edge(shape);
+            }
+        }
+
+        for segment in &self.segments {
+            let shape = Shape::segment(segment.start, segment.end, segment.width);
+
+            checker.add_object(ObjectKind::Track, segment.layer, shape, Some(segment.net));
+        }
+
+        for arc in &self.arcs {
+            for shape in Shape::arc(arc.start, arc.mid, arc.end, arc.width) {
+                checker.add_object(ObjectKind::Track, arc.layer, shape, Some(arc.net));
+            }
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -194,6 +643,18 @@ enum LayerType {
     User,
 }
 
+/// Returns the static name of the layer with the given name.
+///
+/// Besides the layers of the PCB, this includes the wildcard layers used by pads.
+pub fn static_layer_name(name: &str) -> Option<&'static str> {
+    Layers::default()
+        .0
+        .into_iter()
+        .map(|Layer(_, layer_name, ..)| layer_name)
+        .chain(["*.Cu", "*.Mask"])
+        .find(|&layer_name| layer_name == name)
+}
+
 impl Default for Layers {
     fn default() -> Self {
         Self(vec![
@@ -322,11 +783,25 @@ impl Default for PlotParameters {
 #[derive(Serialize, Clone)]
 pub struct Net(u32, String);
 
+impl Parse for Net {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self(
+            expression.parse_value(0)?,
+            expression.value(1)?.to_owned(),
+        ))
+    }
+}
+
 impl Net {
     /// Returns the ID of the net.
     pub fn id(&self) -> u32 {
         self.0
     }
+
+    /// Returns the name of the net.
+    pub fn name(&self) -> &str {
+        &self.1
+    }
 }
 
 #[derive(Serialize)]
@@ -348,6 +823,48 @@ struct GrArc {
     uuid: Uuid,
 }
 
+impl Parse for GrLine {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            start: expression.point("start")?,
+            end: expression.point("end")?,
+            stroke: Stroke::parse(expression.child("stroke")?)?,
+            layer: expression.layer()?,
+            uuid: expression.uuid()?,
+        })
+    }
+}
+
+impl Parse for GrArc {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            start: expression.point("start")?,
+            mid: expression.point("mid")?,
+            end: expression.point("end")?,
+            stroke: Stroke::parse(expression.child("stroke")?)?,
+            layer: expression.layer()?,
+            uuid: expression.uuid()?,
+        })
+    }
+}
+
+impl GrLine {
+    /// The maximum distance of a point to the line for still being considered on it.
+    const TOLERANCE: f32 = 0.001;
+
+    /// Returns true if the given point lies on the line.
+    fn contains(&self, point: Point) -> bool {
+        let direction = self.end - self.start;
+        let offset = point - self.start;
+        let length = direction.length();
+
+        length > 0.0
+            && direction.perp_dot(offset).abs() / length <= Self::TOLERANCE
+            && (-Self::TOLERANCE..=length + Self::TOLERANCE)
+                .contains(&(direction.dot(offset) / length))
+    }
+}
+
 #[derive(Serialize)]
 struct Stroke {
     width: Length,
@@ -355,12 +872,86 @@ struct Stroke {
     stroke_type: StrokeType,
 }
 
+impl Parse for Stroke {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let stroke_type = expression.child("type")?;
+
+        Ok(Self {
+            width: expression.child("width")?.length(0)?,
+            stroke_type: match stroke_type.value(0)? {
+                "solid" => StrokeType::Solid,
+                value => return Err(stroke_type.invalid_value(value)),
+            },
+        })
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
 enum StrokeType {
     Solid,
 }
 
+#[derive(Serialize)]
+struct GrText(String, GrTextSettings);
+
+impl Parse for GrText {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let effects = expression.child("effects")?;
+        let font = effects.child("font")?;
+        let justify = match effects.optional_child("justify") {
+            Some(justify) => match justify.value(0)? {
+                "mirror" => Some(Justify::Mirror),
+                value => return Err(justify.invalid_value(value)),
+            },
+            None => None,
+        };
+
+        Ok(Self(
+            expression.value(0)?.to_owned(),
+            GrTextSettings {
+                at: expression.position()?,
+                layer: expression.layer()?,
+                uuid: expression.uuid()?,
+                effects: TextEffects {
+                    font: Font {
+                        size: font.size()?,
+                        thickness: font.child("thickness")?.length(0)?,
+                    },
+                    justify,
+                },
+            },
+        ))
+    }
+}
+
+#[derive(Serialize)]
+struct GrTextSettings {
+    at: Position,
+    layer: &'static str,
+    uuid: Uuid,
+    effects: TextEffects,
+}
+
+#[derive(Serialize)]
+struct TextEffects {
+    font: Font,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justify: Option<Justify>,
+}
+
+#[derive(Serialize)]
+struct Font {
+    size: Size,
+    thickness: Length,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Justify {
+    Mirror,
+}
+
 #[derive(Serialize)]
 struct Segment {
     start: Point,
@@ -371,6 +962,19 @@ struct Segment {
     uuid: Uuid,
 }
 
+impl Parse for Segment {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            start: expression.point("start")?,
+            end: expression.point("end")?,
+            width: expression.child("width")?.length(0)?,
+            layer: expression.layer()?,
+            net: expression.child("net")?.parse_value(0)?,
+            uuid: expression.uuid()?,
+        })
+    }
+}
+
 #[derive(Serialize)]
 struct Arc {
     start: Point,
@@ -381,3 +985,102 @@ struct Arc {
     net: u32,
     uuid: Uuid,
 }
+
+impl Parse for Arc {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            start: expression.point("start")?,
+            mid: expression.point("mid")?,
+            end: expression.point("end")?,
+            width: expression.child("width")?.length(0)?,
+            layer: expression.layer()?,
+            net: expression.child("net")?.parse_value(0)?,
+            uuid: expression.uuid()?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Zone {
+    net: u32,
+    net_name: String,
+    layers: (&'static str, &'static str),
+    uuid: Uuid,
+    hatch: Hatch,
+    min_thickness: Length,
+    fill: bool,
+    polygon: Polygon,
+    filled_polygons: Vec<FilledPolygon>,
+}
+
+impl Parse for Zone {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        let layers = expression.child("layers")?;
+        let hatch = expression.child("hatch")?;
+
+        Ok(Self {
+            net: expression.child("net")?.parse_value(0)?,
+            net_name: expression.child("net_name")?.value(0)?.to_owned(),
+            layers: (layers.layer_value(0)?, layers.layer_value(1)?),
+            uuid: expression.uuid()?,
+            hatch: Hatch(
+                match hatch.value(0)? {
+                    "edge" => HatchStyle::Edge,
+                    value => return Err(hatch.invalid_value(value)),
+                },
+                hatch.length(1)?,
+            ),
+            min_thickness: expression.child("min_thickness")?.length(0)?,
+            fill: expression.child("fill")?.bool(0)?,
+            polygon: Polygon {
+                pts: Points::parse(expression.child("polygon")?.child("pts")?)?,
+            },
+            filled_polygons: expression.parse_children("filled_polygon")?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Hatch(HatchStyle, Length);
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum HatchStyle {
+    Edge,
+}
+
+#[derive(Serialize)]
+struct Polygon {
+    pts: Points,
+}
+
+#[derive(Serialize)]
+struct FilledPolygon {
+    layer: &'static str,
+    pts: Points,
+}
+
+impl Parse for FilledPolygon {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            layer: expression.layer()?,
+            pts: Points::parse(expression.child("pts")?)?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Points {
+    xys: Vec<Point>,
+}
+
+impl Parse for Points {
+    fn parse(expression: Expression) -> Result<Self, ParseError> {
+        Ok(Self {
+            xys: expression
+                .children("xy")
+                .map(|xy| Ok(Point::new(xy.length(0)?, xy.length(1)?)))
+                .collect::<Result<_, ParseError>>()?,
+        })
+    }
+}
@@ -0,0 +1,389 @@
+use std::{iter::Peekable, result, str::CharIndices, str::FromStr};
+
+use crate::{
+    kicad_pcb::static_layer_name,
+    primitives::{Point, Position, Size, Uuid},
+    unit::{Angle, Length},
+};
+
+type Result<T> = result::Result<T, Error>;
+
+/// An error which can occur when parsing a KiCAD file.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The input ended unexpectedly.
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+
+    /// An unexpected character was found.
+    #[error("unexpected character `{0}` at byte {1}")]
+    UnexpectedCharacter(char, usize),
+
+    /// A required element is missing.
+    #[error("missing element `{0}`")]
+    MissingElement(String),
+
+    /// A required value of an element is missing.
+    #[error("missing value of element `{0}`")]
+    MissingValue(String),
+
+    /// A value of an element is invalid or not supported.
+    #[error("invalid value `{1}` of element `{0}`")]
+    InvalidValue(String, String),
+}
+
+/// A trait for parsing elements from S-expressions.
+pub trait Parse: Sized {
+    /// Parses `Self` from the given expression.
+    fn parse(expression: Expression) -> Result<Self>;
+}
+
+/// Parses the given input to a single node.
+pub fn parse(input: &str) -> Result<Node> {
+    let mut tokenizer = Tokenizer {
+        input,
+        characters: input.char_indices().peekable(),
+    };
+
+    let node = tokenizer.node()?;
+    tokenizer.skip_whitespace();
+
+    if let Some((index, character)) = tokenizer.characters.next() {
+        return Err(Error::UnexpectedCharacter(character, index));
+    }
+
+    Ok(node)
+}
+
+/// A node of an S-expression.
+pub enum Node {
+    /// A list of nodes enclosed in parentheses.
+    List(Vec<Node>),
+    /// An unquoted symbol like a keyword or a number.
+    Symbol(String),
+    /// A quoted string.
+    String(String),
+}
+
+impl Node {
+    /// Returns the node as expression if it is a list.
+    pub fn expression(&self) -> Option<Expression<'_>> {
+        match self {
+            Node::List(nodes) => Some(Expression(nodes)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the node if it is a symbol or string.
+    fn value(&self) -> Option<&str> {
+        match self {
+            Node::Symbol(value) | Node::String(value) => Some(value),
+            Node::List(_) => None,
+        }
+    }
+}
+
+/// A list in an S-expression starting with the name of the element.
+#[derive(Clone, Copy)]
+pub struct Expression<'a>(&'a [Node]);
+
+impl<'a> Expression<'a> {
+    /// Returns the name of the element.
+    pub fn name(self) -> &'a str {
+        match self.0.first() {
+            Some(Node::Symbol(name)) => name,
+            _ => "",
+        }
+    }
+
+    /// Returns the value with the given index following the name.
+    pub fn value(self, index: usize) -> Result<&'a str> {
+        self.0
+            .get(index + 1)
+            .and_then(Node::value)
+            .ok_or_else(|| Error::MissingValue(self.name().to_owned()))
+    }
+
+    /// Returns the values following the name.
+    pub fn values(self) -> impl Iterator<Item = &'a str> {
+        self.0.iter().skip(1).map_while(Node::value)
+    }
+
+    /// Parses the value with the given index following the name.
+    pub fn parse_value<T: FromStr>(self, index: usize) -> Result<T> {
+        let value = self.value(index)?;
+
+        value.parse().map_err(|_| self.invalid_value(value))
+    }
+
+    /// Returns the length with the given index following the name.
+    pub fn length(self, index: usize) -> Result<Length> {
+        let value = self.value(index)?;
+
+        Length::parse(value).ok_or_else(|| self.invalid_value(value))
+    }
+
+    /// Returns the boolean with the given index following the name.
+    pub fn bool(self, index: usize) -> Result<bool> {
+        match self.value(index)? {
+            "yes" => Ok(true),
+            "no" => Ok(false),
+            value => Err(self.invalid_value(value)),
+        }
+    }
+
+    /// Returns the child elements with the given name.
+    pub fn children(self, name: &'a str) -> impl Iterator<Item = Expression<'a>> {
+        self.0
+            .iter()
+            .filter_map(Node::expression)
+            .filter(move |child| child.name() == name)
+    }
+
+    /// Returns the first child element with the given name if there is one.
+    pub fn optional_child(self, name: &'a str) -> Option<Expression<'a>> {
+        self.children(name).next()
+    }
+
+    /// Returns the first child element with the given name.
+    pub fn child(self, name: &'a str) -> Result<Expression<'a>> {
+        self.optional_child(name)
+            .ok_or_else(|| Error::MissingElement(format!("{} {name}", self.name())))
+    }
+
+    /// Parses all child elements with the given name.
+    pub fn parse_children<T: Parse>(self, name: &'a str) -> Result<Vec<T>> {
+        self.children(name).map(T::parse).collect()
+    }
+
+    /// Returns the point given by the child element with the given name.
+    pub fn point(self, name: &'a str) -> Result<Point> {
+        let child = self.child(name)?;
+
+        Ok(Point::new(child.length(0)?, child.length(1)?))
+    }
+
+    /// Returns the position given by the `at` child element.
+    pub fn position(self) -> Result<Position> {
+        let child = self.child("at")?;
+        let angle = match child.0.get(3).and_then(Node::value) {
+            Some(value) => Some(Angle::parse(value).ok_or_else(|| child.invalid_value(value))?),
+            None => None,
+        };
+
+        Ok(Position::new(child.length(0)?, child.length(1)?, angle))
+    }
+
+    /// Returns the size given by the `size` child element.
+    pub fn size(self) -> Result<Size> {
+        let child = self.child("size")?;
+
+        Ok(Size::new(child.length(0)?, child.length(1)?))
+    }
+
+    /// Returns the layer given by the `layer` child element.
+    pub fn layer(self) -> Result<&'static str> {
+        self.child("layer")?.layer_value(0)
+    }
+
+    /// Returns the layer with the given index following the name.
+    pub fn layer_value(self, index: usize) -> Result<&'static str> {
+        let value = self.value(index)?;
+
+        static_layer_name(value).ok_or_else(|| self.invalid_value(value))
+    }
+
+    /// Returns the UUID given by the `uuid` child element.
+    pub fn uuid(self) -> Result<Uuid> {
+        let child = self.child("uuid")?;
+        let value = child.value(0)?;
+
+        Uuid::parse(value).ok_or_else(|| child.invalid_value(value))
+    }
+
+    /// Returns an error for the given invalid value of the element.
+    pub fn invalid_value(self, value: &str) -> Error {
+        Error::InvalidValue(self.name().to_owned(), value.to_owned())
+    }
+}
+
+/// A tokenizer splitting the input into nodes.
+struct Tokenizer<'a> {
+    input: &'a str,
+    characters: Peekable<CharIndices<'a>>,
+}
+
+impl Tokenizer<'_> {
+    /// Parses the next node.
+    fn node(&mut self) -> Result<Node> {
+        self.skip_whitespace();
+
+        match self.characters.next() {
+            Some((_, '(')) => {
+                let mut nodes = Vec::new();
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.characters.peek() {
+                        Some((_, ')')) => {
+                            self.characters.next();
+
+                            return Ok(Node::List(nodes));
+                        }
+                        Some(_) => nodes.push(self.node()?),
+                        None => return Err(Error::UnexpectedEnd),
+                    }
+                }
+            }
+            Some((_, '"')) => {
+                let mut string = String::new();
+
+                loop {
+                    match self.characters.next() {
+                        Some((_, '"')) => return Ok(Node::String(string)),
+                        Some((_, '\\')) => match self.characters.next() {
+                            Some((_, 'n')) => string.push('\n'),
+                            Some((_, character)) => string.push(character),
+                            None => return Err(Error::UnexpectedEnd),
+                        },
+                        Some((_, character)) => string.push(character),
+                        None => return Err(Error::UnexpectedEnd),
+                    }
+                }
+            }
+            Some((index, ')')) => Err(Error::UnexpectedCharacter(')', index)),
+            Some((start, _)) => {
+                let mut end = self.input.len();
+
+                while let Some(&(index, character)) = self.characters.peek() {
+                    if character.is_whitespace() || character == '(' || character == ')' {
+                        end = index;
+                        break;
+                    }
+                    self.characters.next();
+                }
+
+                Ok(Node::Symbol(self.input[start..end].to_owned()))
+            }
+            None => Err(Error::UnexpectedEnd),
+        }
+    }
+
+    /// Skips the whitespace before the next token.
+    fn skip_whitespace(&mut self) {
+        while self
+            .characters
+            .next_if(|(_, character)| character.is_whitespace())
+            .is_some()
+        {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{Config, Controller};
+
+    use crate::{kicad_pcb::KicadPcb, InterfacePcb, MatrixPcb};
+
+    use super::*;
+
+    /// Returns the board files generated from the default configuration.
+    fn generated_board_files() -> Vec<String> {
+        let mut config = Config::default();
+        let matrix_pcb = MatrixPcb::from_config(&config);
+        let mut board_files = vec![
+            matrix_pcb.to_kicad_board(),
+            matrix_pcb.to_panel().to_kicad_board(),
+        ];
+
+        // Hot-swap sockets add surface mount pads to the matrix PCB
+        config.keyboard.hot_swap = true;
+        board_files.push(MatrixPcb::from_config(&config).to_kicad_board());
+        config.keyboard.hot_swap = false;
+
+        for controller in [
+            Controller::Kb2040,
+            Controller::Rp2040Zero,
+            Controller::ProMicro,
+            Controller::NiceNano,
+        ] {
+            config.keyboard.controller = controller;
+            board_files.push(InterfacePcb::from_config(&config).to_kicad_board());
+        }
+
+        board_files
+    }
+
+    /// Returns a valid board file for modifying it to a malformed one.
+    fn board_file() -> String {
+        InterfacePcb::from_config(&Config::default()).to_kicad_board()
+    }
+
+    #[test]
+    fn round_trip() {
+        for board_file in generated_board_files() {
+            let pcb = KicadPcb::from_board_file(&board_file).expect("board file should be valid");
+
+            assert_eq!(pcb.to_board_file(), board_file);
+        }
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let board_file = board_file();
+
+        for input in [&board_file[..board_file.len() / 2], "(net 1 \"GND", ""] {
+            assert!(matches!(parse(input), Err(Error::UnexpectedEnd)));
+        }
+    }
+
+    #[test]
+    fn unexpected_character() {
+        let board_file = board_file();
+        let trailing_input = format!("{board_file})");
+
+        assert!(matches!(
+            parse(&trailing_input),
+            Err(Error::UnexpectedCharacter(')', index)) if index == trailing_input.len() - 1
+        ));
+        assert!(matches!(
+            parse(") (net 1 \"GND\")"),
+            Err(Error::UnexpectedCharacter(')', 0))
+        ));
+    }
+
+    #[test]
+    fn missing_element() {
+        let board_file = board_file().replacen("(kicad_pcb", "(kicad_sch", 1);
+
+        assert!(matches!(
+            KicadPcb::from_board_file(&board_file),
+            Err(Error::MissingElement(element)) if element == "kicad_pcb"
+        ));
+        assert!(matches!(
+            KicadPcb::from_board_file("(kicad_pcb (version 20240108))"),
+            Err(Error::MissingElement(element)) if element == "kicad_pcb general"
+        ));
+    }
+
+    #[test]
+    fn missing_value() {
+        let board_file = board_file().replacen("(thickness 1.6)", "(thickness)", 1);
+
+        assert!(matches!(
+            KicadPcb::from_board_file(&board_file),
+            Err(Error::MissingValue(element)) if element == "thickness"
+        ));
+    }
+
+    #[test]
+    fn invalid_value() {
+        let board_file = board_file().replacen("(thickness 1.6)", "(thickness thick)", 1);
+
+        assert!(matches!(
+            KicadPcb::from_board_file(&board_file),
+            Err(Error::InvalidValue(element, value)) if element == "thickness" && value == "thick"
+        ));
+    }
+}
@@ -0,0 +1,373 @@
+mod symbols;
+
+use serde::Serialize;
+
+use crate::{
+    kicad_pcb::{Net, Serializer},
+    primitives::{Point, Position, Size, Uuid, UuidGenerator, UuidMode},
+    size,
+    unit::{IntoAngle, Length},
+};
+
+pub use symbols::LibSymbol;
+
+use symbols::PinDirection;
+
+/// A KiCAD schematic.
+#[derive(Serialize)]
+pub struct KicadSchematic {
+    version: u32,
+    generator: String,
+    uuid: Uuid,
+    paper: String,
+    lib_symbols: LibSymbols,
+    global_labels: Vec<GlobalLabel>,
+    symbols: Vec<Symbol>,
+    sheet_instances: SheetInstances,
+    #[serde(skip)]
+    project: &'static str,
+    #[serde(skip)]
+    uuids: UuidGenerator,
+}
+
+impl KicadSchematic {
+    /// Creates an empty KiCAD schematic belonging to the project with the given name.
+    ///
+    /// The UUIDs of the schematic are created using the given mode.
+    #[must_use]
+    pub fn new(project: &'static str, uuid_mode: UuidMode) -> Self {
+        let mut uuids = UuidGenerator::new(uuid_mode);
+
+        Self {
+            #[allow(clippy::unreadable_literal)]
+            version: 20231120,
+            generator: "concavum_customizer".to_owned(),
+            uuid: uuids.generate("schematic", &project),
+            paper: "A4".to_owned(),
+            lib_symbols: LibSymbols {
+                symbols: Vec::new(),
+            },
+            global_labels: Vec::new(),
+            symbols: Vec::new(),
+            sheet_instances: SheetInstances {
+                path: SheetPath(
+                    "/".to_owned(),
+                    SheetPathSettings {
+                        page: "1".to_owned(),
+                    },
+                ),
+            },
+            project,
+            uuids,
+        }
+    }
+
+    /// Serializes the schematic to the KiCAD schematic file format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schematic could not be serialized.
+    #[must_use]
+    pub fn to_schematic_file(&self) -> String {
+        let mut serializer = Serializer::new("kicad_sch");
+
+        self.serialize(&mut serializer)
+            .expect("schematic should always be serializable");
+
+        serializer.finish()
+    }
+
+    /// Adds a symbol of the given library symbol at the given position to the schematic.
+    ///
+    /// The pins with the given numbers are connected to global labels named after the given nets.
+    /// Returns the path of the symbol, which links the corresponding footprint to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the library symbol has no pin with one of the given numbers.
+    pub fn add_symbol(
+        &mut self,
+        lib_symbol: LibSymbol,
+        reference: String,
+        position: Point,
+        pin_nets: &[(&str, &Net)],
+    ) -> String {
+        for &(number, net) in pin_nets {
+            let (pin_position, direction) = lib_symbol
+                .pin(number)
+                .expect("the library symbol should have a pin with the given number");
+
+            self.add_global_label(
+                net.name().to_owned(),
+                to_schematic(position, pin_position),
+                direction.opposite(),
+            );
+        }
+
+        let uuid = self.uuids.generate("symbol", &reference);
+        let path = format!("/{uuid}");
+        let at = Position::new(position.x(), position.y(), Some(0.deg()));
+
+        self.symbols.push(Symbol {
+            lib_id: lib_symbol.id().to_owned(),
+            at,
+            unit: 1,
+            exclude_from_sim: false,
+            in_bom: true,
+            on_board: true,
+            dnp: false,
+            uuid,
+            properties: vec![
+                Property::new(
+                    "Reference",
+                    reference.clone(),
+                    at + Position::new(0.into(), Length::new(-6.35), None),
+                ),
+                Property::hidden("Value", lib_symbol.value().to_owned(), at),
+                Property::hidden("Footprint", lib_symbol.footprint().to_owned(), at),
+                Property::hidden("Datasheet", String::new(), at),
+                Property::hidden("Description", String::new(), at),
+            ],
+            pins: lib_symbol
+                .pin_numbers()
+                .into_iter()
+                .map(|number| {
+                    let uuid = self.uuids.generate("pin", &(uuid, &number));
+
+                    SymbolPin(number, SymbolPinSettings { uuid })
+                })
+                .collect(),
+            instances: Instances {
+                project: Project(
+                    self.project.to_owned(),
+                    ProjectSettings {
+                        path: InstancePath(
+                            format!("/{}", self.uuid),
+                            InstancePathSettings { reference, unit: 1 },
+                        ),
+                    },
+                ),
+            },
+        });
+
+        if !self
+            .lib_symbols
+            .symbols
+            .iter()
+            .any(|symbol| symbol.id() == lib_symbol.id())
+        {
+            self.lib_symbols.symbols.push(lib_symbol);
+        }
+
+        path
+    }
+
+    /// Adds a global label at the given position pointing in the given direction.
+    fn add_global_label(&mut self, name: String, position: Point, direction: PinDirection) {
+        let justify = match direction {
+            PinDirection::Right | PinDirection::Up => Justify::Left,
+            PinDirection::Left | PinDirection::Down => Justify::Right,
+        };
+        let at = Position::new(position.x(), position.y(), Some(direction.angle()));
+        let uuid = self.uuids.generate("global_label", &(&name, at));
+
+        self.global_labels.push(GlobalLabel(
+            name,
+            GlobalLabelSettings {
+                shape: LabelShape::Passive,
+                at,
+                effects: Effects(
+                    EffectsSettings {
+                        font: Font::default(),
+                        justify: Some(justify),
+                    },
+                    None,
+                ),
+                uuid,
+            },
+        ));
+    }
+}
+
+/// Converts a point in the coordinates of a library symbol placed at the given position.
+///
+/// In contrast to the schematic, the Y axis of library symbols points upwards.
+fn to_schematic(position: Point, point: Point) -> Point {
+    Point::new(position.x() + point.x(), position.y() - point.y())
+}
+
+#[derive(Serialize)]
+struct LibSymbols {
+    symbols: Vec<LibSymbol>,
+}
+
+#[derive(Serialize)]
+struct GlobalLabel(String, GlobalLabelSettings);
+
+#[derive(Serialize)]
+struct GlobalLabelSettings {
+    shape: LabelShape,
+    at: Position,
+    effects: Effects,
+    uuid: Uuid,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LabelShape {
+    Passive,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Serialize)]
+struct Symbol {
+    lib_id: String,
+    at: Position,
+    unit: u32,
+    exclude_from_sim: bool,
+    in_bom: bool,
+    on_board: bool,
+    dnp: bool,
+    uuid: Uuid,
+    #[serde(rename = "property_")]
+    properties: Vec<Property>,
+    #[serde(rename = "pin_")]
+    pins: Vec<SymbolPin>,
+    instances: Instances,
+}
+
+#[derive(Serialize)]
+struct SymbolPin(String, SymbolPinSettings);
+
+#[derive(Serialize)]
+struct SymbolPinSettings {
+    uuid: Uuid,
+}
+
+#[derive(Serialize)]
+struct Instances {
+    project: Project,
+}
+
+#[derive(Serialize)]
+struct Project(String, ProjectSettings);
+
+#[derive(Serialize)]
+struct ProjectSettings {
+    path: InstancePath,
+}
+
+#[derive(Serialize)]
+struct InstancePath(String, InstancePathSettings);
+
+#[derive(Serialize)]
+struct InstancePathSettings {
+    reference: String,
+    unit: u32,
+}
+
+#[derive(Serialize)]
+struct SheetInstances {
+    path: SheetPath,
+}
+
+#[derive(Serialize)]
+struct SheetPath(String, SheetPathSettings);
+
+#[derive(Serialize)]
+struct SheetPathSettings {
+    page: String,
+}
+
+#[derive(Serialize)]
+struct Property(&'static str, String, PropertySettings);
+
+impl Property {
+    /// Creates a new visible property at the given position.
+    fn new(key: &'static str, value: String, position: Position) -> Self {
+        Self(
+            key,
+            value,
+            PropertySettings {
+                at: position,
+                effects: Effects::default(),
+            },
+        )
+    }
+
+    /// Creates a new hidden property at the given position.
+    fn hidden(key: &'static str, value: String, position: Position) -> Self {
+        Self(
+            key,
+            value,
+            PropertySettings {
+                at: position,
+                effects: Effects(EffectsSettings::default(), Some(Visibility::Hide)),
+            },
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct PropertySettings {
+    at: Position,
+    effects: Effects,
+}
+
+#[derive(Serialize, Default)]
+struct Effects(EffectsSettings, Option<Visibility>);
+
+#[derive(Serialize, Default)]
+struct EffectsSettings {
+    font: Font,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justify: Option<Justify>,
+}
+
+#[derive(Serialize)]
+struct Font {
+    size: Size,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self {
+            size: size!(1.27, 1.27),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Justify {
+    Left,
+    Right,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Visibility {
+    Hide,
+}
+
+#[derive(Serialize)]
+struct Stroke {
+    width: Length,
+    #[serde(rename = "type")]
+    stroke_type: StrokeType,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self {
+            width: Length::default(),
+            stroke_type: StrokeType::Default,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StrokeType {
+    Default,
+}
@@ -0,0 +1,354 @@
+use serde::Serialize;
+
+use crate::{
+    kicad_schematic::{Effects, Property, Stroke, Visibility},
+    point, position,
+    primitives::{Point, Position},
+    unit::{Angle, IntoAngle, Length},
+};
+
+/// The length of the pins.
+const PIN_LENGTH: Length = Length::new(2.54);
+/// The distance between adjacent pins of a connector.
+const PIN_SPACING: Length = Length::new(2.54);
+
+/// A symbol of the schematic library.
+#[derive(Serialize)]
+pub struct LibSymbol(String, LibSymbolSettings);
+
+impl LibSymbol {
+    /// Creates the symbol of a key switch with the given value and a diode in series.
+    ///
+    /// Pin 1 is the cathode of the diode, pin 2 the free switch pin and pin 3 the common node.
+    pub fn switch(value: &'static str) -> Self {
+        Self::new(
+            "SW_Diode",
+            "SW",
+            value,
+            "switch_dual_side",
+            vec![
+                Graphic::circle(point!(-2.032, 2.54), 0.508.into()),
+                Graphic::circle(point!(2.032, 2.54), 0.508.into()),
+                Graphic::polyline(vec![point!(-2.54, 3.556), point!(2.54, 3.556)]),
+                Graphic::polyline(vec![point!(0, 3.556), point!(0, 4.572)]),
+                Graphic::polyline(vec![point!(2.54, 2.54), point!(2.54, 0.635)]),
+                Graphic::polyline(vec![
+                    point!(1.27, 0.635),
+                    point!(3.81, 0.635),
+                    point!(2.54, -0.635),
+                    point!(1.27, 0.635),
+                ]),
+                Graphic::polyline(vec![point!(1.27, -0.635), point!(3.81, -0.635)]),
+                Graphic::polyline(vec![point!(2.54, -0.635), point!(2.54, -2.54)]),
+            ],
+            vec![
+                Pin::new("1", point!(2.54, -5.08), PinDirection::Up),
+                Pin::new("2", point!(-5.08, 2.54), PinDirection::Right),
+                Pin::new("3", point!(5.08, 2.54), PinDirection::Left),
+            ],
+        )
+    }
+
+    /// Creates the symbol of the 12 pin FFC connector.
+    pub fn ffc_connector() -> Self {
+        let pins = (1..=12)
+            .map(|number: i32| {
+                Pin::new(
+                    number.to_string(),
+                    Point::new(
+                        Length::new(-5.08),
+                        Length::new(13.97) - number * PIN_SPACING,
+                    ),
+                    PinDirection::Right,
+                )
+            })
+            .collect();
+
+        Self::new(
+            "FFC_Connector",
+            "J",
+            "FFC_Connector",
+            "ffc_connector_dual_side",
+            vec![Graphic::rectangle(
+                point!(-1.27, 12.7),
+                point!(1.27, -17.78),
+            )],
+            pins,
+        )
+    }
+
+    /// Returns the library ID of the symbol.
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the value of the symbol.
+    pub fn value(&self) -> &str {
+        &self.1.properties[1].1
+    }
+
+    /// Returns the footprint of the symbol.
+    pub fn footprint(&self) -> &str {
+        &self.1.properties[2].1
+    }
+
+    /// Returns the position and direction of the pin with the given number.
+    pub fn pin(&self, number: &str) -> Option<(Point, PinDirection)> {
+        self.1.units.iter().flat_map(|unit| &unit.1.pins).find_map(
+            |Pin(_, _, settings, direction)| {
+                (settings.number.0 == number).then_some((settings.at.point(), *direction))
+            },
+        )
+    }
+
+    /// Returns the numbers of all pins.
+    pub fn pin_numbers(&self) -> Vec<String> {
+        self.1
+            .units
+            .iter()
+            .flat_map(|unit| &unit.1.pins)
+            .map(|Pin(_, _, settings, _)| settings.number.0.clone())
+            .collect()
+    }
+
+    fn new(
+        name: &'static str,
+        reference: &'static str,
+        value: &'static str,
+        footprint: &'static str,
+        graphics: Vec<Graphic>,
+        pins: Vec<Pin>,
+    ) -> Self {
+        let (circles, polylines, rectangles) = graphics.into_iter().fold(
+            (Vec::new(), Vec::new(), Vec::new()),
+            |(mut circles, mut polylines, mut rectangles), graphic| {
+                match graphic {
+                    Graphic::Circle(circle) => circles.push(circle),
+                    Graphic::Polyline(polyline) => polylines.push(polyline),
+                    Graphic::Rectangle(rectangle) => rectangles.push(rectangle),
+                }
+                (circles, polylines, rectangles)
+            },
+        );
+
+        let origin = position!(0, 0, Some(0.deg()));
+
+        Self(
+            format!("concavum:{name}"),
+            LibSymbolSettings {
+                pin_numbers: Visibility::Hide,
+                pin_names: Visibility::Hide,
+                exclude_from_sim: false,
+                in_bom: true,
+                on_board: true,
+                properties: vec![
+                    Property::new(
+                        "Reference",
+                        reference.to_owned(),
+                        position!(0, 6.35, Some(0.deg())),
+                    ),
+                    Property::hidden("Value", value.to_owned(), origin),
+                    Property::hidden("Footprint", footprint.to_owned(), origin),
+                    Property::hidden("Datasheet", String::new(), origin),
+                    Property::hidden("Description", String::new(), origin),
+                ],
+                units: vec![Unit(
+                    format!("{name}_1_1"),
+                    UnitSettings {
+                        circles,
+                        polylines,
+                        rectangles,
+                        pins,
+                    },
+                )],
+            },
+        )
+    }
+}
+
+/// The direction a pin points to from its connection point towards the symbol body.
+#[derive(Clone, Copy)]
+pub enum PinDirection {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+impl PinDirection {
+    /// Returns the angle of the direction.
+    pub fn angle(self) -> Angle {
+        match self {
+            PinDirection::Right => 0.deg(),
+            PinDirection::Up => 90.deg(),
+            PinDirection::Left => 180.deg(),
+            PinDirection::Down => 270.deg(),
+        }
+    }
+
+    /// Returns the opposite direction.
+    pub fn opposite(self) -> Self {
+        match self {
+            PinDirection::Right => PinDirection::Left,
+            PinDirection::Up => PinDirection::Down,
+            PinDirection::Left => PinDirection::Right,
+            PinDirection::Down => PinDirection::Up,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LibSymbolSettings {
+    pin_numbers: Visibility,
+    pin_names: Visibility,
+    exclude_from_sim: bool,
+    in_bom: bool,
+    on_board: bool,
+    #[serde(rename = "property_")]
+    properties: Vec<Property>,
+    #[serde(rename = "symbol_")]
+    units: Vec<Unit>,
+}
+
+#[derive(Serialize)]
+struct Unit(String, UnitSettings);
+
+#[derive(Serialize)]
+struct UnitSettings {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    circles: Vec<Circle>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    polylines: Vec<Polyline>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rectangles: Vec<Rectangle>,
+    pins: Vec<Pin>,
+}
+
+/// A graphical element of a symbol.
+enum Graphic {
+    Circle(Circle),
+    Polyline(Polyline),
+    Rectangle(Rectangle),
+}
+
+impl Graphic {
+    fn circle(center: Point, radius: Length) -> Self {
+        Self::Circle(Circle {
+            center,
+            radius,
+            stroke: Stroke::default(),
+            fill: Fill::new(FillType::None),
+        })
+    }
+
+    fn polyline(points: Vec<Point>) -> Self {
+        Self::Polyline(Polyline {
+            pts: Points { xys: points },
+            stroke: Stroke::default(),
+            fill: Fill::new(FillType::None),
+        })
+    }
+
+    fn rectangle(start: Point, end: Point) -> Self {
+        Self::Rectangle(Rectangle {
+            start,
+            end,
+            stroke: Stroke::default(),
+            fill: Fill::new(FillType::Background),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Circle {
+    center: Point,
+    radius: Length,
+    stroke: Stroke,
+    fill: Fill,
+}
+
+#[derive(Serialize)]
+struct Polyline {
+    pts: Points,
+    stroke: Stroke,
+    fill: Fill,
+}
+
+#[derive(Serialize)]
+struct Points {
+    xys: Vec<Point>,
+}
+
+#[derive(Serialize)]
+struct Rectangle {
+    start: Point,
+    end: Point,
+    stroke: Stroke,
+    fill: Fill,
+}
+
+#[derive(Serialize)]
+struct Fill {
+    #[serde(rename = "type")]
+    fill_type: FillType,
+}
+
+impl Fill {
+    fn new(fill_type: FillType) -> Self {
+        Self { fill_type }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FillType {
+    None,
+    Background,
+}
+
+#[derive(Serialize)]
+struct Pin(PinType, PinShape, PinSettings, #[serde(skip)] PinDirection);
+
+impl Pin {
+    fn new(number: impl Into<String>, position: Point, direction: PinDirection) -> Self {
+        Self(
+            PinType::Passive,
+            PinShape::Line,
+            PinSettings {
+                at: Position::new(position.x(), position.y(), Some(direction.angle())),
+                length: PIN_LENGTH,
+                name: PinText("~".to_owned(), PinTextSettings::default()),
+                number: PinText(number.into(), PinTextSettings::default()),
+            },
+            direction,
+        )
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PinType {
+    Passive,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PinShape {
+    Line,
+}
+
+#[derive(Serialize)]
+struct PinSettings {
+    at: Position,
+    length: Length,
+    name: PinText,
+    number: PinText,
+}
+
+#[derive(Serialize)]
+struct PinText(String, PinTextSettings);
+
+#[derive(Serialize, Default)]
+struct PinTextSettings {
+    effects: Effects,
+}
@@ -1,10 +1,20 @@
-//! The `pcb` crate contains everything required for generating the matrix PCB.
+//! The `pcb` crate contains everything required for generating the matrix and interface PCB.
 
+mod drc;
 mod footprints;
+mod gerber;
+mod interface_pcb;
 mod kicad_pcb;
+mod kicad_schematic;
 mod matrix_pcb;
+mod panel;
 mod path;
 mod primitives;
 mod unit;
 
+pub use gerber::FabricationFile;
+pub use interface_pcb::InterfacePcb;
+pub use kicad_pcb::{KicadPcb, ParseError};
 pub use matrix_pcb::MatrixPcb;
+pub use panel::Panel;
+pub use primitives::UuidMode;
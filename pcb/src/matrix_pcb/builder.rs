@@ -1,75 +1,118 @@
-use std::iter::once;
+use std::{cmp::Ordering, hash::Hasher, iter::once, ops::Range};
 
-use config::Config;
-use model::{
-    matrix_pcb::{MatrixPcb as Model, PAD_SIZE, ROUTER_BIT_DIAMETER, THICKNESS},
-    KeyPositions,
-};
+use config::{Config, StableHasher, SwitchType};
+use model::{matrix_pcb::MatrixPcb as Model, KeyPositions};
 
 use crate::{
-    footprints::{FfcConnector, Switch, Tab},
+    footprints::{FfcConnector, Footprint, Switch, Tab},
     kicad_pcb::{KicadPcb, Net},
+    kicad_schematic::{KicadSchematic, LibSymbol},
     matrix_pcb::{
-        centered_track_offset,
         connector::Connector,
         features::{Column, Features, ThumbSwitches},
         nets::Nets,
-        AddPath, BOTTOM_LAYER, TOP_LAYER,
+        AddPath, Rules, BOTTOM_LAYER, TOP_LAYER,
     },
     point, position,
-    primitives::Position,
+    primitives::{Point, Position, UuidMode},
     unit::{IntoAngle, Length},
 };
 
+/// The name of the project the schematic belongs to.
+const PROJECT_NAME: &str = "matrix_pcb";
+/// The position of the first symbol in the schematic.
+const SCHEMATIC_ORIGIN: Point = Point::new(Length::new(25.4), Length::new(25.4));
+/// The horizontal distance between the symbols of adjacent columns in the schematic.
+const SCHEMATIC_COLUMN_SPACING: Length = Length::new(25.4);
+/// The vertical distance between the symbols of adjacent rows in the schematic.
+const SCHEMATIC_ROW_SPACING: Length = Length::new(17.78);
+/// The height of the config hash on the silkscreen.
+const CONFIG_HASH_HEIGHT: Length = Length::new(1.0);
+
 /// A builder for the matrix PCB.
 pub struct Builder {
     pcb: KicadPcb,
+    schematic: KicadSchematic,
     model: Model,
+    rules: Rules,
+    switch_type: SwitchType,
+    hot_swap: bool,
     cluster_connector_index: usize,
     home_row_index: usize,
     row_count: usize,
     column_count: usize,
     thumb_switch_count: usize,
     switch_count: usize,
+    channel_track_count: usize,
+    pad_channel_track_count: usize,
+    config_hash: String,
 }
 
 impl Builder {
-    /// Creates a new builder from the given config.
-    pub fn from_config(config: &Config) -> Self {
-        let pcb = KicadPcb::new(THICKNESS.into());
+    /// Creates a new builder following the given rules from the given config, creating UUIDs
+    /// using the given mode.
+    pub fn from_config(config: &Config, rules: Rules, uuid_mode: UuidMode) -> Self {
+        let pcb = KicadPcb::new(rules.dimensions.thickness.into(), uuid_mode);
 
         let key_positions = KeyPositions::from_config(config);
-        let model = Model::from_positions(&key_positions);
+        let model = Model::from_positions(&key_positions, rules.dimensions);
 
+        let matrix_rows = config.finger_cluster.matrix_rows();
         #[allow(clippy::cast_sign_loss)]
-        let home_row_index = key_positions.columns.home_row_index as usize;
+        let home_row_index = i8::from(matrix_rows.home_row_index) as usize;
         let cluster_connector_index =
             usize::from(config.finger_cluster.columns.left_side_column.active);
         #[allow(clippy::cast_sign_loss)]
-        let row_count = i8::from(config.finger_cluster.rows) as usize;
+        let row_count = i8::from(matrix_rows.count) as usize;
         let column_count = model.column_key_connectors.len();
         #[allow(clippy::cast_sign_loss)]
         let thumb_switch_count = i8::from(config.thumb_cluster.keys) as usize;
 
+        // Hash the bytes of the exported sections affecting the board to keep the hash the same
+        // across Rust versions and independent of the preview, colors, firmware and keymap
+        let mut table =
+            toml::Table::try_from(config).expect("the configuration should be serializable");
+        table.retain(|section, _| {
+            [
+                "finger_cluster",
+                "thumb_cluster",
+                "keyboard",
+                "key_overrides",
+                "fabrication",
+            ]
+            .contains(&section)
+        });
+        let toml = toml::to_string(&table).expect("the configuration should be serializable");
+        let mut hasher = StableHasher::default();
+        hasher.write(toml.as_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        let config_hash = format!("{:08x}", hasher.finish() as u32);
+
         Self {
             pcb,
+            schematic: KicadSchematic::new(PROJECT_NAME, uuid_mode),
             model,
+            rules,
+            switch_type: config.keyboard.switch_type,
+            hot_swap: config.keyboard.hot_swap,
             cluster_connector_index,
             home_row_index,
             row_count,
             column_count,
             thumb_switch_count,
             switch_count: 0,
+            channel_track_count: 0,
+            pad_channel_track_count: 0,
+            config_hash,
         }
     }
 
-    /// Builds the matrix PCB.
-    pub fn build(mut self) -> KicadPcb {
-        let features = Features::from_model(
-            &self.model,
-            self.home_row_index,
-            self.cluster_connector_index,
-        );
+    /// Builds the matrix PCB and the corresponding schematic.
+    ///
+    /// Also returns the maximum numbers of tracks routed side by side through a connector and
+    /// along the channel between the switch pins and the edge of a pad.
+    pub fn build(mut self) -> (KicadPcb, KicadSchematic, usize, usize) {
+        let features = Features::from_model(&self.model, self.rules, self.cluster_connector_index);
         let nets = Nets::create(
             &mut self.pcb,
             self.row_count,
@@ -91,8 +134,14 @@ impl Builder {
         features.thumb_switches.add_tracks(&mut self.pcb, &nets);
 
         self.add_tabs(&features);
-
-        self.pcb
+        self.add_config_hash(&features);
+
+        (
+            self.pcb,
+            self.schematic,
+            self.channel_track_count,
+            self.pad_channel_track_count,
+        )
     }
 
     /// Adds the outline to the PCB using the given features.
@@ -125,76 +174,155 @@ impl Builder {
 
     /// Adds the switches for the finger and thumb cluster to the PCB.
     fn add_switches(&mut self, columns: &[Column], thumb_switches: &ThumbSwitches, nets: &Nets) {
-        for (&position, column_net) in thumb_switches.positions().iter().zip(nets.thumb_columns()) {
-            self.add_switch(position, nets.thumb_row().clone(), column_net.clone());
+        for (column_index, (&position, column_net)) in thumb_switches
+            .positions()
+            .iter()
+            .zip(nets.thumb_columns())
+            .enumerate()
+        {
+            self.add_switch(
+                position,
+                nets.thumb_row().clone(),
+                column_net.clone(),
+                schematic_position(0, column_index),
+            );
         }
-        for (column, column_net) in columns.iter().zip(nets.columns()) {
-            for (&position, row_net) in column.positions().zip(nets.finger_rows()) {
-                self.add_switch(position, row_net.clone(), column_net.clone());
+        for (column_index, (column, column_net)) in columns.iter().zip(nets.columns()).enumerate() {
+            let matrix_rows = column.matrix_rows(self.home_row_index);
+            let row_nets = &nets.finger_rows()[matrix_rows.clone()];
+
+            for ((&position, row_net), row_index) in
+                column.positions().zip(row_nets).zip(matrix_rows)
+            {
+                self.add_switch(
+                    position,
+                    row_net.clone(),
+                    column_net.clone(),
+                    schematic_position(row_index + 1, column_index),
+                );
             }
         }
     }
 
     /// Adds a single switch at the given position and with the given row and column net to the PCB.
-    fn add_switch(&mut self, position: Position, row_net: Net, column_net: Net) {
+    ///
+    /// The corresponding symbol is added to the schematic at the given schematic position.
+    fn add_switch(
+        &mut self,
+        position: Position,
+        row_net: Net,
+        column_net: Net,
+        schematic_position: Point,
+    ) {
         self.switch_count += 1;
         let reference = format!("SW{}", self.switch_count);
         let internal_net = self.pcb.create_net(reference.clone());
 
-        let column_track_points = [
-            position + point!(2.54, -5.08),
-            position + point!(3.81, -2.54),
-        ];
-        let internal_track_points = [
-            position + point!(-2.54, -5.08),
-            position + point!(-3.81, -2.54),
-            position + point!(-3.81, 0.24),
-            position + point!(-1.65, 2.4),
-            position + point!(-1.65, 3.4),
-        ];
-        self.pcb
-            .add_track(&column_track_points, TOP_LAYER, &column_net);
-        self.pcb
-            .add_track(&internal_track_points, TOP_LAYER, &internal_net);
+        let path = self.schematic.add_symbol(
+            LibSymbol::switch(Switch::value(self.switch_type)),
+            reference.clone(),
+            schematic_position,
+            &[("1", &row_net), ("2", &column_net), ("3", &internal_net)],
+        );
+
+        let switch = Switch::new(
+            reference,
+            position,
+            self.switch_type,
+            self.hot_swap,
+            row_net,
+            column_net,
+            internal_net,
+        );
+        for (track_path, layer, net) in switch.tracks() {
+            self.pcb
+                .add_track(&track_path, self.rules.track_width, layer, net);
+        }
 
-        let switch = Switch::new(reference, position, row_net, column_net, internal_net);
-        self.pcb.add_footprint(switch.into());
+        let mut footprint = Footprint::from(switch);
+        footprint.set_path(path);
+        self.pcb.add_footprint(footprint);
     }
 
     /// Adds the FFC connector to the PCB.
     fn add_ffc_connector(&mut self, position: Position, nets: &Nets) {
-        let ffc_connector = FfcConnector::new("J1".to_owned(), position, nets.ffc_connector_nets());
+        let reference = "J1".to_owned();
+        let ffc_connector_nets = nets.ffc_connector_nets();
 
-        self.pcb.add_footprint(ffc_connector.into());
+        let pin_numbers: Vec<_> = (1..=ffc_connector_nets.len())
+            .map(|number| number.to_string())
+            .collect();
+        let pin_nets: Vec<_> = pin_numbers
+            .iter()
+            .map(String::as_str)
+            .zip(&ffc_connector_nets)
+            .collect();
+        let path = self.schematic.add_symbol(
+            LibSymbol::ffc_connector(),
+            reference.clone(),
+            schematic_position(1, Nets::MAXIMUM_COLUMNS + 1),
+            &pin_nets,
+        );
+
+        let ffc_connector = FfcConnector::new(reference, position, ffc_connector_nets);
+        let mut footprint = Footprint::from(ffc_connector);
+        footprint.set_path(path);
+        self.pcb.add_footprint(footprint);
     }
 
     /// Adds the column connector tracks to the PCB.
     fn add_column_connector_tracks(&mut self, features: &Features, nets: &Nets) {
-        let bottom_nets = &nets.finger_rows();
+        let routed_rows = self.routed_rows(&features.columns);
 
-        for (i, connector) in features.column_connectors.iter().rev().enumerate() {
+        for (i, (column_index, connector)) in features
+            .column_connectors
+            .iter()
+            .enumerate()
+            .rev()
+            .enumerate()
+        {
             let top_nets = if i >= self.column_count - self.cluster_connector_index - 1 {
                 &nets.columns()[..self.cluster_connector_index]
             } else {
                 &nets.columns()[self.column_count - i - 1..]
             };
-
             self.add_connector_tracks(connector, TOP_LAYER, top_nets);
-            self.add_connector_tracks(connector, BOTTOM_LAYER, bottom_nets);
+
+            // Only connect the rows routed through the column further away from the FFC connector
+            let outer_column_index = if column_index < self.cluster_connector_index {
+                column_index
+            } else {
+                column_index + 1
+            };
+
+            self.channel_track_count = self.channel_track_count.max(self.row_count);
+
+            for row in routed_rows[outer_column_index].clone() {
+                let offset = -self.rules.centered_track_offset(row, self.row_count);
+
+                connector.add_track(
+                    &mut self.pcb,
+                    offset,
+                    BOTTOM_LAYER,
+                    &nets.finger_rows()[row],
+                );
+            }
         }
     }
 
     /// Adds the cluster connector tracks to the PCB.
     fn add_cluster_connector_tracks(&mut self, features: &Features, nets: &Nets) {
+        self.channel_track_count = self.channel_track_count.max(2);
+
         features.cluster_connector.add_track(
             &mut self.pcb,
-            centered_track_offset(0, 2),
+            self.rules.centered_track_offset(0, 2),
             TOP_LAYER,
             nets.thumb_row(),
         );
         features.cluster_connector.add_track(
             &mut self.pcb,
-            centered_track_offset(1, 2),
+            self.rules.centered_track_offset(1, 2),
             TOP_LAYER,
             &nets.columns()[0],
         );
@@ -206,9 +334,10 @@ impl Builder {
     /// Adds a connector track for each of the given nets on the given layer.
     fn add_connector_tracks(&mut self, connector: &Connector, layer: &'static str, nets: &[Net]) {
         let sign = if layer == TOP_LAYER { 1 } else { -1 };
+        self.channel_track_count = self.channel_track_count.max(nets.len());
 
         for (i, net) in nets.iter().enumerate() {
-            let offset = sign * centered_track_offset(i, nets.len());
+            let offset = sign * self.rules.centered_track_offset(i, nets.len());
 
             connector.add_track(&mut self.pcb, offset, layer, net);
         }
@@ -225,10 +354,13 @@ impl Builder {
         };
         let right_column_connector = features.column_connectors.get(self.cluster_connector_index);
 
+        let ffc_column = &features.columns[self.cluster_connector_index];
+        let ffc_column_rows = ffc_column.matrix_rows(self.home_row_index);
         features.ffc_connector.add_row_tracks(
             &mut self.pcb,
-            nets.finger_rows(),
-            &features.columns[self.cluster_connector_index],
+            &nets.finger_rows()[ffc_column_rows.clone()],
+            ffc_column_rows.start,
+            ffc_column,
         );
         features.ffc_connector.add_column_tracks(
             &mut self.pcb,
@@ -250,6 +382,16 @@ impl Builder {
             column.add_switch_tracks(&mut self.pcb, net);
         }
 
+        for (index, (column, net)) in features.columns.iter().zip(nets.columns()).enumerate() {
+            let attachment_side = match index.cmp(&self.cluster_connector_index) {
+                Ordering::Less => features.column_connectors[index].start_attachment_side(),
+                Ordering::Equal => continue,
+                Ordering::Greater => features.column_connectors[index - 1].end_attachment_side(),
+            };
+
+            column.add_column_attachment_track(&mut self.pcb, net, attachment_side);
+        }
+
         for (i, (window, column)) in features
             .column_connectors
             .windows(2)
@@ -281,17 +423,31 @@ impl Builder {
 
     /// Adds the tracks for the rows to the PCB.
     fn add_row_tracks(&mut self, features: &Features, nets: &Nets) {
-        let home_row_offset = centered_track_offset(self.home_row_index, self.row_count);
+        let home_row_offset = self
+            .rules
+            .centered_track_offset(self.home_row_index, self.row_count);
         for column_connector in &features.column_connectors {
             column_connector.add_home_row_tracks(&mut self.pcb, nets.home_row(), home_row_offset);
         }
 
-        for (window, column) in features
+        let routed_rows = self.routed_rows(&features.columns);
+
+        // The rows above and below the home row are routed along the channels of the home switch
+        for rows in &routed_rows {
+            let rows_below = self.home_row_index.saturating_sub(rows.start);
+            let rows_above = rows.end.saturating_sub(self.home_row_index + 1);
+
+            self.pad_channel_track_count =
+                self.pad_channel_track_count.max(rows_below).max(rows_above);
+        }
+
+        for ((window, column), rows) in features
             .column_connectors
             .windows(2)
             .zip(features.columns.iter().skip(1))
+            .zip(routed_rows.iter().skip(1))
         {
-            column.add_inner_column_row_tracks(&mut self.pcb, nets, &window[0], &window[1]);
+            column.add_inner_column_row_tracks(&mut self.pcb, nets, rows, &window[0], &window[1]);
         }
 
         if let Some(column_connector) = features.column_connectors.first() {
@@ -310,16 +466,51 @@ impl Builder {
         }
     }
 
+    /// Returns the rows of the key matrix routed through each of the given columns.
+    ///
+    /// Besides its own rows, a column routes the rows of the columns further away from the FFC
+    /// connector.
+    fn routed_rows(&self, columns: &[Column]) -> Vec<Range<usize>> {
+        let mut routed_rows: Vec<_> = columns
+            .iter()
+            .map(|column| column.matrix_rows(self.home_row_index))
+            .collect();
+
+        let outer_column_indices = (0..self.cluster_connector_index)
+            .map(|index| (index + 1, index))
+            .chain(
+                (self.cluster_connector_index + 1..columns.len())
+                    .rev()
+                    .map(|index| (index - 1, index)),
+            );
+        for (index, outer_index) in outer_column_indices {
+            let (rows, outer_rows) = (&routed_rows[index], &routed_rows[outer_index]);
+            routed_rows[index] = rows.start.min(outer_rows.start)..rows.end.max(outer_rows.end);
+        }
+
+        routed_rows
+    }
+
+    /// Adds the hash of the config to the silkscreen above the last switch of the FFC column.
+    fn add_config_hash(&mut self, features: &Features) {
+        let position =
+            features.columns[self.cluster_connector_index].last() + position!(0, -7.0, None);
+
+        self.pcb
+            .add_silkscreen_text(&self.config_hash, position, CONFIG_HASH_HEIGHT);
+    }
+
     /// Adds the tab markers to the PCB.
     fn add_tabs(&mut self, features: &Features) {
-        let tab_offset = Length::new(PAD_SIZE.y / 2.0) + Length::EPSILON;
+        let pad_size = self.rules.dimensions.pad_size;
+        let tab_offset = Length::new(pad_size.y / 2.0) + Length::EPSILON;
         let last_thumb_switch = features.thumb_switches.last();
 
-        let upper_thumb_corner = last_thumb_switch + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0);
-        let lower_thumb_corner = last_thumb_switch + point!(PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0);
+        let upper_thumb_corner = last_thumb_switch + point!(pad_size.x / 2.0, -pad_size.y / 2.0);
+        let lower_thumb_corner = last_thumb_switch + point!(pad_size.x / 2.0, pad_size.y / 2.0);
 
         let minimum_x_value = upper_thumb_corner.x().max(lower_thumb_corner.x())
-            + ROUTER_BIT_DIAMETER.into()
+            + self.rules.dimensions.router_bit_diameter.into()
             + Tab::WIDTH / 2;
 
         for column in &features.columns {
@@ -340,3 +531,12 @@ impl Builder {
         }
     }
 }
+
+/// Returns the position of the symbol in the given row and column of the schematic.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn schematic_position(row: usize, column: usize) -> Point {
+    Point::new(
+        SCHEMATIC_ORIGIN.x() + column as i32 * SCHEMATIC_COLUMN_SPACING,
+        SCHEMATIC_ORIGIN.y() + row as i32 * SCHEMATIC_ROW_SPACING,
+    )
+}
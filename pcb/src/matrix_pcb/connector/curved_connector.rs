@@ -1,11 +1,8 @@
-use model::matrix_pcb::{
-    ClusterConnector, NormalColumnConnector, Segment, ARC_RADIUS, CONNECTOR_WIDTH,
-    MINIMUM_SEGMENT_LENGTH, PAD_SIZE,
-};
+use model::matrix_pcb::{ClusterConnector, NormalColumnConnector, Segment};
 
 use crate::{
     kicad_pcb::{KicadPcb, Net},
-    matrix_pcb::{connector::AttachmentSide, OUTLINE_LAYER, OUTLINE_WIDTH, TRACK_WIDTH},
+    matrix_pcb::{connector::AttachmentSide, Rules, OUTLINE_LAYER},
     point, position,
     primitives::{Point, Position},
     unit::{Angle, IntoAngle, Length},
@@ -19,23 +16,26 @@ pub struct CurvedConnector {
     start_switch_position: Position,
     end_switch_position: Position,
     attachment_side: AttachmentSide,
+    rules: Rules,
 }
 
 impl CurvedConnector {
-    /// Creates a connector from a normal column connector and a switch position.
+    /// Creates a connector following the given rules from a normal column connector and a switch
+    /// position.
     pub fn from_normal_column_connector(
         normal_column_connector: &NormalColumnConnector,
         start_switch_position: Position,
+        rules: Rules,
     ) -> Self {
         let length = normal_column_connector.bezier_curve.length();
         let direction = normal_column_connector.left_arc_side.direction();
         let segment_length = normal_column_connector.segment_length.into();
 
-        let radius = Length::from(direction * ARC_RADIUS);
+        let radius = Length::from(direction * normal_column_connector.arc_radius);
         let angle = (direction * 90.0).deg();
         let offset = position!(
-            PAD_SIZE.x / 2.0 + normal_column_connector.segment_length,
-            -direction * (PAD_SIZE.y - CONNECTOR_WIDTH) / 2.0,
+            rules.dimensions.pad_size.x / 2.0 + normal_column_connector.segment_length,
+            -direction * (rules.dimensions.pad_size.y - rules.dimensions.connector_width) / 2.0,
             None
         );
 
@@ -59,34 +59,40 @@ impl CurvedConnector {
             start_switch_position,
             end_switch_position,
             attachment_side,
+            rules,
         }
     }
 
-    /// Creates a connector from a cluster connector and a start position.
+    /// Creates a connector following the given rules from a cluster connector and a start
+    /// position.
     pub fn from_cluster_connector(
         cluster_connector: &ClusterConnector,
         start: Position,
         start_switch_position: Position,
+        rules: Rules,
     ) -> Self {
-        let segment_length = MINIMUM_SEGMENT_LENGTH.into();
+        let dimensions = cluster_connector.dimensions;
+        let minimum_segment_length = dimensions.minimum_segment_length();
+        let arc_radius = dimensions.arc_radius();
+        let segment_length = minimum_segment_length.into();
         let length = cluster_connector.bezier_curve.length();
 
         let first_arc = Arc::new(
-            start + position!(MINIMUM_SEGMENT_LENGTH, 0, None),
-            (-ARC_RADIUS).into(),
+            start + position!(minimum_segment_length, 0, None),
+            (-arc_radius).into(),
             -cluster_connector.finger_cluster_arc_angle.rad(),
         );
         let second_arc_start = first_arc.end_position() + position!(length, 0, None);
         let second_arc = Arc::new(
             second_arc_start,
-            ARC_RADIUS.into(),
+            arc_radius.into(),
             cluster_connector.thumb_cluster_arc_angle.rad(),
         );
 
         let end_switch_position = second_arc.end_position()
             + position!(
-                PAD_SIZE.y / 2.0 + MINIMUM_SEGMENT_LENGTH,
-                (PAD_SIZE.x - CONNECTOR_WIDTH) / 2.0,
+                dimensions.pad_size.y / 2.0 + minimum_segment_length,
+                (dimensions.pad_size.x - dimensions.connector_width) / 2.0,
                 Some(90.deg())
             );
 
@@ -99,6 +105,7 @@ impl CurvedConnector {
             start_switch_position,
             end_switch_position,
             attachment_side,
+            rules,
         }
     }
 
@@ -127,39 +134,34 @@ impl CurvedConnector {
         self.attachment_side
     }
 
+    /// Returns the rules the connector follows.
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
     /// Adds the outline of the connector to the PCB.
     pub fn add_outline(&self, pcb: &mut KicadPcb) {
         let start_position = self.start_position();
         let end_position = self.end_position();
+        let width = self.rules.outline_width;
 
         for sign in [-1, 1] {
-            let offset = sign * Length::from(CONNECTOR_WIDTH / 2.0);
+            let offset = sign * Length::from(self.rules.dimensions.connector_width / 2.0);
 
             let first_arc = self.first_arc.offset(offset);
             let second_arc = self.second_arc.offset(offset);
 
-            pcb.add_graphical_line(
-                first_arc.end(),
-                second_arc.start(),
-                OUTLINE_WIDTH,
-                OUTLINE_LAYER,
-            );
+            pcb.add_graphical_line(first_arc.end(), second_arc.start(), width, OUTLINE_LAYER);
 
             for arc in [first_arc, second_arc] {
-                pcb.add_graphical_arc(
-                    arc.start(),
-                    arc.mid(),
-                    arc.end(),
-                    OUTLINE_WIDTH,
-                    OUTLINE_LAYER,
-                );
+                pcb.add_graphical_arc(arc.start(), arc.mid(), arc.end(), width, OUTLINE_LAYER);
             }
 
             for (sign, position) in [(1, start_position), (-1, end_position)] {
                 pcb.add_graphical_line(
                     position + point!(0, offset),
                     position + point!(sign * self.segment_length, offset),
-                    OUTLINE_WIDTH,
+                    width,
                     OUTLINE_LAYER,
                 );
             }
@@ -170,17 +172,18 @@ impl CurvedConnector {
     pub fn add_track(&self, pcb: &mut KicadPcb, offset: Length, layer: &'static str, net: &Net) {
         let first_arc = self.first_arc.offset(offset);
         let second_arc = self.second_arc.offset(offset);
+        let width = self.rules.track_width;
 
-        pcb.add_segment(first_arc.end(), second_arc.start(), TRACK_WIDTH, layer, net);
+        pcb.add_segment(first_arc.end(), second_arc.start(), width, layer, net);
         for arc in [first_arc, second_arc] {
-            pcb.add_arc(arc.start(), arc.mid(), arc.end(), TRACK_WIDTH, layer, net);
+            pcb.add_arc(arc.start(), arc.mid(), arc.end(), width, layer, net);
         }
 
         for (sign, position) in [(1, self.start_position()), (-1, self.end_position())] {
             pcb.add_segment(
                 position + point!(0, offset),
                 position + point!(sign * self.segment_length, offset),
-                TRACK_WIDTH,
+                width,
                 layer,
                 net,
             );
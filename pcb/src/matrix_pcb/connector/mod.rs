@@ -2,17 +2,15 @@ mod curved_connector;
 mod straight_connector;
 
 use curved_connector::CurvedConnector;
-use model::matrix_pcb::{ClusterConnector, ColumnConnector, CONNECTOR_WIDTH, PAD_SIZE};
+use model::matrix_pcb::{ClusterConnector, ColumnConnector};
 use straight_connector::StraightConnector;
 
 use crate::{
-    footprints::{
-        ABOVE_ROW_PAD, BELOW_ROW_PAD, LEFT_OF_ROW_PAD, LOWER_COLUMN_PAD, ROW_PAD, UPPER_COLUMN_PAD,
-    },
+    footprints::ROW_PAD,
     kicad_pcb::{KicadPcb, Net},
-    matrix_pcb::{centered_track_offset, x_offset, AddPath, BOTTOM_LAYER, TOP_LAYER},
+    matrix_pcb::{AddPath, Rules, BOTTOM_LAYER, TOP_LAYER},
     path::Path,
-    point,
+    point, position,
     primitives::{Point, Position},
     unit::Length,
 };
@@ -24,40 +22,54 @@ pub enum Connector {
 }
 
 impl Connector {
-    /// Creates a new connector from a column connector.
+    /// Creates a new connector following the given rules from a column connector.
     pub fn from_column_connector(
         column_connector: &ColumnConnector,
         switch_position: Position,
+        rules: Rules,
     ) -> Self {
         match column_connector {
             ColumnConnector::Side(side_column_connector) => {
                 Self::Straight(StraightConnector::from_side_column_connector(
                     side_column_connector,
                     switch_position,
+                    rules,
                 ))
             }
             ColumnConnector::Normal(normal_column_connector) => {
                 Self::Curved(CurvedConnector::from_normal_column_connector(
                     normal_column_connector,
                     switch_position,
+                    rules,
                 ))
             }
         }
     }
 
-    /// Creates a connector from a cluster connector and a start position.
+    /// Creates a connector following the given rules from a cluster connector and a start
+    /// position.
     pub fn from_cluster_connector(
         cluster_connector: &ClusterConnector,
         start: Position,
         start_switch_position: Position,
+        rules: Rules,
     ) -> Self {
         Self::Curved(CurvedConnector::from_cluster_connector(
             cluster_connector,
             start,
             start_switch_position,
+            rules,
         ))
     }
 
+    /// Returns the rules the connector follows.
+    pub fn rules(&self) -> Rules {
+        match self {
+            Connector::Straight(straight_connector) => straight_connector.rules(),
+            Connector::Curved(curved_connector) => curved_connector.rules(),
+        }
+    }
+
     /// Returns the start position of the connector.
     pub fn start_position(&self) -> Position {
         match self {
@@ -125,29 +137,34 @@ impl Connector {
 
     /// Adds the column track connecting the column to the left.
     pub fn add_left_column_track(&self, pcb: &mut KicadPcb, column_net: &Net) {
-        let position = self.start_position();
-        let track_points = [
-            position.point(),
-            position + point!(-Length::from(PAD_SIZE.x / 2.0) + LOWER_COLUMN_PAD.x(), 0),
-        ];
-
-        pcb.add_track(&track_points, TOP_LAYER, column_net);
+        let rules = self.rules();
+        let pad_size = rules.dimensions.pad_size;
+        let track_path = rules
+            .routing
+            .side_path(
+                point!(pad_size.x / 2.0, 0),
+                rules.routing.center_column_point,
+            )
+            .at(self.start_position() + position!(-pad_size.x / 2.0, 0, None));
+
+        pcb.add_track(&track_path, rules.track_width, TOP_LAYER, column_net);
     }
 
     /// Adds the column track connecting the column to the right.
     pub fn add_right_column_track(&self, pcb: &mut KicadPcb, track_count: usize, column_net: &Net) {
+        let rules = self.rules();
         let attachment_side = self.end_attachment_side();
         let start = point!(
-            -PAD_SIZE.x / 2.0,
-            attachment_side.y_offset() + centered_track_offset(0, track_count)
+            -rules.dimensions.pad_size.x / 2.0,
+            attachment_side.y_offset(rules) + rules.centered_track_offset(0, track_count)
         );
 
-        let end = point!(LOWER_COLUMN_PAD.x(), ROW_PAD.y());
+        let end = point!(rules.routing.lower_column_pad.x(), ROW_PAD.y());
 
         let track_path = match attachment_side {
-            AttachmentSide::Top => Path::angled_start(start, UPPER_COLUMN_PAD),
+            AttachmentSide::Top => Path::angled_start(start, rules.routing.top_column_pin),
             AttachmentSide::Center => {
-                let center = point!(0, BELOW_ROW_PAD.y());
+                let center = point!(0, rules.routing.below_row_pad.y());
 
                 Path::angled_end(start, center).join(&Path::angled_start(center, end))
             }
@@ -155,11 +172,13 @@ impl Connector {
         }
         .at(self.end_switch_position());
 
-        pcb.add_track(&track_path, TOP_LAYER, column_net);
+        pcb.add_track(&track_path, rules.track_width, TOP_LAYER, column_net);
     }
 
     /// Adds the home row tracks connecting to the left and right columns to the PCB.
     pub fn add_home_row_tracks(&self, pcb: &mut KicadPcb, net: &Net, home_row_offset: Length) {
+        let rules = self.rules();
+
         for (left, sign_x, attachment_side, position) in [
             (
                 true,
@@ -174,33 +193,47 @@ impl Connector {
                 self.end_switch_position(),
             ),
         ] {
-            let y_offset = attachment_side.y_offset() - home_row_offset;
-            let start_point = point!(sign_x * PAD_SIZE.x / 2.0, y_offset);
+            let y_offset = attachment_side.y_offset(rules) - home_row_offset;
+            let start_point = point!(sign_x * rules.dimensions.pad_size.x / 2.0, y_offset);
 
+            let routing = rules.routing;
             let home_row_track_path = if matches!(attachment_side, AttachmentSide::Top) {
-                let first_path_point = point!(2.4, -1.6);
-                let second_path_point = point!(0.7, -5.6);
-
-                Path::angled_start(start_point, second_path_point)
-                    .join(&Path::angled_start(second_path_point, first_path_point))
-                    .join(&Path::angled_start(first_path_point, ABOVE_ROW_PAD))
+                let path_points = if left {
+                    routing.top_right_home_row_points
+                } else {
+                    routing.top_left_home_row_points
+                };
+
+                path_points.iter().chain([&routing.above_row_pad]).fold(
+                    Path::new([start_point]),
+                    |path, &point| {
+                        let last = *path.last().expect("the path starts at the side edge");
+
+                        path.join(&Path::angled_start(last, point))
+                    },
+                )
+            } else if matches!(attachment_side, AttachmentSide::Center) {
+                if left {
+                    Path::angled_start(start_point, routing.center_right_home_row_point)
+                        .append(routing.above_row_pad)
+                } else {
+                    Path::angled_start(start_point, routing.below_internal_pad).join(
+                        &Path::angled_start(routing.below_internal_pad, routing.left_of_row_pad),
+                    )
+                }
             } else {
                 Path::angled_start(
                     start_point,
                     if left {
-                        if matches!(attachment_side, AttachmentSide::Center) {
-                            ABOVE_ROW_PAD
-                        } else {
-                            BELOW_ROW_PAD
-                        }
+                        routing.below_row_pad
                     } else {
-                        LEFT_OF_ROW_PAD
+                        routing.left_of_row_pad
                     },
                 )
             }
             .append(ROW_PAD)
             .at(position);
-            pcb.add_track(&home_row_track_path, BOTTOM_LAYER, net);
+            pcb.add_track(&home_row_track_path, rules.track_width, BOTTOM_LAYER, net);
         }
     }
 
@@ -222,21 +255,20 @@ impl Connector {
             .last()
             .expect("connector track should always have points");
 
-        let (edge_side, sign, chamfer_depth) = if above {
-            (AttachmentSide::Top, 1, Length::new(1.4))
+        let rules = self.rules();
+        let (edge_side, sign, y_offset, chamfer_depth) = if above {
+            let y_offset = rules.routing.upper_row_y_offset;
+            let chamfer_depth = Length::from(rules.dimensions.pad_size.y / 2.0) + y_offset;
+            (AttachmentSide::Top, 1, y_offset, chamfer_depth)
         } else {
-            (AttachmentSide::Bottom, -1, Length::new(3.0))
+            let y_offset = rules.routing.below_row_pad.y();
+            (AttachmentSide::Bottom, -1, y_offset, Length::new(3.0))
         };
 
         let left_is_at_edge = self.end_attachment_side() == edge_side;
         let right_is_at_edge = other.start_attachment_side() == edge_side;
 
-        let y_offset = if above {
-            (-6.6).into()
-        } else {
-            BELOW_ROW_PAD.y()
-        };
-        let x_offset = x_offset(0);
+        let x_offset = rules.x_offset(0);
 
         let center_point = position + point!(0, y_offset);
         let left_chamfer_points = Path::new([
@@ -272,7 +304,7 @@ impl Connector {
         };
 
         let attachment_point = if right_is_at_edge {
-            point!(x_offset, edge_side.y_offset() + track_offset)
+            point!(x_offset, edge_side.y_offset(rules) + track_offset)
         } else {
             point!(x_offset - chamfer_depth, y_offset)
         };
@@ -288,7 +320,9 @@ impl Connector {
         let sign_x = if right { 1 } else { -1 };
         let sign_y = if up { -1 } else { 1 };
 
-        let x_offset = sign_x * (Length::new(PAD_SIZE.x / 2.0) - x_offset(0));
+        let rules = self.rules();
+        let x_offset =
+            sign_x * (Length::from(rules.dimensions.pad_size.x / 2.0) - rules.x_offset(0));
 
         Path::chamfered(
             point!(0, y_offset),
@@ -313,13 +347,13 @@ pub enum AttachmentSide {
 }
 
 impl AttachmentSide {
-    /// Returns the Y offset corresponding to the attachment side.
-    pub fn y_offset(self) -> Length {
+    /// Returns the Y offset corresponding to the attachment side for the given rules.
+    pub fn y_offset(self, rules: Rules) -> Length {
         (match self {
             AttachmentSide::Top => -1.0,
             AttachmentSide::Center => 0.0,
             AttachmentSide::Bottom => 1.0,
-        } * (PAD_SIZE.y - CONNECTOR_WIDTH)
+        } * (rules.dimensions.pad_size.y - rules.dimensions.connector_width)
             / 2.0)
             .into()
     }
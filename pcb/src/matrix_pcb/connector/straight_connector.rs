@@ -1,8 +1,8 @@
-use model::matrix_pcb::{Segment, SideColumnConnector, CONNECTOR_WIDTH, PAD_SIZE};
+use model::matrix_pcb::{Segment, SideColumnConnector};
 
 use crate::{
     kicad_pcb::{KicadPcb, Net},
-    matrix_pcb::{OUTLINE_LAYER, OUTLINE_WIDTH, TRACK_WIDTH},
+    matrix_pcb::{Rules, OUTLINE_LAYER},
     point, position,
     primitives::Position,
     unit::Length,
@@ -12,18 +12,30 @@ use crate::{
 pub struct StraightConnector {
     start: Position,
     length: Length,
+    rules: Rules,
 }
 
 impl StraightConnector {
-    /// Creates a straight connector from a side column connector and a switch position.
+    /// Creates a straight connector following the given rules from a side column connector and a
+    /// switch position.
     pub fn from_side_column_connector(
         side_column_connector: &SideColumnConnector,
         switch_position: Position,
+        rules: Rules,
     ) -> Self {
-        let start = switch_position + position!(PAD_SIZE.x / 2.0, 0, None);
+        let start = switch_position + position!(rules.dimensions.pad_size.x / 2.0, 0, None);
         let length = side_column_connector.length().into();
 
-        Self { start, length }
+        Self {
+            start,
+            length,
+            rules,
+        }
+    }
+
+    /// Returns the rules the connector follows.
+    pub fn rules(&self) -> Rules {
+        self.rules
     }
 
     /// Returns the start position of the connector
@@ -32,7 +44,7 @@ impl StraightConnector {
     }
 
     pub fn start_switch_position(&self) -> Position {
-        self.start_position() + position!(-PAD_SIZE.x / 2.0, 0, None)
+        self.start_position() + position!(-self.rules.dimensions.pad_size.x / 2.0, 0, None)
     }
 
     /// Returns the end position of the connector.
@@ -42,20 +54,22 @@ impl StraightConnector {
 
     /// Returns the position of the switch at the end of the connector
     pub fn end_switch_position(&self) -> Position {
-        self.end_position() + position!(PAD_SIZE.x / 2.0, 0, None)
+        self.end_position() + position!(self.rules.dimensions.pad_size.x / 2.0, 0, None)
     }
 
     /// Adds the outline of the connector to the PCB.
     pub fn add_outline(&self, pcb: &mut KicadPcb) {
-        let offset = point!(0, CONNECTOR_WIDTH / 2.0);
+        let offset = point!(0, self.rules.dimensions.connector_width / 2.0);
 
         let start_top = self.start + offset;
         let start_bottom = self.start - offset;
         let end_top = self.end_position() + offset;
         let end_bottom = self.end_position() - offset;
 
-        pcb.add_graphical_line(start_top, end_top, OUTLINE_WIDTH, OUTLINE_LAYER);
-        pcb.add_graphical_line(start_bottom, end_bottom, OUTLINE_WIDTH, OUTLINE_LAYER);
+        let width = self.rules.outline_width;
+
+        pcb.add_graphical_line(start_top, end_top, width, OUTLINE_LAYER);
+        pcb.add_graphical_line(start_bottom, end_bottom, width, OUTLINE_LAYER);
     }
 
     /// Adds a track to the PCB with the given offset to the center.
@@ -64,6 +78,6 @@ impl StraightConnector {
         let start = self.start + offset;
         let end = self.end_position() + offset;
 
-        pcb.add_segment(start, end, TRACK_WIDTH, layer, net);
+        pcb.add_segment(start, end, self.rules.track_width, layer, net);
     }
 }
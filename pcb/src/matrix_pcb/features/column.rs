@@ -1,19 +1,14 @@
-use std::iter::once;
+use std::{iter::once, ops::Range};
 
-use model::matrix_pcb::{
-    ColumnKeyConnectors, Segment, CONNECTOR_WIDTH, PAD_SIZE, ROUTER_BIT_DIAMETER,
-};
+use model::matrix_pcb::{ColumnKeyConnectors, Segment};
 
 use crate::{
-    footprints::{
-        ABOVE_ROW_PAD, BELOW_ROW_PAD, LEFT_OF_ROW_PAD, LOWER_COLUMN_PAD, ROW_PAD, UPPER_COLUMN_PAD,
-    },
+    footprints::ROW_PAD,
     kicad_pcb::{KicadPcb, Net},
     matrix_pcb::{
-        centered_track_offset,
         connector::{AttachmentSide, Connector},
         nets::Nets,
-        track_offset, x_offset, AddPath, BOTTOM_LAYER, TOP_LAYER, TRACK_CLEARANCE, TRACK_WIDTH,
+        AddPath, Rules, BOTTOM_LAYER, TOP_LAYER,
     },
     path::Path,
     point,
@@ -27,30 +22,41 @@ pub struct Column {
     switches_below: Vec<Position>,
     switches_above: Vec<Position>,
     offsets: Vec<Length>,
+    rules: Rules,
 }
 
 impl Column {
-    /// Creates a new column from the corresponding key connectors and home switch position.
+    /// Creates a new column following the given rules from the corresponding key connectors and
+    /// home switch position.
     pub fn from_key_connectors(
         key_connectors: &ColumnKeyConnectors,
         home_switch: Position,
-        home_row_index: usize,
+        rules: Rules,
     ) -> Self {
-        let y_offset = (-key_connectors.connector.length() - PAD_SIZE.y).into();
+        let pad_size = rules.dimensions.pad_size;
         let offsets: Vec<_> = key_connectors
             .offsets
             .iter()
             .copied()
             .map(Into::into)
             .collect();
-        let (offsets_below, offsets_above) = offsets.split_at(home_row_index);
+        let switch_offsets: Vec<_> = offsets
+            .iter()
+            .zip(&key_connectors.connectors)
+            .map(|(&x_offset, connector)| {
+                let y_offset = (-connector.length() - pad_size.y).into();
+
+                Position::new(x_offset, y_offset, None)
+            })
+            .collect();
+        let (offsets_below, offsets_above) = switch_offsets.split_at(key_connectors.home_row_index);
 
         let mut position = home_switch;
         let switches_below = offsets_below
             .iter()
             .rev()
-            .map(|&x_offset| {
-                position -= Position::new(x_offset, y_offset, None);
+            .map(|&offset| {
+                position -= offset;
 
                 position
             })
@@ -59,8 +65,8 @@ impl Column {
         let mut position = home_switch;
         let switches_above = offsets_above
             .iter()
-            .map(|&x_offset| {
-                position += Position::new(x_offset, y_offset, None);
+            .map(|&offset| {
+                position += offset;
 
                 position
             })
@@ -71,6 +77,7 @@ impl Column {
             switches_below,
             switches_above,
             offsets,
+            rules,
         }
     }
 
@@ -83,6 +90,13 @@ impl Column {
             .chain(&self.switches_above)
     }
 
+    /// Returns the rows of the key matrix containing a switch of the column.
+    pub fn matrix_rows(&self, matrix_home_row_index: usize) -> Range<usize> {
+        let first_row = matrix_home_row_index - self.switches_below.len();
+
+        first_row..first_row + self.switches_below.len() + 1 + self.switches_above.len()
+    }
+
     /// Returns the position of the first switch in the column.
     pub fn first(&self) -> Position {
         self.switches_below
@@ -107,40 +121,49 @@ impl Column {
         right_connector_position: Option<Position>,
         is_ffc_column: bool,
     ) {
+        let pad_size = self.rules.dimensions.pad_size;
         for ((&bottom_switch, &top_switch), &offset) in self
             .positions()
             .zip(self.positions().skip(1))
             .zip(&self.offsets)
         {
-            add_connector_outline(pcb, bottom_switch, top_switch, offset);
+            add_connector_outline(pcb, bottom_switch, top_switch, offset, self.rules);
         }
 
         if let Some((&last, remaining)) = self.switches_below.split_last() {
             if !is_ffc_column {
-                add_bottom_switch_outline(pcb, last);
+                add_bottom_switch_outline(pcb, last, self.rules);
             }
 
             for &position in remaining {
-                add_pad_outline(pcb, position);
+                add_pad_outline(pcb, position, self.rules);
             }
+        } else if !is_ffc_column {
+            let bottom_outline = [
+                self.home_switch + point!(-pad_size.x / 2.0, pad_size.y / 2.0),
+                self.home_switch + point!(pad_size.x / 2.0, pad_size.y / 2.0),
+            ];
+            pcb.add_outline_path(&bottom_outline, self.rules.outline_width);
         }
         for &position in &self.switches_above {
-            add_pad_outline(pcb, position);
+            add_pad_outline(pcb, position, self.rules);
         }
 
         let last_position = self.last();
         let top_outline = [
-            last_position + point!(-PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-            last_position + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
+            last_position + point!(-pad_size.x / 2.0, -pad_size.y / 2.0),
+            last_position + point!(pad_size.x / 2.0, -pad_size.y / 2.0),
         ];
-        pcb.add_outline_path(&top_outline);
+        pcb.add_outline_path(&top_outline, self.rules.outline_width);
 
         self.add_home_switch_outline(pcb, left_connector_position, right_connector_position);
     }
 
     /// Adds the track connecting the switches in the column to each other.
     pub fn add_switch_tracks(&self, pcb: &mut KicadPcb, column_net: &Net) {
-        let x_offset = x_offset(0) - TRACK_WIDTH - TRACK_CLEARANCE;
+        let pad_size = self.rules.dimensions.pad_size;
+        let lower_column_pad = self.rules.routing.lower_column_pad;
+        let x_offset = self.rules.x_offset(0) - self.rules.track_width - self.rules.track_clearance;
 
         for ((&bottom_switch, &top_switch), &offset) in self
             .positions()
@@ -151,20 +174,40 @@ impl Column {
             let positive_offset = offset.max(0.into());
 
             let track_path = Path::angled_end_center(
-                UPPER_COLUMN_PAD,
-                point!(x_offset + negative_offset, -PAD_SIZE.y / 2.0),
+                self.rules.routing.upper_column_pad,
+                point!(x_offset + negative_offset, -pad_size.y / 2.0),
             )
             .at(bottom_switch)
             .join(
                 &Path::angled_start_center(
-                    point!(x_offset - positive_offset, PAD_SIZE.y / 2.0),
-                    point!(LOWER_COLUMN_PAD.x(), ROW_PAD.y()),
+                    point!(x_offset - positive_offset, pad_size.y / 2.0),
+                    point!(lower_column_pad.x(), ROW_PAD.y()),
                 )
-                .append(LOWER_COLUMN_PAD)
+                .append(lower_column_pad)
                 .at(top_switch),
             );
 
-            pcb.add_track(&track_path, TOP_LAYER, column_net);
+            pcb.add_track(&track_path, self.rules.track_width, TOP_LAYER, column_net);
+        }
+    }
+
+    /// Adds the track the column track from the given attachment side connects to if there is no
+    /// switch below the home switch.
+    ///
+    /// Otherwise, the column track connects to the track coming from the switch below.
+    pub fn add_column_attachment_track(
+        &self,
+        pcb: &mut KicadPcb,
+        column_net: &Net,
+        attachment_side: AttachmentSide,
+    ) {
+        if self.switches_below.is_empty() && attachment_side != AttachmentSide::Top {
+            let lower_column_pad = self.rules.routing.lower_column_pad;
+            let track_path =
+                Path::new([point!(lower_column_pad.x(), ROW_PAD.y()), lower_column_pad])
+                    .at(self.home_switch);
+
+            pcb.add_track(&track_path, self.rules.track_width, TOP_LAYER, column_net);
         }
     }
 
@@ -176,56 +219,117 @@ impl Column {
         left_column_connector: &Connector,
         right_column_connector: &Connector,
     ) {
-        let path = left_column_connector
-            .column_track(true, false, centered_track_offset(1, column_nets.len() + 1))
-            .join(&double_chamfer(0, false).at(self.first()))
-            .join(
-                &right_column_connector
-                    .column_track(false, false, centered_track_offset(0, column_nets.len()))
-                    .reverse(),
-            );
+        const CHAMFER_DEPTH: Length = Length::new(1.0);
+        let pad_size = self.rules.dimensions.pad_size;
+
+        let left_track_offset = self.rules.centered_track_offset(1, column_nets.len() + 1);
+        let right_track_offset = self.rules.centered_track_offset(0, column_nets.len());
+        let left_connector_path =
+            left_column_connector.column_track(true, false, left_track_offset);
+        let right_connector_path = right_column_connector
+            .column_track(false, false, right_track_offset)
+            .reverse();
+
+        // Without a switch below, tracks from connectors at the bottom can not pass below the
+        // home switch, so they are routed along its lower edge instead
+        let bottom_y_offset = AttachmentSide::Bottom.y_offset(self.rules);
+        let x_offset = self.rules.x_offset(0);
+        let left_start = point!(-pad_size.x / 2.0, bottom_y_offset + left_track_offset);
+        let right_end = point!(pad_size.x / 2.0, bottom_y_offset + right_track_offset);
+        let left_is_at_bottom = self.switches_below.is_empty()
+            && left_column_connector.end_attachment_side() == AttachmentSide::Bottom;
+        let right_is_at_bottom = self.switches_below.is_empty()
+            && right_column_connector.start_attachment_side() == AttachmentSide::Bottom;
+
+        let path = match (left_is_at_bottom, right_is_at_bottom) {
+            (false, false) => left_connector_path
+                .join(&double_chamfer(0, false, self.rules).at(self.first()))
+                .join(&right_connector_path),
+            (true, false) => Path::new([
+                left_start,
+                point!(x_offset - CHAMFER_DEPTH, left_start.y()),
+                point!(x_offset, left_start.y() - CHAMFER_DEPTH),
+            ])
+            .at(self.home_switch)
+            .join(&right_connector_path),
+            (false, true) => left_connector_path.join(
+                &Path::new([
+                    point!(-x_offset, right_end.y() - CHAMFER_DEPTH),
+                    point!(-x_offset + CHAMFER_DEPTH, right_end.y()),
+                    right_end,
+                ])
+                .at(self.home_switch),
+            ),
+            (true, true) => Path::angled_center(left_start, right_end).at(self.home_switch),
+        };
 
         for (i, column_net) in column_nets.iter().enumerate() {
-            pcb.add_track(&path.offset(-track_offset(i)), TOP_LAYER, column_net);
+            pcb.add_track(
+                &path.offset(-self.rules.track_offset(i)),
+                self.rules.track_width,
+                TOP_LAYER,
+                column_net,
+            );
         }
     }
 
     /// Adds the tracks connecting the rows in an inner column to the PCB.
+    ///
+    /// The given rows of the key matrix are routed through the column, which may exceed the rows
+    /// of the column itself by one row on each side.
     pub fn add_inner_column_row_tracks(
         &self,
         pcb: &mut KicadPcb,
         nets: &Nets,
+        rows: &Range<usize>,
         left_column_connector: &Connector,
         right_column_connector: &Connector,
     ) {
+        let pad_size = self.rules.dimensions.pad_size;
         let row_count = nets.finger_rows().len();
+        let home_row_index = nets.home_row_index();
 
-        for (switches, nets, above, sign) in [
-            (&self.switches_below, nets.lower_finger_rows(), false, -1f32),
-            (&self.switches_above, nets.upper_finger_rows(), true, 1f32),
+        for (switches, nets, routed_row_count, above, sign) in [
+            (
+                &self.switches_below,
+                nets.lower_finger_rows(),
+                home_row_index - rows.start,
+                false,
+                -1f32,
+            ),
+            (
+                &self.switches_above,
+                nets.upper_finger_rows(),
+                rows.end - home_row_index - 1,
+                true,
+                1f32,
+            ),
         ] {
-            let row_pad_attachment_point = if above { BELOW_ROW_PAD } else { ABOVE_ROW_PAD };
+            let routing = self.rules.routing;
+            let row_pad_attachment_point = if above {
+                routing.below_row_pad
+            } else {
+                routing.above_row_pad
+            };
 
-            if let Some(&first) = switches.first() {
+            if routed_row_count > 0 {
                 let connector_track_offset =
-                    sign * centered_track_offset(switches.len() - 1, row_count);
+                    sign * self.rules.centered_track_offset(nets.len() - 1, row_count);
                 let left_column_connector_path =
                     left_column_connector.column_track(true, above, connector_track_offset);
                 let right_column_connector_path = right_column_connector
                     .column_track(false, above, connector_track_offset)
                     .reverse();
 
-                for (i, (window, net)) in once([self.home_switch, first].as_slice())
-                    .chain(switches.windows(2))
+                for (i, (&first_switch, net)) in once(&self.home_switch)
+                    .chain(switches)
                     .zip(nets)
+                    .take(routed_row_count)
                     .enumerate()
                 {
-                    let first_switch = window[0];
-                    let second_switch = window[1];
-
                     let (column_connector_path, track_attachment_point) = if i > 0 {
-                        let offset = sign * track_offset(i);
-                        let double_chamfer = double_chamfer(i, above);
+                        let offset = sign * self.rules.track_offset(i);
+                        let double_chamfer = double_chamfer(i, above, self.rules);
 
                         let column_connector_path = left_column_connector_path
                             .offset(offset)
@@ -242,22 +346,37 @@ impl Column {
                             above,
                         )
                     };
-                    pcb.add_track(&column_connector_path, BOTTOM_LAYER, net);
-
-                    let row_path = Path::angled_end(
-                        track_attachment_point,
-                        point!(x_offset(0), -f64::from(sign) * PAD_SIZE.y / 2.0),
-                    )
-                    .at(first_switch)
-                    .join(
-                        &Path::angled_start(
-                            point!(x_offset(0), f64::from(sign) * PAD_SIZE.y / 2.0),
-                            row_pad_attachment_point,
-                        )
-                        .append(ROW_PAD)
-                        .at(second_switch),
+                    pcb.add_track(
+                        &column_connector_path,
+                        self.rules.track_width,
+                        BOTTOM_LAYER,
+                        net,
                     );
-                    pcb.add_track(&row_path, BOTTOM_LAYER, net);
+
+                    // Rows only passing through to the neighboring columns end here
+                    let Some(&second_switch) = switches.get(i) else {
+                        continue;
+                    };
+                    let row_path = routing
+                        .channel_path(
+                            point!(self.rules.x_offset(0), -f64::from(sign) * pad_size.y / 2.0),
+                            track_attachment_point,
+                        )
+                        .reverse()
+                        .at(first_switch)
+                        .join(
+                            &routing
+                                .channel_path(
+                                    point!(
+                                        self.rules.x_offset(0),
+                                        f64::from(sign) * pad_size.y / 2.0
+                                    ),
+                                    row_pad_attachment_point,
+                                )
+                                .append(ROW_PAD)
+                                .at(second_switch),
+                        );
+                    pcb.add_track(&row_path, self.rules.track_width, BOTTOM_LAYER, net);
                 }
             }
         }
@@ -272,6 +391,7 @@ impl Column {
         right: bool,
     ) {
         const EDGE_DISTANCE: f64 = 2.0;
+        let pad_size = self.rules.dimensions.pad_size;
 
         let (attachment_side, sign_x) = if right {
             (column_connector.start_attachment_side(), -1f64)
@@ -279,16 +399,16 @@ impl Column {
             (column_connector.end_attachment_side(), 1f64)
         };
 
-        let track_x_offset = sign_x * x_offset(0);
+        let track_x_offset = sign_x * self.rules.x_offset(0);
         let row_count = nets.finger_rows().len();
 
         let (y_offset_below, y_offset_above) = if matches!(attachment_side, AttachmentSide::Center)
         {
-            (ROW_PAD.y(), UPPER_COLUMN_PAD.y())
+            (ROW_PAD.y(), self.rules.routing.outer_row_y_offset)
         } else {
             (
-                (PAD_SIZE.y / 2.0 + EDGE_DISTANCE).into(),
-                (-PAD_SIZE.y / 2.0 - EDGE_DISTANCE).into(),
+                (pad_size.y / 2.0 + EDGE_DISTANCE).into(),
+                (-pad_size.y / 2.0 - EDGE_DISTANCE).into(),
             )
         };
         let (offsets_below, offsets_above) = self.offsets.split_at(self.switches_below.len());
@@ -316,10 +436,13 @@ impl Column {
                 let start_path = column_connector.column_track(
                     right,
                     above,
-                    sign_y * centered_track_offset(switches.len() - 1, row_count),
+                    sign_y
+                        * self
+                            .rules
+                            .centered_track_offset(row_nets.len() - 1, row_count),
                 );
 
-                for (i, net) in row_nets.iter().enumerate() {
+                for (i, net) in row_nets.iter().take(switches.len()).enumerate() {
                     let path = once(&self.home_switch)
                         .chain(switches)
                         .zip(offsets)
@@ -327,19 +450,19 @@ impl Column {
                         .map(|(&switch, &offset)| {
                             Path::angled_end_center(
                                 point!(track_x_offset, y_offset),
-                                point!(track_x_offset + offset, sign_y * -PAD_SIZE.y / 2.0),
+                                point!(track_x_offset + offset, sign_y * -pad_size.y / 2.0),
                             )
                             .append(point!(
                                 track_x_offset + offset,
-                                sign_y * (-PAD_SIZE.y / 2.0 - 1.0)
+                                sign_y * (-pad_size.y / 2.0 - 1.0)
                             ))
                             .at(switch)
                         })
                         .fold(start_path.clone(), |path, other| path.join(&other))
-                        .offset(sign_x * sign_y * -track_offset(i))
-                        .join(&row_path(i, right, above).at(switches[i]));
+                        .offset(sign_x * sign_y * -self.rules.track_offset(i))
+                        .join(&row_path(i, right, above, self.rules).at(switches[i]));
 
-                    pcb.add_track(&path, BOTTOM_LAYER, net);
+                    pcb.add_track(&path, self.rules.track_width, BOTTOM_LAYER, net);
                 }
             }
         }
@@ -352,27 +475,30 @@ impl Column {
         left_connector_position: Option<Position>,
         right_connector_position: Option<Position>,
     ) {
+        let pad_size = self.rules.dimensions.pad_size;
+        let connector_width = self.rules.dimensions.connector_width;
+
         for (position, x_offset) in [
-            (left_connector_position, -PAD_SIZE.x / 2.0),
-            (right_connector_position, PAD_SIZE.x / 2.0),
+            (left_connector_position, -pad_size.x / 2.0),
+            (right_connector_position, pad_size.x / 2.0),
         ] {
             if let Some(position) = position {
                 let lower_outline_points = [
-                    self.home_switch + point!(x_offset, PAD_SIZE.y / 2.0),
-                    position + point!(0, CONNECTOR_WIDTH / 2.0),
+                    self.home_switch + point!(x_offset, pad_size.y / 2.0),
+                    position + point!(0, connector_width / 2.0),
                 ];
                 let upper_outline_points = [
-                    position + point!(0, -CONNECTOR_WIDTH / 2.0),
-                    self.home_switch + point!(x_offset, -PAD_SIZE.y / 2.0),
+                    position + point!(0, -connector_width / 2.0),
+                    self.home_switch + point!(x_offset, -pad_size.y / 2.0),
                 ];
-                pcb.add_outline_path(&lower_outline_points);
-                pcb.add_outline_path(&upper_outline_points);
+                pcb.add_outline_path(&lower_outline_points, self.rules.outline_width);
+                pcb.add_outline_path(&upper_outline_points, self.rules.outline_width);
             } else {
                 let outline_points = [
-                    self.home_switch + point!(x_offset, PAD_SIZE.y / 2.0),
-                    self.home_switch + point!(x_offset, -PAD_SIZE.y / 2.0),
+                    self.home_switch + point!(x_offset, pad_size.y / 2.0),
+                    self.home_switch + point!(x_offset, -pad_size.y / 2.0),
                 ];
-                pcb.add_outline_path(&outline_points);
+                pcb.add_outline_path(&outline_points, self.rules.outline_width);
             }
         }
     }
@@ -384,83 +510,94 @@ fn add_connector_outline(
     bottom_switch: Position,
     top_switch: Position,
     offset: Length,
+    rules: Rules,
 ) {
+    let pad_size = rules.dimensions.pad_size;
+    let connector_width = rules.dimensions.connector_width;
     let offset = f64::from(offset);
     let negative_offset = offset.min(0.0);
     let positive_offset = offset.max(0.0);
 
     let left_outline_points = [
-        bottom_switch + point!(-PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-        bottom_switch + point!(-PAD_SIZE.x / 2.0 + positive_offset, -PAD_SIZE.y / 2.0),
-        top_switch + point!(-PAD_SIZE.x / 2.0 - negative_offset, PAD_SIZE.y / 2.0),
-        top_switch + point!(-PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
+        bottom_switch + point!(-pad_size.x / 2.0, -pad_size.y / 2.0),
+        bottom_switch + point!(-pad_size.x / 2.0 + positive_offset, -pad_size.y / 2.0),
+        top_switch + point!(-pad_size.x / 2.0 - negative_offset, pad_size.y / 2.0),
+        top_switch + point!(-pad_size.x / 2.0, pad_size.y / 2.0),
     ];
     let right_outline_points = [
-        bottom_switch + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-        bottom_switch + point!(PAD_SIZE.x / 2.0 + negative_offset, -PAD_SIZE.y / 2.0),
-        top_switch + point!(PAD_SIZE.x / 2.0 - positive_offset, PAD_SIZE.y / 2.0),
-        top_switch + point!(PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
+        bottom_switch + point!(pad_size.x / 2.0, -pad_size.y / 2.0),
+        bottom_switch + point!(pad_size.x / 2.0 + negative_offset, -pad_size.y / 2.0),
+        top_switch + point!(pad_size.x / 2.0 - positive_offset, pad_size.y / 2.0),
+        top_switch + point!(pad_size.x / 2.0, pad_size.y / 2.0),
     ];
-    pcb.add_outline_path(&left_outline_points);
-    pcb.add_outline_path(&right_outline_points);
+    pcb.add_outline_path(&left_outline_points, rules.outline_width);
+    pcb.add_outline_path(&right_outline_points, rules.outline_width);
 
-    if offset.abs() < PAD_SIZE.x - 2.0 * CONNECTOR_WIDTH - ROUTER_BIT_DIAMETER {
+    if offset.abs() < pad_size.x - 2.0 * connector_width - rules.dimensions.router_bit_diameter {
         let cutout_points = [
             bottom_switch
                 + point!(
-                    -PAD_SIZE.x / 2.0 + CONNECTOR_WIDTH + positive_offset,
-                    -PAD_SIZE.y / 2.0
+                    -pad_size.x / 2.0 + connector_width + positive_offset,
+                    -pad_size.y / 2.0
                 ),
             bottom_switch
                 + point!(
-                    PAD_SIZE.x / 2.0 - CONNECTOR_WIDTH + negative_offset,
-                    -PAD_SIZE.y / 2.0
+                    pad_size.x / 2.0 - connector_width + negative_offset,
+                    -pad_size.y / 2.0
                 ),
             top_switch
                 + point!(
-                    PAD_SIZE.x / 2.0 - CONNECTOR_WIDTH - positive_offset,
-                    PAD_SIZE.y / 2.0
+                    pad_size.x / 2.0 - connector_width - positive_offset,
+                    pad_size.y / 2.0
                 ),
             top_switch
                 + point!(
-                    -PAD_SIZE.x / 2.0 + CONNECTOR_WIDTH - negative_offset,
-                    PAD_SIZE.y / 2.0
+                    -pad_size.x / 2.0 + connector_width - negative_offset,
+                    pad_size.y / 2.0
                 ),
         ];
-        pcb.add_outline_polygon(&cutout_points);
+        pcb.add_outline_polygon(&cutout_points, rules.outline_width);
     }
 }
 
 /// Adds the outline of the bottom most switch to the given position to the PCB.
-fn add_bottom_switch_outline(pcb: &mut KicadPcb, position: Position) {
+fn add_bottom_switch_outline(pcb: &mut KicadPcb, position: Position, rules: Rules) {
+    let pad_size = rules.dimensions.pad_size;
     let outline_points = [
-        position + point!(-PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-        position + point!(-PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
-        position + point!(PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
-        position + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
+        position + point!(-pad_size.x / 2.0, -pad_size.y / 2.0),
+        position + point!(-pad_size.x / 2.0, pad_size.y / 2.0),
+        position + point!(pad_size.x / 2.0, pad_size.y / 2.0),
+        position + point!(pad_size.x / 2.0, -pad_size.y / 2.0),
     ];
 
-    pcb.add_outline_path(&outline_points);
+    pcb.add_outline_path(&outline_points, rules.outline_width);
 }
 
 /// Adds the outline of a single pad at the given position to the PCB.
-fn add_pad_outline(pcb: &mut KicadPcb, position: Position) {
-    for x_offset in [-PAD_SIZE.x / 2.0, PAD_SIZE.x / 2.0] {
+fn add_pad_outline(pcb: &mut KicadPcb, position: Position, rules: Rules) {
+    let pad_size = rules.dimensions.pad_size;
+    for x_offset in [-pad_size.x / 2.0, pad_size.x / 2.0] {
         let outline_points = [
-            position + point!(x_offset, PAD_SIZE.y / 2.0),
-            position + point!(x_offset, -PAD_SIZE.y / 2.0),
+            position + point!(x_offset, pad_size.y / 2.0),
+            position + point!(x_offset, -pad_size.y / 2.0),
         ];
 
-        pcb.add_outline_path(&outline_points);
+        pcb.add_outline_path(&outline_points, rules.outline_width);
     }
 }
 
 /// Creates a double chamfer for the given index and side.
-fn double_chamfer(index: usize, above: bool) -> Path {
+fn double_chamfer(index: usize, above: bool, rules: Rules) -> Path {
     const CHAMFER_DEPTH: Length = Length::new(3.0);
 
-    let x_offset = x_offset(index);
-    let y_offset = if above { ABOVE_ROW_PAD } else { BELOW_ROW_PAD }.y();
+    let x_offset = rules.x_offset(index);
+    let routing = rules.routing;
+    let y_offset = if above {
+        routing.above_row_pad
+    } else {
+        routing.below_row_pad
+    }
+    .y();
     let sign = if above { 1 } else { -1 };
 
     Path::new([
@@ -472,17 +609,29 @@ fn double_chamfer(index: usize, above: bool) -> Path {
 }
 
 /// Returns the row path with the given index, going to the respective sides.
-fn row_path(index: usize, right: bool, above: bool) -> Path {
+fn row_path(index: usize, right: bool, above: bool, rules: Rules) -> Path {
+    let pad_size = rules.dimensions.pad_size;
+    let routing = rules.routing;
+    let x_offset = rules.x_offset(index);
+
     if right {
         if above {
-            Path::angled_center(point!(-x_offset(index), PAD_SIZE.y / 2.0), LEFT_OF_ROW_PAD)
+            Path::angled_center(point!(-x_offset, pad_size.y / 2.0), routing.left_of_row_pad)
         } else {
-            Path::angled_start(point!(-x_offset(index), -PAD_SIZE.y / 2.0), LEFT_OF_ROW_PAD)
+            routing
+                .channel_path(
+                    point!(-x_offset, -pad_size.y / 2.0),
+                    routing.below_internal_pad,
+                )
+                .join(&Path::angled_start(
+                    routing.below_internal_pad,
+                    routing.left_of_row_pad,
+                ))
         }
     } else if above {
-        Path::angled_start_center(point!(x_offset(index), PAD_SIZE.y / 2.0), BELOW_ROW_PAD)
+        Path::angled_start_center(point!(x_offset, pad_size.y / 2.0), routing.below_row_pad)
     } else {
-        Path::angled_start(point!(x_offset(index), -PAD_SIZE.y / 2.0), ABOVE_ROW_PAD)
+        routing.channel_path(point!(x_offset, -pad_size.y / 2.0), routing.above_row_pad)
     }
     .append(ROW_PAD)
 }
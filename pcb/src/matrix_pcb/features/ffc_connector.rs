@@ -1,11 +1,10 @@
-use model::matrix_pcb::{CONNECTOR_WIDTH, FFC_PAD_OFFSET, FFC_PAD_SIZE, PAD_SIZE};
+use model::matrix_pcb::{FFC_PAD_OFFSET, FFC_PAD_SIZE};
 
 use crate::{
-    footprints::{LEFT_OF_ROW_PAD, LOWER_COLUMN_PAD, ROW_PAD, UPPER_COLUMN_PAD},
+    footprints::ROW_PAD,
     kicad_pcb::{KicadPcb, Net},
     matrix_pcb::{
-        centered_track_offset, connector::Connector, features::Column, nets::Nets, track_offset,
-        x_offset, AddPath, BOTTOM_LAYER, TOP_LAYER,
+        connector::Connector, features::Column, nets::Nets, AddPath, Rules, BOTTOM_LAYER, TOP_LAYER,
     },
     path::Path,
     point, position,
@@ -16,16 +15,16 @@ use crate::{
 /// An FFC connector.
 pub struct FfcConnector {
     anchor: Position,
+    rules: Rules,
 }
 
 impl FfcConnector {
-    const Y_OFFSET: Length = Length::new(5.5);
     const PITCH: Length = Length::new(1.0);
-    const PAD_OFFSET: Length = Length::new(0.6);
+    const PAD_OFFSET: Length = Length::new(0.5);
 
-    /// Creates a new FFC connector from the given anchor switch position.
-    pub fn from_anchor(anchor: Position) -> Self {
-        Self { anchor }
+    /// Creates a new FFC connector following the given rules from the anchor switch position.
+    pub fn from_anchor(anchor: Position, rules: Rules) -> Self {
+        Self { anchor, rules }
     }
 
     /// Calculates the start of the cluster connector.
@@ -35,59 +34,76 @@ impl FfcConnector {
 
     /// Returns the position of the FFC connector.
     pub fn position(&self) -> Position {
-        self.anchor + position!(0, Self::Y_OFFSET, None)
+        self.anchor + position!(0, self.y_offset(), None)
     }
 
     /// Adds the outline of the FFC connector to the PCB.
     pub fn add_outline(&self, pcb: &mut KicadPcb) {
+        let pad_size = self.rules.dimensions.pad_size;
+        let connector_width = self.rules.dimensions.connector_width;
+
         for sign in [-1.0, 1.0] {
             let pad_top_offset = FFC_PAD_OFFSET - FFC_PAD_SIZE.y / 2.0;
             let pad_bottom_offset = FFC_PAD_OFFSET + FFC_PAD_SIZE.y / 2.0;
 
             let outline_points = [
-                self.anchor + point!(sign * CONNECTOR_WIDTH / 2.0, pad_bottom_offset),
+                self.anchor + point!(sign * connector_width / 2.0, pad_bottom_offset),
                 self.anchor + point!(sign * FFC_PAD_SIZE.x / 2.0, pad_bottom_offset),
                 self.anchor + point!(sign * FFC_PAD_SIZE.x / 2.0, pad_top_offset),
-                self.anchor + point!(sign * PAD_SIZE.x / 2.0, pad_top_offset),
-                self.anchor + point!(sign * PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
+                self.anchor + point!(sign * pad_size.x / 2.0, pad_top_offset),
+                self.anchor + point!(sign * pad_size.x / 2.0, -pad_size.y / 2.0),
             ];
 
-            pcb.add_outline_path(&outline_points);
+            pcb.add_outline_path(&outline_points, self.rules.outline_width);
         }
     }
 
     /// Adds the tracks between the rows and FFC connector to the PCB.
-    pub fn add_row_tracks(&self, pcb: &mut KicadPcb, row_nets: &[Net], ffc_column: &Column) {
+    ///
+    /// The row nets start at the given index of the first row of the FFC column.
+    pub fn add_row_tracks(
+        &self,
+        pcb: &mut KicadPcb,
+        row_nets: &[Net],
+        first_row_index: usize,
+        ffc_column: &Column,
+    ) {
+        let pad_size = self.rules.dimensions.pad_size;
         let (first_row_net, row_nets) = row_nets.split_first().expect("there is at least one row");
-        let first_pad_x_offset = Self::pad_x_offset(5);
+        let first_pad_x_offset = Self::pad_x_offset(5 - first_row_index);
         let first_row_path = Path::angled_start(
             ROW_PAD,
-            point!(first_pad_x_offset, Self::Y_OFFSET - Self::PAD_OFFSET),
+            point!(first_pad_x_offset, self.y_offset() - Self::PAD_OFFSET),
         )
-        .append(point!(first_pad_x_offset, Self::Y_OFFSET))
+        .append(point!(first_pad_x_offset, self.y_offset()))
         .at(self.anchor);
-        pcb.add_track(&first_row_path, BOTTOM_LAYER, first_row_net);
+        pcb.add_track(
+            &first_row_path,
+            self.rules.track_width,
+            BOTTOM_LAYER,
+            first_row_net,
+        );
 
         for (i, (&position, net)) in ffc_column.positions().skip(1).zip(row_nets).enumerate() {
-            let x_offset = -x_offset(i);
-            let pad_x_offset = Self::pad_x_offset(4 - i);
+            let x_offset = -self.rules.x_offset(i);
+            let pad_x_offset = Self::pad_x_offset(4 - first_row_index - i);
 
             let path = Path::new([ROW_PAD])
                 .join(&Path::angled_center(
-                    LEFT_OF_ROW_PAD,
-                    point!(x_offset, PAD_SIZE.y / 2.0),
+                    self.rules.routing.left_of_row_pad,
+                    point!(x_offset, pad_size.y / 2.0),
                 ))
                 .at(position)
                 .join(
                     &Path::angled_start(
-                        point!(x_offset, -PAD_SIZE.y / 2.0),
-                        point!(pad_x_offset, Self::Y_OFFSET - Self::PAD_OFFSET),
+                        point!(x_offset, -pad_size.y / 2.0),
+                        point!(pad_x_offset, self.y_offset() - Self::PAD_OFFSET),
                     )
-                    .append(point!(pad_x_offset, Self::Y_OFFSET))
+                    .append(point!(pad_x_offset, self.y_offset()))
                     .at(self.anchor),
                 );
 
-            pcb.add_track(&path, TOP_LAYER, net);
+            pcb.add_track(&path, self.rules.track_width, TOP_LAYER, net);
         }
     }
 
@@ -141,34 +157,39 @@ impl FfcConnector {
 
         let row_pad_x_offset = Self::pad_x_offset(11);
         let row_path = Path::angled_center(
-            point!(centered_track_offset(1, 2), pad_bottom_offset),
-            point!(row_pad_x_offset, Self::Y_OFFSET + Self::PAD_OFFSET),
+            point!(self.rules.centered_track_offset(1, 2), pad_bottom_offset),
+            point!(row_pad_x_offset, self.y_offset() + Self::PAD_OFFSET),
         )
-        .append(point!(row_pad_x_offset, Self::Y_OFFSET))
+        .append(point!(row_pad_x_offset, self.y_offset()))
         .at(self.anchor);
-        pcb.add_track(&row_path, TOP_LAYER, row_net);
+        pcb.add_track(&row_path, self.rules.track_width, TOP_LAYER, row_net);
 
         let first_pad_x_offset = Self::pad_x_offset(0);
         let first_column_path = Path::angled_center(
-            point!(centered_track_offset(0, 2), pad_bottom_offset),
-            point!(first_pad_x_offset, Self::Y_OFFSET + Self::PAD_OFFSET),
+            point!(self.rules.centered_track_offset(0, 2), pad_bottom_offset),
+            point!(first_pad_x_offset, self.y_offset() + Self::PAD_OFFSET),
         )
-        .append(point!(first_pad_x_offset, Self::Y_OFFSET))
+        .append(point!(first_pad_x_offset, self.y_offset()))
         .at(self.anchor);
-        pcb.add_track(&first_column_path, TOP_LAYER, first_column_net);
+        pcb.add_track(
+            &first_column_path,
+            self.rules.track_width,
+            TOP_LAYER,
+            first_column_net,
+        );
 
         for (i, column_net) in column_nets.iter().enumerate() {
             let pad_x_offset = Self::pad_x_offset(i + 6);
             let path = Path::angled_start_center(
                 point!(
-                    centered_track_offset(i, thumb_switch_count - 1),
+                    self.rules.centered_track_offset(i, thumb_switch_count - 1),
                     pad_bottom_offset
                 ),
-                point!(pad_x_offset, Self::Y_OFFSET + Self::PAD_OFFSET),
+                point!(pad_x_offset, self.y_offset() + Self::PAD_OFFSET),
             )
-            .append(point!(pad_x_offset, Self::Y_OFFSET))
+            .append(point!(pad_x_offset, self.y_offset()))
             .at(self.anchor);
-            pcb.add_track(&path, BOTTOM_LAYER, column_net);
+            pcb.add_track(&path, self.rules.track_width, BOTTOM_LAYER, column_net);
         }
     }
 
@@ -180,8 +201,9 @@ impl FfcConnector {
         net: &Net,
         row_count: usize,
     ) {
-        let x_offset = -x_offset(row_count - 1);
-        let connector_offset = Length::from(PAD_SIZE.x / 2.0) + x_offset;
+        let pad_size = self.rules.dimensions.pad_size;
+        let x_offset = -self.rules.x_offset(row_count - 1);
+        let connector_offset = Length::from(pad_size.x / 2.0) + x_offset;
         let pad_x_offset = Self::pad_x_offset(0);
 
         let track_path = Path::chamfered(
@@ -193,34 +215,41 @@ impl FfcConnector {
         .at(column_connector.end_position())
         .join(
             &Path::angled_start(
-                point!(x_offset, -PAD_SIZE.y / 2.0),
-                point!(pad_x_offset, Self::Y_OFFSET - Self::PAD_OFFSET),
+                point!(x_offset, -pad_size.y / 2.0),
+                point!(pad_x_offset, self.y_offset() - Self::PAD_OFFSET),
             )
-            .append(point!(pad_x_offset, Self::Y_OFFSET))
+            .append(point!(pad_x_offset, self.y_offset()))
             .at(self.anchor),
         );
-        pcb.add_track(&track_path, TOP_LAYER, net);
+        pcb.add_track(&track_path, self.rules.track_width, TOP_LAYER, net);
     }
 
     /// Adds the track connecting the center column and FFC connector.
     fn add_center_column_track(&self, pcb: &mut KicadPcb, net: &Net, is_first_column: bool) {
         if is_first_column {
-            let track_path = Path::chamfered(
-                UPPER_COLUMN_PAD,
-                point!(Self::pad_x_offset(0), Self::Y_OFFSET),
-                6.6.into(),
-                true,
-            )
-            .at(self.anchor);
+            let track_path = self
+                .rules
+                .routing
+                .ffc_column_path(point!(Self::pad_x_offset(0), self.y_offset()))
+                .at(self.anchor);
 
-            pcb.add_track(&track_path, BOTTOM_LAYER, net);
+            pcb.add_track(&track_path, self.rules.track_width, BOTTOM_LAYER, net);
         } else {
-            let x_offset = Self::pad_x_offset(6);
-            let track_path = Path::angled_start(LOWER_COLUMN_PAD, point!(x_offset, 3))
-                .append(point!(x_offset, Self::Y_OFFSET))
+            let routing = self.rules.routing;
+            let track_path = routing
+                .ffc_column_points
+                .iter()
+                .fold(Path::new([routing.lower_column_pad]), |path, &point| {
+                    let last = *path
+                        .last()
+                        .expect("the path starts at the lower column pad");
+
+                    path.join(&Path::angled_start(last, point))
+                })
+                .append(point!(Self::pad_x_offset(6), self.y_offset()))
                 .at(self.anchor);
 
-            pcb.add_track(&track_path, TOP_LAYER, net);
+            pcb.add_track(&track_path, self.rules.track_width, TOP_LAYER, net);
         }
     }
 
@@ -232,30 +261,57 @@ impl FfcConnector {
         column_nets: &[Net],
         contains_second_column: bool,
     ) {
+        let pad_size = self.rules.dimensions.pad_size;
         for (i, net) in column_nets.iter().enumerate() {
             let pad_x_offset = Self::pad_x_offset(i + 6 + usize::from(!contains_second_column));
-            let pad_y_offset = if contains_second_column && i == 0 {
-                3.into()
+            let start = point!(self.rules.x_offset(i), -pad_size.y / 2.0);
+            let end = point!(pad_x_offset, self.y_offset());
+            let pad_path = if contains_second_column && i == 0 {
+                let [first_point, second_point] = self.rules.routing.ffc_second_column_points;
+
+                Path::angled_start(start, first_point)
+                    .join(&Path::angled_start(first_point, second_point))
+                    .join(&Path::angled_start(second_point, end))
+            } else if let Some([first_point, second_point]) =
+                self.rules.routing.ffc_right_column_points
+            {
+                let innermost_start = point!(self.rules.x_offset(0), -pad_size.y / 2.0);
+                let channel_path = Path::angled_start(innermost_start, first_point)
+                    .append(second_point)
+                    .offset(self.rules.track_offset(i));
+                let bend = *channel_path
+                    .last()
+                    .expect("offset path should always have points");
+
+                channel_path
+                    .join(&Path::angled_start(
+                        bend,
+                        point!(pad_x_offset, end.y() - Self::PAD_OFFSET),
+                    ))
+                    .append(end)
             } else {
-                Self::Y_OFFSET - Self::PAD_OFFSET
+                Path::angled_start(start, point!(pad_x_offset, end.y() - Self::PAD_OFFSET))
+                    .append(end)
             };
 
             let path = column_connector
-                .column_track(false, false, centered_track_offset(0, column_nets.len()))
-                .offset(track_offset(i))
-                .join(
-                    &Path::angled_start(
-                        point!(x_offset(i), -PAD_SIZE.y / 2.0),
-                        point!(pad_x_offset, pad_y_offset),
-                    )
-                    .append(point!(pad_x_offset, Self::Y_OFFSET))
-                    .at(self.anchor),
-                );
+                .column_track(
+                    false,
+                    false,
+                    self.rules.centered_track_offset(0, column_nets.len()),
+                )
+                .offset(self.rules.track_offset(i))
+                .join(&pad_path.at(self.anchor));
 
-            pcb.add_track(&path, TOP_LAYER, net);
+            pcb.add_track(&path, self.rules.track_width, TOP_LAYER, net);
         }
     }
 
+    /// Returns the offset in the Y direction of the FFC connector.
+    fn y_offset(&self) -> Length {
+        self.rules.routing.ffc_connector_y_offset
+    }
+
     /// Returns the offset in the X direction of the connector pad with the given index.
     #[allow(clippy::cast_precision_loss)]
     fn pad_x_offset(index: usize) -> Length {
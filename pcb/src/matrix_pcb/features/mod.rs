@@ -6,7 +6,7 @@ use std::iter::once;
 
 use model::matrix_pcb::MatrixPcb as Model;
 
-use crate::matrix_pcb::{connector::Connector, ORIGIN_POSITION};
+use crate::matrix_pcb::{connector::Connector, Rules, ORIGIN_POSITION};
 
 pub use column::Column;
 pub use ffc_connector::FfcConnector;
@@ -27,19 +27,16 @@ pub struct Features {
 }
 
 impl Features {
-    /// Calculates all features of the matrix PCB from the model.
-    pub fn from_model(
-        model: &Model,
-        home_row_index: usize,
-        cluster_connector_index: usize,
-    ) -> Self {
+    /// Calculates all features of the matrix PCB following the given rules from the model.
+    pub fn from_model(model: &Model, rules: Rules, cluster_connector_index: usize) -> Self {
         let mut switch_position = ORIGIN_POSITION;
 
         let column_connectors: Vec<_> = model
             .column_connectors
             .iter()
             .map(|column_connector| {
-                let connector = Connector::from_column_connector(column_connector, switch_position);
+                let connector =
+                    Connector::from_column_connector(column_connector, switch_position, rules);
                 switch_position = connector.end_switch_position();
                 connector
             })
@@ -49,20 +46,23 @@ impl Features {
             .chain(column_connectors.iter().map(Connector::end_switch_position))
             .zip(&model.column_key_connectors)
             .map(|(position, column_key_connectors)| {
-                Column::from_key_connectors(column_key_connectors, position, home_row_index)
+                Column::from_key_connectors(column_key_connectors, position, rules)
             })
             .collect();
 
-        let ffc_connector = FfcConnector::from_anchor(columns[cluster_connector_index].first());
+        let ffc_connector =
+            FfcConnector::from_anchor(columns[cluster_connector_index].first(), rules);
         let cluster_connector = Connector::from_cluster_connector(
             &model.cluster_connector,
             ffc_connector.cluster_connector_start(),
             columns[cluster_connector_index].first(),
+            rules,
         );
 
         let thumb_switches = ThumbSwitches::from_key_connectors(
             &model.thumb_key_connectors,
             cluster_connector.end_switch_position(),
+            rules,
         );
 
         Self {
@@ -1,12 +1,11 @@
-use model::matrix_pcb::{Segment, ThumbKeyConnectors, CONNECTOR_WIDTH, PAD_SIZE};
+use std::iter::once;
+
+use model::matrix_pcb::{Segment, ThumbKeyConnectors};
 
 use crate::{
-    footprints::{ABOVE_ROW_PAD, BELOW_ROW_PAD, LEFT_OF_ROW_PAD, ROW_PAD, UPPER_COLUMN_PAD},
+    footprints::ROW_PAD,
     kicad_pcb::{KicadPcb, Net},
-    matrix_pcb::{
-        centered_track_offset, nets::Nets, track_offset, AddPath, BOTTOM_LAYER, TOP_LAYER,
-        TRACK_WIDTH,
-    },
+    matrix_pcb::{nets::Nets, AddPath, Rules, BOTTOM_LAYER, TOP_LAYER},
     path::Path,
     point, position,
     primitives::Position,
@@ -14,34 +13,40 @@ use crate::{
 };
 
 /// The positions of the thumb switches.
-pub struct ThumbSwitches(Vec<Position>);
+pub struct ThumbSwitches {
+    positions: Vec<Position>,
+    rules: Rules,
+}
 
 impl ThumbSwitches {
-    const X_OFFSET: Length = Length::new((PAD_SIZE.x - CONNECTOR_WIDTH) / 2.0);
-
-    /// Creates a new set of thumb switches from the corresponding key connectors and position of the first one.
+    /// Creates a new set of thumb switches following the given rules from the corresponding key
+    /// connectors and position of the first one.
     pub fn from_key_connectors(
         key_connectors: &ThumbKeyConnectors,
         first_switch: Position,
+        rules: Rules,
     ) -> Self {
-        let switch_distance = key_connectors.connector.length() + PAD_SIZE.x;
-
-        #[allow(clippy::cast_precision_loss)]
-        let positions = (0..=key_connectors.positions.len())
-            .map(|i| first_switch + position!(i as f64 * switch_distance, 0, None))
+        let pad_size = rules.dimensions.pad_size;
+        let mut position = first_switch;
+        let positions = once(first_switch)
+            .chain(key_connectors.connectors.iter().map(|connector| {
+                position += position!(connector.length() + pad_size.x, 0, None);
+
+                position
+            }))
             .collect();
 
-        Self(positions)
+        Self { positions, rules }
     }
 
     /// Returns the positions of the thumb switch.
     pub fn positions(&self) -> &[Position] {
-        &self.0
+        &self.positions
     }
 
     /// Returns the position of the first thumb switch.
     pub fn first(&self) -> Position {
-        self.0
+        self.positions
             .first()
             .copied()
             .expect("there is always at least one thumb switch")
@@ -49,7 +54,7 @@ impl ThumbSwitches {
 
     /// Returns the position of the last thumb switch.
     pub fn last(&self) -> Position {
-        self.0
+        self.positions
             .last()
             .copied()
             .expect("there is always at least one thumb switch")
@@ -57,119 +62,155 @@ impl ThumbSwitches {
 
     /// Adds the outline for the thumb switches to the PCB.
     pub fn add_outline(&self, pcb: &mut KicadPcb) {
+        let pad_size = self.rules.dimensions.pad_size;
+        let connector_width = self.rules.dimensions.connector_width;
         let first = self.first();
         let last = self.last();
 
         let outline_points = [
-            first + point!(PAD_SIZE.x / 2.0 - CONNECTOR_WIDTH, -PAD_SIZE.y / 2.0),
-            first + point!(-PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-            first + point!(-PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
-            last + point!(PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0),
-            last + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
-            first + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0),
+            first + point!(pad_size.x / 2.0 - connector_width, -pad_size.y / 2.0),
+            first + point!(-pad_size.x / 2.0, -pad_size.y / 2.0),
+            first + point!(-pad_size.x / 2.0, pad_size.y / 2.0),
+            last + point!(pad_size.x / 2.0, pad_size.y / 2.0),
+            last + point!(pad_size.x / 2.0, -pad_size.y / 2.0),
+            first + point!(pad_size.x / 2.0, -pad_size.y / 2.0),
         ];
-        pcb.add_outline_path(&outline_points);
+        pcb.add_outline_path(&outline_points, self.rules.outline_width);
 
-        for window in self.0.windows(2) {
+        for window in self.positions.windows(2) {
             let position = window[0];
             let next_position = window[1];
 
             let cutout_points = [
-                position + point!(PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0 - CONNECTOR_WIDTH),
-                position + point!(PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0 + CONNECTOR_WIDTH),
-                next_position + point!(-PAD_SIZE.x / 2.0, -PAD_SIZE.y / 2.0 + CONNECTOR_WIDTH),
-                next_position + point!(-PAD_SIZE.x / 2.0, PAD_SIZE.y / 2.0 - CONNECTOR_WIDTH),
+                position + point!(pad_size.x / 2.0, pad_size.y / 2.0 - connector_width),
+                position + point!(pad_size.x / 2.0, -pad_size.y / 2.0 + connector_width),
+                next_position + point!(-pad_size.x / 2.0, -pad_size.y / 2.0 + connector_width),
+                next_position + point!(-pad_size.x / 2.0, pad_size.y / 2.0 - connector_width),
             ];
-            pcb.add_outline_polygon(&cutout_points);
+            pcb.add_outline_polygon(&cutout_points, self.rules.outline_width);
         }
     }
 
     /// Adds the tracks for the thumb switches to the PCB.
     pub fn add_tracks(&self, pcb: &mut KicadPcb, nets: &Nets) {
         self.add_row_track(pcb, nets.thumb_row());
-        self.add_column_tracks(pcb, nets.columns());
+        self.add_column_tracks(pcb, nets.thumb_columns());
     }
 
     /// Adds the track connecting the row of the thumb switches.
     fn add_row_track(&self, pcb: &mut KicadPcb, row_net: &Net) {
-        const Y_OFFSET: Length = Length::new((PAD_SIZE.y - CONNECTOR_WIDTH) / 2.0);
+        let pad_size = self.rules.dimensions.pad_size;
+        let routing = self.rules.routing;
+        let y_offset = Length::new((pad_size.y - self.rules.dimensions.connector_width) / 2.0);
 
         let (&first, rest) = self
-            .0
+            .positions
             .split_first()
             .expect("there is always at least one thumb switch");
 
-        let x_offset = Self::X_OFFSET + centered_track_offset(1, 2);
-        let path = Path::angled_start(point!(x_offset, -PAD_SIZE.y / 2.0), ABOVE_ROW_PAD)
+        let x_offset = self.x_offset() + self.rules.centered_track_offset(1, 2);
+        let path = routing
+            .channel_path(point!(x_offset, -pad_size.y / 2.0), routing.above_row_pad)
             .append(ROW_PAD)
             .at(first);
-        pcb.add_track(&path, TOP_LAYER, row_net);
+        pcb.add_track(&path, self.rules.track_width, TOP_LAYER, row_net);
 
         if let Some((&last, rest)) = rest.split_last() {
             let path = Path::chamfered(
                 ROW_PAD,
-                point!(PAD_SIZE.x / 2.0, Y_OFFSET),
-                Y_OFFSET - BELOW_ROW_PAD.y(),
+                point!(pad_size.x / 2.0, y_offset),
+                y_offset - routing.below_row_pad.y(),
                 true,
             )
             .at(first)
             .join(
-                &Path::angled_start(point!(-PAD_SIZE.x / 2.0, Y_OFFSET), LEFT_OF_ROW_PAD)
+                &Path::angled_start(point!(-pad_size.x / 2.0, y_offset), routing.left_of_row_pad)
                     .append(ROW_PAD)
                     .at(last),
             );
-            pcb.add_track(&path, TOP_LAYER, row_net);
+            pcb.add_track(&path, self.rules.track_width, TOP_LAYER, row_net);
 
             for position in rest {
                 let track_points = [
                     *position + ROW_PAD,
-                    *position + point!(ROW_PAD.x(), Y_OFFSET),
+                    *position + point!(ROW_PAD.x(), y_offset),
                 ];
-                pcb.add_track(&track_points, TOP_LAYER, row_net);
+                pcb.add_track(&track_points, self.rules.track_width, TOP_LAYER, row_net);
             }
         }
     }
 
     /// Adds the tracks connecting the columns of the thumb switches.
     fn add_column_tracks(&self, pcb: &mut KicadPcb, columns: &[Net]) {
-        const TRACK_OFFSET: Length = Length::new(-6.38);
-        const CHAMFER_DEPTH: Length = Length::new(1.0);
+        let pad_size = self.rules.dimensions.pad_size;
+        let routing = self.rules.routing;
 
         let thumb_switch_count = self.positions().len();
 
-        let first_x_offset = Self::X_OFFSET + centered_track_offset(0, 2);
+        let first_x_offset = self.x_offset() + self.rules.centered_track_offset(0, 2);
         let (&first, rest) = self
-            .0
+            .positions
             .split_first()
             .expect("there is always at least one thumb switch");
 
-        let first_column_path =
-            Path::angled_start(point!(first_x_offset, -PAD_SIZE.y / 2.0), UPPER_COLUMN_PAD)
-                .at(first);
-        pcb.add_track(&first_column_path, TOP_LAYER, &columns[0]);
+        let first_column_path = Path::angled_start(
+            point!(first_x_offset, -pad_size.y / 2.0),
+            routing.upper_column_pad,
+        )
+        .at(first);
+        pcb.add_track(
+            &first_column_path,
+            self.rules.track_width,
+            TOP_LAYER,
+            &columns[0],
+        );
 
         if thumb_switch_count > 1 {
-            let x_offset = Self::X_OFFSET + centered_track_offset(0, thumb_switch_count - 1);
-            let y_offset = TRACK_OFFSET - TRACK_WIDTH / 2;
+            let x_offset =
+                self.x_offset() + self.rules.centered_track_offset(0, thumb_switch_count - 1);
+            let y_offset = routing.thumb_column_y_offset - self.rules.track_width / 2;
             let first_path_segment = Path::chamfered(
-                point!(x_offset, -PAD_SIZE.y / 2.0),
-                point!(PAD_SIZE.x / 2.0, y_offset),
-                CHAMFER_DEPTH,
+                point!(x_offset, -pad_size.y / 2.0),
+                point!(pad_size.x / 2.0, y_offset),
+                routing.thumb_column_chamfer_depth,
                 true,
             )
             .at(first);
 
             for (i, &switch) in rest.iter().enumerate() {
-                let offset = track_offset(i);
-                let offset_path = first_path_segment.offset(offset).join(
-                    &Path::angled_start(
-                        point!(-PAD_SIZE.x / 2.0, y_offset - offset),
-                        UPPER_COLUMN_PAD,
-                    )
-                    .at(switch),
+                let offset = self.rules.track_offset(i);
+                let passing_path = rest[..i].iter().fold(
+                    first_path_segment.offset(offset),
+                    |path, &passed_switch| {
+                        path.join(
+                            &routing
+                                .passing_column_path(
+                                    point!(-pad_size.x / 2.0, y_offset),
+                                    point!(pad_size.x / 2.0, y_offset),
+                                )
+                                .at(passed_switch)
+                                .offset(offset),
+                        )
+                    },
+                );
+                let offset_path = passing_path.join(
+                    &routing
+                        .ending_column_path(point!(-pad_size.x / 2.0, y_offset - offset))
+                        .at(switch),
+                );
+                pcb.add_track(
+                    &offset_path,
+                    self.rules.track_width,
+                    BOTTOM_LAYER,
+                    &columns[i + 1],
                 );
-                pcb.add_track(&offset_path, BOTTOM_LAYER, &columns[i + 1]);
             }
         }
     }
+
+    /// Returns the offset in the X direction of the connector attachment points.
+    fn x_offset(&self) -> Length {
+        let pad_size = self.rules.dimensions.pad_size;
+        Length::new((pad_size.x - self.rules.dimensions.connector_width) / 2.0)
+    }
 }
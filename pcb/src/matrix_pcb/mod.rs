@@ -6,69 +6,235 @@ mod nets;
 use std::iter::once;
 
 use config::Config;
+use model::{matrix_pcb::Dimensions, Diagnostic, Severity};
 
 use crate::{
+    footprints::Routing,
+    gerber::FabricationFile,
     kicad_pcb::{KicadPcb, Net},
-    primitives::{Point, Position},
+    kicad_schematic::KicadSchematic,
+    panel::Panel,
+    primitives::{Point, Position, UuidMode},
     unit::Length,
 };
 
 use builder::Builder;
 
 /// The name of the top copper layer.
-const TOP_LAYER: &str = "F.Cu";
+pub const TOP_LAYER: &str = "F.Cu";
 /// The name of the bottom copper layer.
-const BOTTOM_LAYER: &str = "B.Cu";
+pub const BOTTOM_LAYER: &str = "B.Cu";
 /// The name of the outline layer.
-const OUTLINE_LAYER: &str = "Edge.Cuts";
-/// The width of the outline.
-const OUTLINE_WIDTH: Length = Length::new(0.05);
+pub const OUTLINE_LAYER: &str = "Edge.Cuts";
 /// The position of the first home row key.
 const ORIGIN_POSITION: Position = Position::new(Length::new(100.0), Length::new(100.0), None);
-/// The track width.
-const TRACK_WIDTH: Length = Length::new(0.15);
-/// The track clearance.
-const TRACK_CLEARANCE: Length = Length::new(0.15);
+/// The width of the channels for tracks between the switch pins and the edges of the pads.
+const CHANNEL_WIDTH: Length = Length::new(1.54);
+
+/// The fabrication rules of the matrix PCB.
+#[derive(Clone, Copy)]
+pub struct Rules {
+    /// The track width.
+    pub track_width: Length,
+    /// The track clearance.
+    pub track_clearance: Length,
+    /// The clearance between copper and the outline.
+    pub edge_clearance: Length,
+    /// The width of the outline.
+    pub outline_width: Length,
+    /// The dimensions of the board and connectors.
+    pub dimensions: Dimensions,
+    /// The points within the switches the tracks are routed along.
+    pub routing: Routing,
+}
+
+impl Rules {
+    /// The offset for a track with the given index.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    pub fn track_offset(self, index: usize) -> Length {
+        (index as i32) * (self.track_width + self.track_clearance)
+    }
+
+    /// The offset for a track with the given index when centering the given number of tracks.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn centered_track_offset(self, index: usize, track_count: usize) -> Length {
+        (index as f32 - (track_count - 1) as f32 / 2.0) * (self.track_width + self.track_clearance)
+    }
+
+    /// Returns the X offset to clear the switch pads for a track with the given index.
+    pub fn x_offset(self, index: usize) -> Length {
+        Length::from(self.dimensions.pad_size.x / 2.0) - CHANNEL_WIDTH
+            + self.track_clearance
+            + self.track_width / 2
+            + self.track_offset(index)
+    }
+
+    /// Returns the width of a connector required to route the given number of tracks through it.
+    pub fn required_connector_width(self, track_count: usize) -> Length {
+        self.track_offset(track_count) - self.track_clearance + 2 * self.edge_clearance
+    }
+
+    /// Returns the width of the channel between the switch pins and the edge of the pads required
+    /// to route the given number of tracks along it.
+    pub fn required_channel_width(self, track_count: usize) -> Length {
+        self.track_offset(track_count) + self.edge_clearance
+    }
+}
+
+impl From<&Config> for Rules {
+    fn from(config: &Config) -> Self {
+        let fabrication = &config.fabrication;
+
+        Self {
+            track_width: f64::from(fabrication.track_width).into(),
+            track_clearance: f64::from(fabrication.track_clearance).into(),
+            edge_clearance: f64::from(fabrication.edge_clearance).into(),
+            outline_width: f64::from(fabrication.outline_width).into(),
+            dimensions: config.into(),
+            routing: Routing::new(config.keyboard.switch_type, config.keyboard.hot_swap),
+        }
+    }
+}
 
 /// A PCB connecting the keys to each other in a matrix.
-pub struct MatrixPcb(KicadPcb);
+pub struct MatrixPcb {
+    pcb: KicadPcb,
+    schematic: KicadSchematic,
+    rules: Rules,
+    channel_track_count: usize,
+    pad_channel_track_count: usize,
+}
 
 impl MatrixPcb {
     /// Creates a new matrix PCB from the given configuration.
+    ///
+    /// The UUIDs are derived from the elements, so the same configuration results in the same
+    /// board and schematic files.
     #[must_use]
     pub fn from_config(config: &Config) -> Self {
-        Self(Builder::from_config(config).build())
+        Self::from_config_with_uuid_mode(config, UuidMode::default())
+    }
+
+    /// Creates a new matrix PCB from the given configuration, creating UUIDs using the given mode.
+    #[must_use]
+    pub fn from_config_with_uuid_mode(config: &Config, uuid_mode: UuidMode) -> Self {
+        let rules = Rules::from(config);
+        let (pcb, schematic, channel_track_count, pad_channel_track_count) =
+            Builder::from_config(config, rules, uuid_mode).build();
+
+        Self {
+            pcb,
+            schematic,
+            rules,
+            channel_track_count,
+            pad_channel_track_count,
+        }
     }
 
     /// Serializes the matrix PCB to the KiCAD board file format.
     #[must_use]
     pub fn to_kicad_board(&self) -> String {
-        self.0.to_board_file()
+        self.pcb.to_board_file()
+    }
+
+    /// Serializes the schematic of the matrix PCB to the KiCAD schematic file format.
+    #[must_use]
+    pub fn to_kicad_schematic(&self) -> String {
+        self.schematic.to_schematic_file()
+    }
+
+    /// Plots the matrix PCB to Gerber and Excellon drill files.
+    #[must_use]
+    pub fn to_fabrication_files(&self) -> Vec<FabricationFile> {
+        self.pcb.to_fabrication_files()
+    }
+
+    /// Checks the clearances of the matrix PCB and returns the violations as diagnostics.
+    ///
+    /// Connectors and channels along the pad edges too narrow for the tracks routed through them
+    /// are reported first.
+    #[must_use]
+    pub fn check_design_rules(&self) -> Vec<Diagnostic> {
+        let connector_width = self.rules.dimensions.connector_width;
+        let required_connector_width = self
+            .rules
+            .required_connector_width(self.channel_track_count);
+        let required_channel_width = self
+            .rules
+            .required_channel_width(self.pad_channel_track_count);
+
+        (Length::from(connector_width) < required_connector_width)
+            .then(|| Diagnostic {
+                severity: Severity::Error,
+                keys: Vec::new(),
+                message: format!(
+                    "matrix PCB: the connectors are {connector_width:.2} mm wide, but routing {} tracks \
+                    through them requires {:.2} mm",
+                    self.channel_track_count,
+                    f64::from(required_connector_width)
+                ),
+            })
+            .into_iter()
+            .chain((CHANNEL_WIDTH < required_channel_width).then(|| Diagnostic {
+                severity: Severity::Error,
+                keys: Vec::new(),
+                message: format!(
+                    "matrix PCB: the channels between the switch pins and the pad edges are {:.2} mm \
+                    wide, but routing {} tracks along them requires {:.2} mm",
+                    f64::from(CHANNEL_WIDTH),
+                    self.pad_channel_track_count,
+                    f64::from(required_channel_width)
+                ),
+            }))
+            .chain(
+                self.pcb
+                    .check_design_rules(self.rules.track_clearance, self.rules.edge_clearance)
+                    .into_iter()
+                    .map(|violation| violation.into_diagnostic("matrix PCB")),
+            )
+            .collect()
+    }
+
+    /// Checks the connectivity of the tracks and pads of the matrix PCB and returns the
+    /// opens, shorts and unrouted pads as diagnostics.
+    #[must_use]
+    pub fn check_connectivity(&self) -> Vec<Diagnostic> {
+        self.pcb
+            .check_connectivity()
+            .into_iter()
+            .map(|violation| violation.into_diagnostic("matrix PCB"))
+            .collect()
+    }
+
+    /// Creates a panel containing the matrix PCB for both halves.
+    #[must_use]
+    pub fn to_panel(&self) -> Panel {
+        Panel::from_board(&self.pcb)
     }
 }
 
 /// A trait for adding paths and polygons.
-trait AddPath {
-    /// Adds the path given by the points to the outline of `self`.
-    fn add_outline_path(&mut self, points: &[Point]);
+pub trait AddPath {
+    /// Adds the path given by the points with the given width to the outline of `self`.
+    fn add_outline_path(&mut self, points: &[Point], width: Length);
 
-    /// Adds the polygon given by the points to the outline of `self`.
-    fn add_outline_polygon(&mut self, points: &[Point]);
+    /// Adds the polygon given by the points with the given width to the outline of `self`.
+    fn add_outline_polygon(&mut self, points: &[Point], width: Length);
 
-    /// Adds a track along the path given by the points to `self`.
-    fn add_track(&mut self, points: &[Point], layer: &'static str, net: &Net);
+    /// Adds a track with the given width along the path given by the points to `self`.
+    fn add_track(&mut self, points: &[Point], width: Length, layer: &'static str, net: &Net);
 }
 
 impl AddPath for KicadPcb {
-    fn add_outline_path(&mut self, points: &[Point]) {
+    fn add_outline_path(&mut self, points: &[Point], width: Length) {
         for window in points.windows(2) {
             if window[0] != window[1] {
-                self.add_graphical_line(window[0], window[1], OUTLINE_WIDTH, OUTLINE_LAYER);
+                self.add_graphical_line(window[0], window[1], width, OUTLINE_LAYER);
             }
         }
     }
 
-    fn add_outline_polygon(&mut self, points: &[Point]) {
+    fn add_outline_polygon(&mut self, points: &[Point], width: Length) {
         assert!(points.len() >= 3);
 
         let first = *points.first().expect("there are at least three vertices");
@@ -76,33 +242,80 @@ impl AddPath for KicadPcb {
 
         for window in points.windows(2).chain(once([last, first].as_slice())) {
             if window[0] != window[1] {
-                self.add_graphical_line(window[0], window[1], OUTLINE_WIDTH, OUTLINE_LAYER);
+                self.add_graphical_line(window[0], window[1], width, OUTLINE_LAYER);
             }
         }
     }
 
-    fn add_track(&mut self, points: &[Point], layer: &'static str, net: &Net) {
+    fn add_track(&mut self, points: &[Point], width: Length, layer: &'static str, net: &Net) {
         for window in points.windows(2) {
             if window[0] != window[1] {
-                self.add_segment(window[0], window[1], TRACK_WIDTH, layer, net);
+                self.add_segment(window[0], window[1], width, layer, net);
             }
         }
     }
 }
 
-/// The offset for a track with the given index.
-#[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
-fn track_offset(index: usize) -> Length {
-    (index as i32) * (TRACK_WIDTH + TRACK_CLEARANCE)
-}
+#[cfg(test)]
+mod tests {
+    use config::{Config, SwitchType};
 
-/// The offset for a track with the given index when centering the given number of tracks.
-#[allow(clippy::cast_precision_loss)]
-pub fn centered_track_offset(index: usize, track_count: usize) -> Length {
-    (index as f32 - (track_count - 1) as f32 / 2.0) * (TRACK_WIDTH + TRACK_CLEARANCE)
-}
+    use super::*;
+
+    #[test]
+    fn hot_swap_boards_are_routed() {
+        for switch_type in [
+            SwitchType::Mx,
+            SwitchType::ChocV1,
+            SwitchType::ChocV2,
+            SwitchType::GateronLowProfile,
+        ] {
+            let mut config = Config::default();
+            config.set_switch_type(switch_type, true);
+            let matrix_pcb = MatrixPcb::from_config(&config);
+
+            assert!(
+                matrix_pcb.check_connectivity().is_empty(),
+                "{switch_type} board should be fully connected"
+            );
+            assert!(
+                matrix_pcb.check_design_rules().is_empty(),
+                "{switch_type} board should pass the design rules"
+            );
+        }
+    }
+
+    #[test]
+    fn overfull_pad_channels_are_reported() {
+        let mut config = Config::default();
+        config.fabrication.track_width = 0.6.try_into().expect("0.6 is positive");
+        config.fabrication.track_clearance = 0.8.try_into().expect("0.8 is positive");
+        let matrix_pcb = MatrixPcb::from_config(&config);
+
+        assert!(matrix_pcb
+            .check_design_rules()
+            .iter()
+            .any(|diagnostic| diagnostic
+                .message
+                .contains("channels between the switch pins")));
+    }
+
+    #[test]
+    fn thumb_keys_beyond_the_columns_are_connected() {
+        let mut table = toml::Table::try_from(Config::default()).expect("config is serializable");
+        let columns = &mut table["finger_cluster"]["columns"];
+        columns["left_side_column"]["active"] = false.into();
+        columns["right_side_column"]["active"] = false.into();
+        table["thumb_cluster"]["keys"] = 6.into();
+        let config: Config = table.try_into().expect("config should be valid");
+        let matrix_pcb = MatrixPcb::from_config(&config);
 
-/// Returns the X offset to clear the switch pads for a track with the given index.
-pub fn x_offset(index: usize) -> Length {
-    Length::from(4.96) + TRACK_CLEARANCE + TRACK_WIDTH / 2 + track_offset(index)
+        assert!(
+            !matrix_pcb
+                .check_connectivity()
+                .iter()
+                .any(|diagnostic| diagnostic.message.contains("not connected")),
+            "every thumb switch should be connected to its column"
+        );
+    }
 }
@@ -13,6 +13,11 @@ pub struct Nets {
 }
 
 impl Nets {
+    /// The maximum number of rows.
+    pub const MAXIMUM_ROWS: usize = 6;
+    /// The maximum number of columns.
+    pub const MAXIMUM_COLUMNS: usize = 6;
+
     /// Creates and adds the nets to the matrix PCB.
     pub fn create(
         pcb: &mut KicadPcb,
@@ -21,13 +26,10 @@ impl Nets {
         thumb_switch_count: usize,
         home_row_index: usize,
     ) -> Self {
-        const MAXIMUM_ROWS: usize = 6;
-        const MAXIMUM_COLUMNS: usize = 6;
-
-        let rows: Vec<_> = (1..=MAXIMUM_ROWS)
+        let rows: Vec<_> = (1..=Self::MAXIMUM_ROWS)
             .map(|index| pcb.create_net(format!("ROW{index}")))
             .collect();
-        let columns: Vec<_> = (1..=MAXIMUM_COLUMNS)
+        let columns: Vec<_> = (1..=Self::MAXIMUM_COLUMNS)
             .map(|index| pcb.create_net(format!("COL{index}")))
             .collect();
 
@@ -41,6 +43,11 @@ impl Nets {
         }
     }
 
+    /// Returns the index of the home row within the finger rows.
+    pub fn home_row_index(&self) -> usize {
+        self.home_row_index
+    }
+
     /// Returns the net of the home row.
     pub fn home_row(&self) -> &Net {
         &self.rows[self.home_row_index + 1]
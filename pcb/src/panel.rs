@@ -0,0 +1,220 @@
+use glam::{vec2, Vec2};
+
+use crate::{
+    footprints::{MouseBites, Tab},
+    kicad_pcb::KicadPcb,
+    matrix_pcb::{AddPath, OUTLINE_LAYER},
+    primitives::{Point, Position},
+    unit::{IntoAngle, Length},
+};
+
+/// The position of the top left corner of the panel.
+const ORIGIN: Point = Point::new(Length::new(100.0), Length::new(100.0));
+/// The width of the rails at the top and bottom of the panel.
+const RAIL_WIDTH: Length = Length::new(5.0);
+/// The space between the boards and the rails.
+const RAIL_SPACING: Length = Length::new(2.0);
+/// The space between the two boards.
+const BOARD_SPACING: Length = Length::new(2.0);
+/// The width of the outline.
+const OUTLINE_WIDTH: Length = Length::new(0.05);
+/// The radius of the router bit milling the inner corners between the tabs and the boards or rails.
+const MILL_RADIUS: Length = Length::new(1.0);
+/// The clearance between the copper fill of the rails and their edges.
+const COPPER_FILL_CLEARANCE: Length = Length::new(0.5);
+/// The offset of the mouse bites from the board edge into the board.
+const MOUSE_BITES_OFFSET: Length = Length::new(0.25);
+/// The height of the texts on the rails.
+const RAIL_TEXT_HEIGHT: Length = Length::new(1.5);
+/// The text on the top rail.
+const TOP_RAIL_TEXT: &str =
+    "Open Source Hardware - https://github.com/julianschuler/concavum-customizer";
+/// The text on the bottom rail.
+const BOTTOM_RAIL_TEXT: &str =
+    "This PCB was automatically generated using the Concavum Customizer.";
+
+/// A panel holding a board for each half between rails at the top and bottom.
+pub struct Panel(KicadPcb);
+
+impl Panel {
+    /// Creates a new panel containing two copies of the given board.
+    ///
+    /// The boards are connected to the copper filled rails by tabs at the tab markers of the board,
+    /// with the inner corners of the tabs rounded for milling.
+    /// Markers not pointing towards a rail or not placed at a straight edge are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board has no outline.
+    #[must_use]
+    pub fn from_board(board: &KicadPcb) -> Self {
+        let mut pcb = KicadPcb::new(board.thickness(), board.uuid_mode());
+
+        let (minimum, maximum) = board
+            .outline_bounds()
+            .expect("the board should have an outline");
+        let board_width = maximum.x() - minimum.x();
+        let board_height = maximum.y() - minimum.y();
+
+        let panel_width = 2 * board_width + BOARD_SPACING;
+        let top_rail = ORIGIN.y() + RAIL_WIDTH;
+        let bottom_rail = top_rail + 2 * RAIL_SPACING + board_height;
+
+        // The rails are added first to allow cutting them for the tabs
+        for (y, text) in [(ORIGIN.y(), TOP_RAIL_TEXT), (bottom_rail, BOTTOM_RAIL_TEXT)] {
+            let minimum = Point::new(ORIGIN.x(), y);
+            let maximum = Point::new(ORIGIN.x() + panel_width, y + RAIL_WIDTH);
+
+            pcb.add_outline_polygon(
+                &[
+                    minimum,
+                    Point::new(maximum.x(), minimum.y()),
+                    maximum,
+                    Point::new(minimum.x(), maximum.y()),
+                ],
+                OUTLINE_WIDTH,
+            );
+            pcb.add_copper_fill(minimum, maximum, COPPER_FILL_CLEARANCE);
+
+            let center = Position::new(ORIGIN.x() + panel_width / 2, y + RAIL_WIDTH / 2, None);
+            pcb.add_silkscreen_text(text, center, RAIL_TEXT_HEIGHT);
+        }
+
+        for index in 0..2 {
+            let offset = Point::new(
+                ORIGIN.x() + index * (board_width + BOARD_SPACING) - minimum.x(),
+                top_rail + RAIL_SPACING - minimum.y(),
+            );
+            pcb.add_pcb(board, offset);
+
+            for tab in board
+                .footprints()
+                .iter()
+                .filter(|footprint| footprint.name() == Tab::NAME)
+            {
+                let position = tab.position();
+                let position = Position::new(
+                    position.x() + offset.x(),
+                    position.y() + offset.y(),
+                    position.angle(),
+                );
+
+                add_tab(&mut pcb, position, top_rail, bottom_rail);
+            }
+        }
+
+        Self(pcb)
+    }
+
+    /// Serializes the panel to the KiCAD board file format.
+    #[must_use]
+    pub fn to_kicad_board(&self) -> String {
+        self.0.to_board_file()
+    }
+}
+
+/// Adds a tab from the board edge at the given marker position to the closer rail.
+fn add_tab(pcb: &mut KicadPcb, position: Position, top_rail: Length, bottom_rail: Length) {
+    let angle = position.angle().unwrap_or_default();
+    let (sin, cos) = angle.sin_cos();
+
+    // The marker points into the board, so the tab extends in the opposite direction
+    let direction = vec2(-cos, sin);
+    if direction.y.abs() < f32::EPSILON {
+        return;
+    }
+
+    let rail = if direction.y < 0.0 {
+        top_rail
+    } else {
+        bottom_rail
+    };
+    let side = f32::from(Tab::WIDTH) / 2.0 * vec2(-direction.y, direction.x);
+    let origin = position.point();
+
+    let board_corners = [origin + side, origin - side];
+
+    // The board edge is perpendicular to the tab and the rail edge is horizontal
+    let fillets = board_corners.map(|board_corner| {
+        let rail_corner =
+            board_corner + f32::from(rail - board_corner.y()) / direction.y * direction;
+        let board_edge = (board_corner - origin).normalize();
+        let rail_edge = vec2(board_edge.x.signum(), 0.0);
+
+        // Both fillets have to fit on the side of the tab
+        let side_length = (rail_corner - board_corner).length();
+        let radius = f32::from(MILL_RADIUS).min(
+            side_length
+                / (Fillet::distance_factor(board_edge, direction)
+                    + Fillet::distance_factor(rail_edge, -direction)),
+        );
+
+        (
+            Fillet::new(board_corner, board_edge, direction, radius),
+            Fillet::new(rail_corner, rail_edge, -direction, radius),
+        )
+    });
+    let [(first_board_fillet, first_rail_fillet), (second_board_fillet, second_rail_fillet)] =
+        &fillets;
+
+    if !pcb.cut_outline_line(first_board_fillet.start, second_board_fillet.start) {
+        return;
+    }
+    pcb.cut_outline_line(first_rail_fillet.start, second_rail_fillet.start);
+
+    for (board_fillet, rail_fillet) in &fillets {
+        pcb.add_outline_path(&[board_fillet.end, rail_fillet.end], OUTLINE_WIDTH);
+
+        for fillet in [board_fillet, rail_fillet] {
+            pcb.add_graphical_arc(
+                fillet.start,
+                fillet.mid,
+                fillet.end,
+                OUTLINE_WIDTH,
+                OUTLINE_LAYER,
+            );
+        }
+    }
+
+    let mouse_bites_position = origin - f32::from(MOUSE_BITES_OFFSET) * direction;
+    let mouse_bites = MouseBites::new(
+        Position::new(
+            mouse_bites_position.x(),
+            mouse_bites_position.y(),
+            Some(angle + 90.deg()),
+        ),
+        Tab::WIDTH,
+    );
+    pcb.add_footprint(mouse_bites.into());
+}
+
+/// An arc rounding an inner corner of the outline.
+struct Fillet {
+    start: Point,
+    mid: Point,
+    end: Point,
+}
+
+impl Fillet {
+    /// Creates a fillet with the given radius rounding the corner between the edges leaving it in
+    /// the given directions.
+    ///
+    /// The fillet starts on the edge in the first direction and ends on the one in the second.
+    fn new(corner: Point, first_direction: Vec2, second_direction: Vec2, radius: f32) -> Self {
+        let half_angle = first_direction.angle_to(second_direction).abs() / 2.0;
+        let bisector = (first_direction + second_direction).normalize();
+        let center = corner + radius / half_angle.sin() * bisector;
+        let distance = radius * Self::distance_factor(first_direction, second_direction);
+
+        Self {
+            start: corner + distance * first_direction,
+            mid: center - radius * bisector,
+            end: corner + distance * second_direction,
+        }
+    }
+
+    /// Returns the distance of the fillet ends from the corner per unit of radius.
+    fn distance_factor(first_direction: Vec2, second_direction: Vec2) -> f32 {
+        1.0 / (first_direction.angle_to(second_direction).abs() / 2.0).tan()
+    }
+}
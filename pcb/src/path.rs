@@ -134,6 +134,29 @@ impl Path {
         Self(offset_path)
     }
 
+    /// Chamfers all corners of the path with the given depth.
+    pub fn chamfer(&self, depth: Length) -> Self {
+        let deduplicated = self.deduplicate();
+
+        let depth = f32::from(depth);
+        let mut chamfered_path: Vec<_> = deduplicated.first().copied().into_iter().collect();
+
+        for window in deduplicated.windows(3) {
+            let previous_point = window[0];
+            let point = window[1];
+            let next_point = window[2];
+
+            chamfered_path.push(point - depth * (point - previous_point).normalize());
+            chamfered_path.push(point + depth * (next_point - point).normalize());
+        }
+
+        if deduplicated.len() > 1 {
+            chamfered_path.extend(deduplicated.last());
+        }
+
+        Self(chamfered_path)
+    }
+
     /// Joins the path with the given other one, returning the resulting path.
     pub fn join(mut self, other: &[Point]) -> Self {
         self.extend_from_slice(other);
@@ -1,12 +1,18 @@
-use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
-
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    hash::Hasher,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+};
+
+use config::StableHasher;
 use glam::{vec2, Vec2};
 use serde::Serialize;
 
 use crate::unit::{Angle, Length};
 
 /// A 2-dimensional point.
-#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point(Length, Length);
 
 impl Point {
@@ -75,6 +81,16 @@ impl Size {
     pub fn new(width: Length, height: Length) -> Self {
         Self(width, height)
     }
+
+    /// Returns the width.
+    pub fn width(self) -> Length {
+        self.0
+    }
+
+    /// Returns the height.
+    pub fn height(self) -> Length {
+        self.1
+    }
 }
 
 /// Creates a size tuple from the given values.
@@ -86,7 +102,7 @@ macro_rules! size {
 }
 
 /// A 2-dimensional position with an optional orientation angle.
-#[derive(Serialize, Clone, Copy)]
+#[derive(Serialize, Clone, Copy, Hash)]
 pub struct Position(Length, Length, Option<Angle>);
 
 impl Position {
@@ -203,6 +219,10 @@ macro_rules! position {
 }
 
 /// A Universally Unique Identifier (UUID).
+///
+/// The default is the nil UUID, which footprints are created with until their UUIDs are assigned
+/// by the PCB they are added to.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Uuid(uuid::Uuid);
 
 impl Serialize for Uuid {
@@ -214,8 +234,195 @@ impl Serialize for Uuid {
     }
 }
 
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl Uuid {
-    pub fn new() -> Self {
+    /// Creates a random UUID.
+    fn random() -> Self {
         Self(uuid::Uuid::new_v4())
     }
+
+    /// Parses a UUID from its hyphenated string representation.
+    pub fn parse(value: &str) -> Option<Self> {
+        uuid::Uuid::parse_str(value).ok().map(Self)
+    }
+
+    /// Creates a UUID derived from a stable hash of the given role, content bytes and salt.
+    fn derived(role: &str, content: &[u8], salt: u32) -> Self {
+        let mut hasher = StableHasher::default();
+
+        hasher.write(role.as_bytes());
+        hasher.write(content);
+        hasher.write(&salt.to_le_bytes());
+
+        Self(uuid::Builder::from_custom_bytes(hasher.finish_bytes()).into_uuid())
+    }
+}
+
+/// Content distinguishing an element of a KiCAD file from other elements with the same role.
+///
+/// The content is written as explicit little endian bytes, so the UUIDs derived from it stay the
+/// same across platforms and Rust versions. Variable sized content is prefixed with its length.
+pub trait UuidContent {
+    /// Appends the bytes of the content to the given buffer.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+impl<T: UuidContent + ?Sized> UuidContent for &T {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        (**self).write_bytes(bytes);
+    }
+}
+
+impl UuidContent for u32 {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.to_le_bytes());
+    }
+}
+
+impl UuidContent for usize {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend((*self as u64).to_le_bytes());
+    }
+}
+
+impl UuidContent for str {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.len().write_bytes(bytes);
+        bytes.extend(self.as_bytes());
+    }
+}
+
+impl UuidContent for String {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.as_str().write_bytes(bytes);
+    }
+}
+
+impl<T: UuidContent> UuidContent for [T] {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.len().write_bytes(bytes);
+        for element in self {
+            element.write_bytes(bytes);
+        }
+    }
+}
+
+impl<T: UuidContent> UuidContent for Vec<T> {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.as_slice().write_bytes(bytes);
+    }
+}
+
+impl<T: UuidContent> UuidContent for Option<T> {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        if let Some(value) = self {
+            bytes.push(1);
+            value.write_bytes(bytes);
+        } else {
+            bytes.push(0);
+        }
+    }
+}
+
+impl UuidContent for Point {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.x().write_bytes(bytes);
+        self.y().write_bytes(bytes);
+    }
+}
+
+impl UuidContent for Position {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.x().write_bytes(bytes);
+        self.y().write_bytes(bytes);
+        self.angle().write_bytes(bytes);
+    }
+}
+
+impl UuidContent for Uuid {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.0.as_bytes());
+    }
+}
+
+/// Implements the UUID content for tuples by writing their elements in order.
+macro_rules! impl_uuid_content_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: UuidContent),+> UuidContent for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                $($name.write_bytes(bytes);)+
+            }
+        }
+    };
+}
+
+impl_uuid_content_for_tuple!(A, B);
+impl_uuid_content_for_tuple!(A, B, C);
+impl_uuid_content_for_tuple!(A, B, C, D);
+impl_uuid_content_for_tuple!(A, B, C, D, E);
+
+/// The way the UUIDs of the elements of a KiCAD file are created.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum UuidMode {
+    /// The UUIDs are derived from the role and position of the elements.
+    ///
+    /// Creating the same file twice results in the same UUIDs.
+    #[default]
+    Deterministic,
+    /// The UUIDs are created randomly.
+    Random,
+}
+
+/// A generator for the UUIDs of the elements of a KiCAD file.
+pub struct UuidGenerator {
+    mode: UuidMode,
+    used: HashSet<Uuid>,
+}
+
+impl UuidGenerator {
+    /// Creates a new UUID generator using the given mode.
+    pub fn new(mode: UuidMode) -> Self {
+        Self {
+            mode,
+            used: HashSet::new(),
+        }
+    }
+
+    /// Returns the mode of the generator.
+    pub fn mode(&self) -> UuidMode {
+        self.mode
+    }
+
+    /// Creates a UUID for an element with the given role and content.
+    ///
+    /// The content should contain the position of the element and everything else required to
+    /// distinguish it from other elements of the same role. Elements with the same role and
+    /// content are distinguished by the order they are created in.
+    pub fn generate(&mut self, role: &str, content: &impl UuidContent) -> Uuid {
+        match self.mode {
+            UuidMode::Deterministic => {
+                let mut content_bytes = Vec::new();
+                content.write_bytes(&mut content_bytes);
+                let content = content_bytes.as_slice();
+
+                let mut salt = 0;
+                let mut uuid = Uuid::derived(role, content, salt);
+
+                while !self.used.insert(uuid) {
+                    salt += 1;
+                    uuid = Uuid::derived(role, content, salt);
+                }
+
+                uuid
+            }
+            UuidMode::Random => Uuid::random(),
+        }
+    }
 }
@@ -2,13 +2,15 @@ use std::ops::{Add, Div, Mul, Neg, Sub};
 
 use serde::Serialize;
 
+use crate::primitives::UuidContent;
+
 /// The conversion factor between an i32 value and the units of length or rotation.
 pub const VALUE_TO_UNIT: i32 = 1_000_000;
 /// The maximum angle value.
 const MAXIMUM_ANGLE: i32 = 360 * VALUE_TO_UNIT;
 
 /// A unit of length.
-#[derive(Serialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd)]
+#[derive(Serialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(transparent)]
 pub struct Length(i32);
 
@@ -22,6 +24,11 @@ impl Length {
         Length((value * VALUE_TO_UNIT as f64) as i32)
     }
 
+    /// Parses a length from the given decimal value in millimeters.
+    pub fn parse(value: &str) -> Option<Length> {
+        parse_units(value).map(Length)
+    }
+
     /// Returns the minimum of the given values.
     pub fn min(self, other: Length) -> Length {
         Length(self.0.min(other.0))
@@ -33,6 +40,12 @@ impl Length {
     }
 }
 
+impl UuidContent for Length {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.0.to_le_bytes());
+    }
+}
+
 impl Add for Length {
     type Output = Self;
 
@@ -125,17 +138,28 @@ impl From<Length> for f64 {
 }
 
 /// A unit of rotation.
-#[derive(Serialize, Clone, Copy, Default)]
+#[derive(Serialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(transparent)]
 pub struct Angle(i32);
 
 impl Angle {
+    /// Parses an angle from the given decimal value in degrees.
+    pub fn parse(value: &str) -> Option<Angle> {
+        parse_units(value).map(Angle)
+    }
+
     /// Calculates the sine and cosine of the angle.
     pub fn sin_cos(self) -> (f32, f32) {
         f32::from(self).to_radians().sin_cos()
     }
 }
 
+impl UuidContent for Angle {
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.0.to_le_bytes());
+    }
+}
+
 impl Add for Angle {
     type Output = Self;
 
@@ -229,3 +253,37 @@ impl IntoAngle for f64 {
         self.to_degrees().deg()
     }
 }
+
+/// Parses the given decimal value to units without loss of precision.
+///
+/// Fractional digits beyond the precision of the units are truncated.
+fn parse_units(value: &str) -> Option<i32> {
+    const FRACTIONAL_DIGITS: usize = VALUE_TO_UNIT.ilog10() as usize;
+
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (integer_part, fractional_part) = value.split_once('.').unwrap_or((value, ""));
+
+    if integer_part.is_empty() && fractional_part.is_empty()
+        || !integer_part.bytes().all(|byte| byte.is_ascii_digit())
+        || !fractional_part.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let integer = if integer_part.is_empty() {
+        0
+    } else {
+        integer_part.parse::<i32>().ok()?
+    };
+    let fraction = fractional_part
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(FRACTIONAL_DIGITS)
+        .fold(0, |fraction, digit| 10 * fraction + i32::from(digit - b'0'));
+    let units = integer.checked_mul(VALUE_TO_UNIT)?.checked_add(fraction)?;
+
+    Some(if negative { -units } else { units })
+}
@@ -0,0 +1,162 @@
+use config::Controller;
+use matrix::RP2040_ZERO_PINS;
+
+/// The Pro Micro pins used for the serial connection between the halves.
+const SERIAL_PINS: [u8; 2] = [2, 3];
+/// The Pro Micro pin connected to USB voltage using a voltage divider.
+const VBUS_PIN: u8 = 20;
+/// The Pro Micro pin determining the handedness of a half.
+const HANDEDNESS_PIN: u8 = 21;
+
+/// The names of the KB2040 pins in the order of the Pro Micro pins.
+static KB2040_PINS: [(u8, &str); 18] = [
+    (0, "GP0"),
+    (1, "GP1"),
+    (2, "GP2"),
+    (3, "GP3"),
+    (4, "GP4"),
+    (5, "GP5"),
+    (6, "GP6"),
+    (7, "GP7"),
+    (8, "GP8"),
+    (9, "GP9"),
+    (10, "GP10"),
+    (14, "GP20"),
+    (15, "GP18"),
+    (16, "GP19"),
+    (18, "GP26"),
+    (19, "GP27"),
+    (20, "GP28"),
+    (21, "GP29"),
+];
+
+/// The names of the ATmega32U4 pins in the order of the Pro Micro pins.
+static PRO_MICRO_PINS: [(u8, &str); 18] = [
+    (0, "D3"),
+    (1, "D2"),
+    (2, "D1"),
+    (3, "D0"),
+    (4, "D4"),
+    (5, "C6"),
+    (6, "D7"),
+    (7, "E6"),
+    (8, "B4"),
+    (9, "B5"),
+    (10, "B6"),
+    (14, "B3"),
+    (15, "B1"),
+    (16, "B2"),
+    (18, "F7"),
+    (19, "F6"),
+    (20, "F5"),
+    (21, "F4"),
+];
+
+/// A controller board supported by QMK.
+#[derive(Clone, Copy)]
+pub enum Board {
+    /// An Adafruit KB2040.
+    Kb2040,
+    /// A Waveshare RP2040-Zero.
+    Rp2040Zero,
+    /// A Pro Micro with an ATmega32U4.
+    ProMicro,
+}
+
+impl Board {
+    /// Returns the board of the given controller if it is supported by QMK.
+    pub fn from_controller(controller: Controller) -> Option<Self> {
+        match controller {
+            Controller::Kb2040 => Some(Board::Kb2040),
+            Controller::Rp2040Zero => Some(Board::Rp2040Zero),
+            Controller::ProMicro => Some(Board::ProMicro),
+            Controller::NiceNano => None,
+        }
+    }
+
+    /// Returns the name of the board.
+    pub fn name(self) -> &'static str {
+        match self {
+            Board::Kb2040 => "Adafruit KB2040",
+            Board::Rp2040Zero => "Waveshare RP2040-Zero",
+            Board::ProMicro => "Pro Micro",
+        }
+    }
+
+    /// Returns the instructions for entering the bootloader by resetting the board.
+    pub fn reset_instructions(self) -> &'static str {
+        match self {
+            // Double tapping is activated in `config_rp2040.h`
+            Board::Kb2040 | Board::Rp2040Zero => {
+                "**Physical reset button**: Double tap the reset button on the controller"
+            }
+            // The Caterina bootloader only stays active if the reset is repeated quickly
+            Board::ProMicro => {
+                "**Physical reset**: Short the RST and GND pins of the controller twice in quick succession"
+            }
+        }
+    }
+
+    /// Returns the name of the processor as used by QMK.
+    pub fn processor(self) -> &'static str {
+        match self {
+            Board::Kb2040 | Board::Rp2040Zero => "RP2040",
+            Board::ProMicro => "atmega32u4",
+        }
+    }
+
+    /// Returns the name of the bootloader as used by QMK.
+    pub fn bootloader(self) -> &'static str {
+        match self {
+            Board::Kb2040 | Board::Rp2040Zero => "rp2040",
+            Board::ProMicro => "caterina",
+        }
+    }
+
+    /// Returns the name of the serial driver used for the connection between the halves.
+    pub fn serial_driver(self) -> &'static str {
+        match self {
+            // The RP2040 supports full-duplex serial on arbitrary pins using its PIO peripheral
+            Board::Kb2040 | Board::Rp2040Zero => "vendor",
+            Board::ProMicro => "bitbang",
+        }
+    }
+
+    /// Returns the name of the pin determining the handedness of a half.
+    pub fn handedness_pin(self) -> &'static str {
+        self.pin(HANDEDNESS_PIN)
+    }
+
+    /// Returns the content of the `config.h` file.
+    pub fn config_h(self) -> String {
+        let [transmit_pin, receive_pin] = SERIAL_PINS.map(|pin| self.pin(pin));
+
+        match self {
+            Board::Kb2040 | Board::Rp2040Zero => include_str!("config_rp2040.h")
+                .replace("$transmit_pin", transmit_pin)
+                .replace("$receive_pin", receive_pin)
+                .replace("$vbus_pin", self.pin(VBUS_PIN)),
+            // The ATmega32U4 detects the USB connection by itself
+            Board::ProMicro => {
+                include_str!("config_atmega32u4.h").replace("$serial_pin", transmit_pin)
+            }
+        }
+    }
+
+    /// Returns the name of the pin of the board replacing the given Pro Micro pin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given pin does not exist on a Pro Micro.
+    pub fn pin(self, pin: u8) -> &'static str {
+        let pins = match self {
+            Board::Kb2040 => &KB2040_PINS,
+            Board::Rp2040Zero => &RP2040_ZERO_PINS,
+            Board::ProMicro => &PRO_MICRO_PINS,
+        };
+
+        pins.iter()
+            .find_map(|&(pro_micro_pin, name)| (pro_micro_pin == pin).then_some(name))
+            .expect("pin should exist on a Pro Micro")
+    }
+}
@@ -0,0 +1,7 @@
+#ifndef CONFIG_H
+#define CONFIG_H
+
+// split keyboard settings
+#define SOFT_SERIAL_PIN $serial_pin
+
+#endif
@@ -6,10 +6,10 @@
 #define RP2040_BOOTLOADER_DOUBLE_TAP_RESET_TIMEOUT 500U
 
 // split keyboard settings
-#define USB_VBUS_PIN GP28
+#define USB_VBUS_PIN $vbus_pin
 #define SERIAL_USART_FULL_DUPLEX
-#define SERIAL_USART_TX_PIN GP2
-#define SERIAL_USART_RX_PIN GP3
+#define SERIAL_USART_TX_PIN $transmit_pin
+#define SERIAL_USART_RX_PIN $receive_pin
 #define SERIAL_USART_PIN_SWAP
 
 #endif
@@ -1,150 +1,317 @@
 use std::fmt::{Display, Formatter, Result};
 
-use crate::replace_indented::ReplaceIndented;
+use config::{Config, Firmware, KeySize};
+use glam::{dvec2, DAffine3, DVec2};
+use matrix::{Matrix, Side};
+use model::KeyPositions;
+use serde_json::Value;
+
+use crate::{board::Board, replace_indented::ReplaceIndented};
 
 pub struct Keyboard {
-    columns: usize,
-    rows: usize,
-    thumb_keys: usize,
+    matrix: Matrix,
+    key_positions: KeyPositions,
+    key_distance: DVec2,
+    thumb_key_height: Option<f64>,
 }
 
 impl Keyboard {
-    /// Creates a new keyboard from the given matrix parameters.
-    pub fn new(columns: usize, rows: usize, thumb_keys: usize) -> Self {
+    /// Creates a new keyboard from the given configuration.
+    pub fn new(config: &Config) -> Self {
+        let thumb_key_height = match config.thumb_cluster.key_size {
+            KeySize::U1 => None,
+            KeySize::U1_5 => Some(1.5),
+        };
+
         Self {
-            columns,
-            rows,
-            thumb_keys,
+            matrix: Matrix::from_config(config),
+            key_positions: KeyPositions::untilted_from_config(config),
+            key_distance: config.finger_cluster.key_distance.into(),
+            thumb_key_height,
         }
     }
 
-    /// Returns the content the `keyboard.json` file.
+    /// Returns the key matrix of a single half.
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+
+    /// Returns the number of rows and columns of the key matrix spanning both halves.
     #[must_use]
-    pub fn to_file(&self) -> String {
-        let column_count = self.columns.max(self.thumb_keys);
-        let row_count = self.rows + 1;
-
-        let left_column_pins = [
-            "\"GP8\"", "\"GP7\"", "\"GP6\"", "\"GP5\"", "\"GP4\"", "\"GP27\"",
-        ];
-        let left_row_pins = [
-            "\"GP9\"", "\"GP10\"", "\"GP19\"", "\"GP20\"", "\"GP18\"", "\"GP26\"",
-        ];
-        let right_column_pins = [
-            "\"GP4\"", "\"GP27\"", "\"GP26\"", "\"GP18\"", "\"GP20\"", "\"GP19\"",
-        ];
-        let right_row_pins = [
-            "\"GP10\"", "\"GP9\"", "\"GP8\"", "\"GP7\"", "\"GP6\"", "\"GP5\"",
-        ];
-
-        let left_columns = left_column_pins.iter().rev().take(column_count).rev();
-        let left_rows = left_row_pins.iter().take(row_count);
-        let right_columns = right_column_pins.iter().take(column_count);
-        let right_rows = right_row_pins.iter().take(row_count);
-        let left_bootmagic_matrix = format!("[{row}, 0]", row = row_count - 1);
-        let right_bootmagic_matrix = format!(
-            "[{row}, {column}]",
-            row = 2 * row_count - 1,
-            column = self.columns - 1
-        );
+    pub fn matrix_size(&self) -> (usize, usize) {
+        (2 * self.matrix.row_count(), self.matrix.column_count())
+    }
+
+    /// Returns the content the `keyboard.json` file for the given board using the given firmware
+    /// metadata.
+    #[must_use]
+    pub fn to_file(&self, board: Board, firmware: &Firmware) -> String {
+        let pin_names = |pins: Vec<u8>| -> Vec<_> {
+            pins.into_iter()
+                .map(|pin| format!("\"{}\"", board.pin(pin)))
+                .collect()
+        };
+
+        let (left_columns, left_rows) = self.matrix.pins(Side::Left);
+        let (right_columns, right_rows) = self.matrix.pins(Side::Right);
+        let bootmagic_matrix = |side| {
+            let (row, column) = self.bootmagic_position(side);
+
+            format!("[{row}, {column}]")
+        };
 
         include_str!("keyboard.json")
-            .replace_indented("$left_columns", left_columns)
-            .replace_indented("$left_rows", left_rows)
-            .replace_indented("$right_columns", right_columns)
-            .replace_indented("$right_rows", right_rows)
+            .replace_indented("$left_columns", pin_names(left_columns))
+            .replace_indented("$left_rows", pin_names(left_rows))
+            .replace_indented("$right_columns", pin_names(right_columns))
+            .replace_indented("$right_rows", pin_names(right_rows))
             .replace_indented("$layout", self.layout())
-            .replace("$left_bootmagic_matrix", &left_bootmagic_matrix)
-            .replace("$right_bootmagic_matrix", &right_bootmagic_matrix)
+            .replace("$name", &Value::from(firmware.name.as_str()).to_string())
+            .replace(
+                "$maintainer",
+                &Value::from(firmware.maintainer.as_str()).to_string(),
+            )
+            .replace("$vendor_id", &firmware.vendor_id.to_string())
+            .replace("$product_id", &firmware.product_id.to_string())
+            .replace("$version", &firmware.version.to_string())
+            .replace("$processor", board.processor())
+            .replace("$bootloader", board.bootloader())
+            .replace("$handedness_pin", board.handedness_pin())
+            .replace("$serial_driver", board.serial_driver())
+            .replace("$left_bootmagic_matrix", &bootmagic_matrix(Side::Left))
+            .replace("$right_bootmagic_matrix", &bootmagic_matrix(Side::Right))
     }
 
     /// Returns the layout given the matrix parameters.
+    ///
+    /// The keys are placed according to their positions projected onto the plane of the keyboard,
+    /// with the halves mirroring each other.
     #[must_use]
-    fn layout(&self) -> Vec<Key> {
-        const CENTER_PADDING: usize = 3;
-
-        #[allow(clippy::cast_precision_loss)]
-        let (row_offset, thumb_key_offset, matrix_row_offset, matrix_thumb_key_offset) =
-            if self.columns >= self.thumb_keys {
-                (
-                    0.0,
-                    (self.columns - self.thumb_keys) as f32 + 0.5,
-                    0,
-                    self.columns - self.thumb_keys,
+    pub fn layout(&self) -> Vec<Key> {
+        let matrix = &self.matrix;
+        let rows = matrix.rows();
+        let columns = matrix.columns();
+        let thumb_key_count = matrix.thumb_keys();
+
+        let finger_keys = (0..rows).flat_map(|row| {
+            let matrix_row = rows - 1 - row;
+
+            (0..columns)
+                .rev()
+                .filter_map(move |column| {
+                    self.finger_key_position(column, matrix_row)
+                        .map(|position| {
+                            let matrix_position = self.split_matrix_position(
+                                Side::Left,
+                                matrix.finger_key_position(Side::Left, column, matrix_row),
+                            );
+                            let keymap_position = KeymapPosition::Finger {
+                                row,
+                                column: columns - 1 - column,
+                            };
+
+                            PlacedKey::new(
+                                (matrix_position, keymap_position),
+                                Side::Left,
+                                position,
+                                None,
+                            )
+                        })
+                })
+                .chain((0..columns).filter_map(move |column| {
+                    self.finger_key_position(column, matrix_row)
+                        .map(|position| {
+                            let matrix_position = self.split_matrix_position(
+                                Side::Right,
+                                matrix.finger_key_position(Side::Right, column, matrix_row),
+                            );
+                            let keymap_position = KeymapPosition::Finger {
+                                row,
+                                column: columns + column,
+                            };
+
+                            PlacedKey::new(
+                                (matrix_position, keymap_position),
+                                Side::Right,
+                                position,
+                                None,
+                            )
+                        })
+                }))
+        });
+        let thumb_keys = (0..thumb_key_count)
+            .rev()
+            .map(|key| {
+                let matrix_position = self
+                    .split_matrix_position(Side::Left, matrix.thumb_key_position(Side::Left, key));
+                let keymap_position = KeymapPosition::Thumb(thumb_key_count - 1 - key);
+                let position = self.key_positions.thumb_keys[key];
+
+                PlacedKey::new(
+                    (matrix_position, keymap_position),
+                    Side::Left,
+                    position,
+                    self.thumb_key_height,
                 )
-            } else {
-                (
-                    (self.thumb_keys - self.columns) as f32 - 0.5,
-                    0.0,
-                    self.thumb_keys - self.columns,
-                    0,
+            })
+            .chain((0..thumb_key_count).map(|key| {
+                let matrix_position = self.split_matrix_position(
+                    Side::Right,
+                    matrix.thumb_key_position(Side::Right, key),
+                );
+                let keymap_position = KeymapPosition::Thumb(thumb_key_count + key);
+                let position = self.key_positions.thumb_keys[key];
+
+                PlacedKey::new(
+                    (matrix_position, keymap_position),
+                    Side::Right,
+                    position,
+                    self.thumb_key_height,
                 )
-            };
-
-        #[allow(clippy::cast_precision_loss)]
-        (0..self.rows)
-            .flat_map(|row| {
-                (0..self.columns)
-                    .map(move |column| {
-                        let matrix_position = (self.rows - row, matrix_row_offset + column);
-                        let x = row_offset + column as f32;
-                        let y = row as f32;
-
-                        Key::finger(matrix_position, x, y)
-                    })
-                    .chain((0..self.columns).map(move |column| {
-                        let matrix_position = (2 * self.rows + 1 - row, matrix_row_offset + column);
-                        let x = row_offset + (CENTER_PADDING + 1 + self.columns + column) as f32;
-                        let y = row as f32;
-
-                        Key::finger(matrix_position, x, y)
-                    }))
+            }));
+
+        let placed_keys: Vec<_> = finger_keys.chain(thumb_keys).collect();
+
+        self.arrange(&placed_keys)
+    }
+
+    /// Returns the position of the finger key in the given column and matrix row if there is one.
+    ///
+    /// The columns are indexed from the inside out.
+    fn finger_key_position(&self, column: usize, matrix_row: usize) -> Option<DAffine3> {
+        let rows = &self.matrix.column_rows()[column];
+
+        rows.contains(&matrix_row)
+            .then(|| self.key_positions.columns[column][matrix_row - rows.start])
+    }
+
+    /// Returns the position of the bootmagic key of the given side in the split matrix spanning
+    /// both halves.
+    ///
+    /// This is the topmost key of the outermost column, which exists even if the columns have
+    /// different numbers of rows.
+    fn bootmagic_position(&self, side: Side) -> (usize, usize) {
+        let column = self.matrix.columns() - 1;
+        let row = self.matrix.column_rows()[column].end - 1;
+
+        self.split_matrix_position(side, self.matrix.finger_key_position(side, column, row))
+    }
+
+    /// Returns the position in the split matrix spanning both halves for the given position in the
+    /// matrix of the given side.
+    ///
+    /// The rows of the right half follow the ones of the left half.
+    fn split_matrix_position(&self, side: Side, (row, column): (usize, usize)) -> (usize, usize) {
+        match side {
+            Side::Left => (row, column),
+            Side::Right => (self.matrix.row_count() + row, column),
+        }
+    }
+
+    /// Arranges the given placed keys in units, starting at the origin.
+    ///
+    /// The halves are moved apart such that there is a gap between them.
+    fn arrange(&self, placed_keys: &[PlacedKey]) -> Vec<Key> {
+        /// The minimum distance between the two halves in units.
+        const CENTER_PADDING: f64 = 2.0;
+
+        let inner_x = placed_keys
+            .iter()
+            .filter(|key| matches!(key.side, Side::Right))
+            .map(|key| key.center.x / self.key_distance.x - 0.5)
+            .fold(f64::INFINITY, f64::min);
+        let half_offset = CENTER_PADDING / 2.0 - inner_x;
+
+        let keys: Vec<_> = placed_keys
+            .iter()
+            .map(|key| {
+                let center = key.center / self.key_distance + dvec2(half_offset, 0.0);
+                let (center, rotation) = match key.side {
+                    Side::Left => (dvec2(-center.x, -center.y), key.angle),
+                    Side::Right => (dvec2(center.x, -center.y), -key.angle),
+                };
+                let size = dvec2(1.0, key.height.unwrap_or(1.0));
+
+                (key, center - size / 2.0, rotation)
+            })
+            .collect();
+
+        let origin = keys.iter().fold(DVec2::INFINITY, |origin, (_, corner, _)| {
+            origin.min(*corner)
+        });
+
+        keys.into_iter()
+            .map(|(key, corner, rotation)| {
+                let corner = corner - origin;
+
+                Key {
+                    matrix_position: key.matrix_position,
+                    keymap_position: key.keymap_position,
+                    x: round(corner.x),
+                    y: round(corner.y),
+                    height: key.height,
+                    rotation: round(rotation),
+                }
             })
-            .chain((0..self.thumb_keys).map(move |key| {
-                let matrix_position = (0, matrix_thumb_key_offset + key);
-                let x = thumb_key_offset + key as f32;
-                let y = self.rows as f32;
-
-                Key::thumb(matrix_position, x, y)
-            }))
-            .chain((0..self.thumb_keys).map(move |key| {
-                let matrix_position = (self.rows + 1, matrix_row_offset + key);
-                let x = thumb_key_offset + (CENTER_PADDING + self.thumb_keys + key) as f32;
-                let y = self.rows as f32;
-
-                Key::thumb(matrix_position, x, y)
-            }))
             .collect()
     }
 }
 
-// A key in a layout.
-struct Key {
+/// A key placed on the plane of the right half of the keyboard.
+struct PlacedKey {
     matrix_position: (usize, usize),
-    x: f32,
-    y: f32,
-    height: Option<f32>,
+    keymap_position: KeymapPosition,
+    side: Side,
+    center: DVec2,
+    angle: f64,
+    height: Option<f64>,
 }
 
-impl Key {
-    /// Creates a new finger key with the given matrix and key positions.
-    pub fn finger(matrix_position: (usize, usize), x: f32, y: f32) -> Self {
+impl PlacedKey {
+    /// Creates a new placed key with the given matrix and keymap position from the given position
+    /// in 3D space.
+    fn new(
+        (matrix_position, keymap_position): ((usize, usize), KeymapPosition),
+        side: Side,
+        position: DAffine3,
+        height: Option<f64>,
+    ) -> Self {
+        let x_axis = position.matrix3.x_axis;
+
         Self {
             matrix_position,
-            x,
-            y,
-            height: None,
+            keymap_position,
+            side,
+            center: position.translation.truncate(),
+            angle: x_axis.y.atan2(x_axis.x).to_degrees(),
+            height,
         }
     }
+}
 
-    /// Creates a new thumb key with the given matrix and key positions.
-    pub fn thumb(matrix_position: (usize, usize), x: f32, y: f32) -> Self {
-        Self {
-            matrix_position,
-            x,
-            y,
-            height: Some(1.5),
-        }
+/// A key in a layout.
+pub struct Key {
+    /// The row and column of the key in the matrix.
+    pub matrix_position: (usize, usize),
+    /// The position of the key in the keymap.
+    pub keymap_position: KeymapPosition,
+    /// The X position of the top left corner of the unrotated key in units.
+    pub x: f64,
+    /// The Y position of the top left corner of the unrotated key in units.
+    pub y: f64,
+    /// The height of the key in units if it differs from 1.
+    pub height: Option<f64>,
+    /// The clockwise rotation of the key around its center in degrees.
+    pub rotation: f64,
+}
+
+impl Key {
+    /// Returns the center of the key in units.
+    pub fn center(&self) -> (f64, f64) {
+        (
+            round(self.x + 0.5),
+            round(self.y + self.height.unwrap_or(1.0) / 2.0),
+        )
     }
 }
 
@@ -155,18 +322,45 @@ impl Display for Key {
             x,
             y,
             height,
+            rotation,
+            ..
         } = self;
 
+        write!(
+            f,
+            "{{\"matrix\": [{matrix_x}, {matrix_y}], \"x\": {x}, \"y\": {y}"
+        )?;
         if let Some(height) = height {
+            write!(f, ", \"h\": {height}")?;
+        }
+        if *rotation != 0.0 {
+            let (rotation_x, rotation_y) = self.center();
             write!(
                 f,
-                "{{\"matrix\": [{matrix_x}, {matrix_y}], \"x\": {x}, \"y\": {y}, \"h\": {height}}}"
-            )
-        } else {
-            write!(
-                f,
-                "{{\"matrix\": [{matrix_x}, {matrix_y}], \"x\": {x}, \"y\": {y}}}"
-            )
+                ", \"r\": {rotation}, \"rx\": {rotation_x}, \"ry\": {rotation_y}"
+            )?;
         }
+        write!(f, "}}")
     }
 }
+
+/// The position of a key in a keymap.
+#[derive(Clone, Copy)]
+pub enum KeymapPosition {
+    /// A finger key with the row indexed from the top and the column from the left across both
+    /// halves.
+    Finger {
+        /// The row of the key.
+        row: usize,
+        /// The column of the key.
+        column: usize,
+    },
+    /// A thumb key with the index from the left across both halves.
+    Thumb(usize),
+}
+
+/// Rounds the given value to two decimal places.
+pub fn round(value: f64) -> f64 {
+    // Adding zero turns negative zero into positive zero
+    (value * 100.0).round() / 100.0 + 0.0
+}
@@ -1,133 +1,129 @@
-use std::{
-    fmt::Write,
-    iter::{once, repeat_n},
-};
-
-use itertools::Itertools;
-
-static FULL_SIZE_ROWS: [[&str; 12]; 5] = [
-    [
-        "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
-    ],
-    [
-        "ESC", "1", "2", "3", "4", "5", "6", "7", "8", "9", "0", "BSPC",
-    ],
-    [
-        "TAB", "Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "ENT",
-    ],
-    [
-        "CAPS", "A", "S", "D", "F", "G", "H", "J", "K", "L", "SCLN", "QUOT",
-    ],
-    [
-        "LSFT", "Z", "X", "C", "V", "B", "N", "M", "COMM", "DOT", "SLSH", "RSFT",
-    ],
-];
-
-static FULL_SIZE_THUMB_KEYS: [&str; 12] = [
-    "NO", "NO", "LCTL", "LGUI", "LALT", "SPC", "SPC", "RALT", "RGUI", "RCTL", "NO", "NO",
-];
-
-// A keymap consisting of finger and thumb keys.
+use std::fmt::Write;
+
+use config::KeymapSize;
+use matrix::Matrix;
+
+/// A keymap consisting of layers of finger and thumb keys.
 pub struct Keymap {
-    rows: Vec<Row>,
-    thumb_keys: Row,
+    layers: Vec<Layer>,
     column_count: usize,
     thumb_key_count: usize,
 }
 
 impl Keymap {
-    /// Creates a new keymap from the given matrix parameters.
-    pub fn new(column_count: usize, row_count: usize, thumb_key_count: usize) -> Self {
-        let rows = FULL_SIZE_ROWS
-            .iter()
-            .skip(FULL_SIZE_ROWS.len() - row_count)
-            .map(|full_size_row| Row::from_full_size(full_size_row, column_count))
+    /// Creates a new keymap from the given keymap configuration for the given key matrix.
+    pub fn new(keymap: &config::Keymap, size: KeymapSize, matrix: &Matrix) -> Self {
+        let layers = keymap
+            .layer_names()
+            .into_iter()
+            .enumerate()
+            .map(|(layer, name)| {
+                let rows = (0..size.rows)
+                    .map(|row| {
+                        let matrix_row = size.rows - 1 - row;
+
+                        Row::new(
+                            size.columns,
+                            |column| keymap.finger_keycode(layer, size, row, column),
+                            |column| matrix.has_key(column, matrix_row),
+                        )
+                    })
+                    .collect();
+                let thumb_keys = Row::new(
+                    size.thumb_keys,
+                    |index| keymap.thumb_keycode(layer, size, index),
+                    |_| true,
+                );
+
+                Layer {
+                    name,
+                    rows,
+                    thumb_keys,
+                }
+            })
             .collect();
-        let thumb_keys = Row::from_full_size(&FULL_SIZE_THUMB_KEYS, thumb_key_count);
 
         Self {
-            rows,
-            thumb_keys,
-            column_count,
-            thumb_key_count,
+            layers,
+            column_count: size.columns,
+            thumb_key_count: size.thumb_keys,
         }
     }
 
     /// Generates the content of the `keymap.c` file.
     pub fn to_file(&self) -> String {
-        let comment = self.to_comment();
-        let layout_array = self.to_layout_array();
-
-        format!(
-            "#include QMK_KEYBOARD_H\n\
-            \n\
-            const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {{\n\
-            /*\n{comment} */\n\
-            [0] = LAYOUT(\n{layout_array}),\n\
-            }};\n"
-        )
-    }
-
-    /// Returns a comment depicting the keymap.
-    fn to_comment(&self) -> String {
-        const CENTER_PADDING: usize = 10;
-
-        let (row_padding, thumb_key_padding) = if self.column_count >= self.thumb_key_count {
-            (0, 4 * (self.column_count - self.thumb_key_count) + 2)
-        } else {
-            (4 * (self.thumb_key_count - self.column_count) - 2, 0)
-        };
+        let mut out = "#include QMK_KEYBOARD_H\n\nenum layers {\n".to_owned();
 
-        let mut out = String::new();
+        for layer in &self.layers {
+            writeln!(out, "    {},", layer.name).expect("format should never fail");
+        }
 
-        let top_border = comment_top_border(self.column_count);
-        let center_border = comment_center_border(self.column_count);
+        out += "};\n\nconst uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {\n";
 
-        let row_comments: Vec<_> = self
-            .rows
-            .iter()
-            .map(|row| (row.left.to_comment(), row.right.to_comment()))
-            .collect();
+        for layer in &self.layers {
+            let comment = self.to_comment(layer);
+            let layout_array = self.to_layout_array(layer);
 
-        for (left, right) in once((&top_border, &top_border)).chain(Itertools::intersperse(
-            row_comments.iter().map(|(left, right)| (left, right)),
-            (&center_border, &center_border),
-        )) {
-            writeln!(
+            write!(
                 out,
-                " * {:row_padding$}{left}  {:CENTER_PADDING$}  {right}",
-                "", ""
+                "/*\n{comment} */\n\
+                [{name}] = LAYOUT(\n{layout_array}),\n",
+                name = layer.name
             )
             .expect("format should never fail");
         }
 
-        let (left, right) = comment_cluster_borders(self.column_count, self.thumb_key_count);
-        writeln!(out, " * {left}{:CENTER_PADDING$}{right}", "").expect("format should never fail");
+        out + "};\n"
+    }
+
+    /// Returns a comment depicting the given layer.
+    ///
+    /// Only existing keys are drawn, leaving the cells of missing keys blank.
+    fn to_comment(&self, layer: &Layer) -> String {
+        const CENTER_PADDING: usize = 10;
+
+        let key_width = HalfRow::COMMENT_COLUMN_WIDTH;
+        let row_width = key_width * self.column_count;
+        let thumb_keys_width = key_width * self.thumb_key_count;
+
+        // The thumb keys are shifted half a key towards the center relative to the finger keys
+        let left_row_offset = thumb_keys_width.saturating_sub(row_width + key_width / 2);
+        let left_thumb_key_offset = left_row_offset + row_width + key_width / 2 - thumb_keys_width;
+        let left_width = left_row_offset + row_width + key_width / 2 + 1;
+
+        let mut left = Canvas::default();
+        let mut right = Canvas::default();
+
+        for (index, row) in layer.rows.iter().enumerate() {
+            row.left
+                .draw_comment(&mut left, left_row_offset, 2 * index, 2);
+            row.right
+                .draw_comment(&mut right, key_width / 2, 2 * index, 2);
+        }
+
+        let thumb_keys_y = 2 * layer.rows.len();
+        layer
+            .thumb_keys
+            .left
+            .draw_comment(&mut left, left_thumb_key_offset, thumb_keys_y, 3);
+        layer
+            .thumb_keys
+            .right
+            .draw_comment(&mut right, 0, thumb_keys_y, 3);
 
-        let thumb_keys_border = comment_thumb_keys_border(self.thumb_key_count);
-        let bottom_border = comment_bottom_border(self.thumb_key_count);
+        let mut out = String::new();
 
-        for (left, right) in [
-            (&thumb_keys_border, &thumb_keys_border),
-            (
-                &self.thumb_keys.left.to_comment(),
-                &self.thumb_keys.right.to_comment(),
-            ),
-            (&bottom_border, &bottom_border),
-        ] {
-            writeln!(
-                out,
-                " * {:thumb_key_padding$}{left}{:CENTER_PADDING$}{right}",
-                "", ""
-            )
-            .expect("format should never fail");
+        for (left, right) in left.to_lines().into_iter().zip(right.to_lines()) {
+            let line = format!(" * {left:left_width$}{:CENTER_PADDING$}{right}", "");
+
+            writeln!(out, "{}", line.trim_end()).expect("format should never fail");
         }
 
         out
     }
 
-    /// Returns the layout array of the keymap.
-    fn to_layout_array(&self) -> String {
+    /// Returns the layout array of the given layer.
+    fn to_layout_array(&self, layer: &Layer) -> String {
         const CENTER_PADDING: usize = 10;
 
         let row_center_padding = CENTER_PADDING + HalfRow::LAYOUT_ARRAY_COLUMN_WIDTH;
@@ -151,7 +147,7 @@ impl Keymap {
 
         let mut out = String::new();
 
-        for row in &self.rows {
+        for row in &layer.rows {
             let left = row.left.to_layout_array() + ",";
             let right = row.right.to_layout_array();
 
@@ -163,8 +159,8 @@ impl Keymap {
             .expect("format should never fail");
         }
 
-        let left = self.thumb_keys.left.to_layout_array() + ",";
-        let right = self.thumb_keys.right.to_layout_array();
+        let left = layer.thumb_keys.left.to_layout_array() + ",";
+        let right = layer.thumb_keys.right.to_layout_array();
         writeln!(
             out,
             "{:thumb_key_padding$}{left:<thumb_keys_width$}{:CENTER_PADDING$}{right}",
@@ -176,6 +172,13 @@ impl Keymap {
     }
 }
 
+// A named layer consisting of finger and thumb keys.
+struct Layer {
+    name: String,
+    rows: Vec<Row>,
+    thumb_keys: Row,
+}
+
 // A row consisting of a left and right half row.
 struct Row {
     left: HalfRow,
@@ -183,60 +186,77 @@ struct Row {
 }
 
 impl Row {
-    /// Creates a new row with the given number of columns from a full size one.
-    pub fn from_full_size(default_row: &[&'static str], columns: usize) -> Self {
-        let (left, right) = default_row.split_at(default_row.len() / 2);
-
-        let left = HalfRow::new(left.iter().rev().take(columns).rev().copied());
-        let right = HalfRow::new(right.iter().take(columns).copied());
+    /// Creates a new row with the given number of columns per half.
+    ///
+    /// The keycodes are indexed from the left across both halves. Only the columns for which
+    /// `has_key` returns true contain a key, with the columns being indexed from the inside out.
+    pub fn new<'a>(
+        columns: usize,
+        keycode: impl Fn(usize) -> &'a str,
+        has_key: impl Fn(usize) -> bool,
+    ) -> Self {
+        let left = HalfRow::new(
+            (0..columns).map(|column| has_key(columns - 1 - column).then(|| keycode(column))),
+        );
+        let right = HalfRow::new(
+            (0..columns).map(|column| has_key(column).then(|| keycode(columns + column))),
+        );
 
         Self { left, right }
     }
 }
 
-// A half row consisting of keys.
+// A half row consisting of keys, with missing keys being `None`.
 struct HalfRow {
-    keys: Vec<Key>,
+    keys: Vec<Option<Key>>,
 }
 
 impl HalfRow {
+    /// The width of a column in the comment, including one of its borders.
+    pub const COMMENT_COLUMN_WIDTH: usize = 4;
     /// The width of a column in the layout array
     pub const LAYOUT_ARRAY_COLUMN_WIDTH: usize = 12;
 
     /// Creates a new half from the given keys.
-    pub fn new(keys: impl IntoIterator<Item = impl Into<Key>>) -> Self {
+    pub fn new(keys: impl IntoIterator<Item = Option<impl Into<Key>>>) -> Self {
         Self {
-            keys: keys.into_iter().map(Into::into).collect(),
+            keys: keys.into_iter().map(|key| key.map(Into::into)).collect(),
         }
     }
 
-    /// Returns the comment representation of the half row.
-    pub fn to_comment(&self) -> String {
-        let mut out = "│".to_owned();
+    /// Draws the boxes of the keys of the half row with the given height onto the given canvas,
+    /// starting at the given position.
+    pub fn draw_comment(&self, canvas: &mut Canvas, x: usize, y: usize, height: usize) {
+        for (index, key) in self.keys.iter().enumerate() {
+            if let Some(key) = key {
+                let label = format!("{:^3}", key.to_comment());
 
-        for key in &self.keys {
-            let comment = key.to_comment();
-            write!(out, "{comment:^3}│").expect("format should never fail");
+                canvas.draw_box(x + Self::COMMENT_COLUMN_WIDTH * index, y, height, &label);
+            }
         }
-
-        out
     }
 
     /// Returns the layout array representation of the half row.
     pub fn to_layout_array(&self) -> String {
+        let last_index = self
+            .keys
+            .iter()
+            .rposition(Option::is_some)
+            .expect("there is always at least one key");
         let mut out = String::new();
 
-        for key in &self.keys[..self.keys.len() - 1] {
-            let keycode = key.to_keycode() + ",";
+        for key in &self.keys[..last_index] {
+            let keycode = key
+                .as_ref()
+                .map(|key| key.to_keycode() + ",")
+                .unwrap_or_default();
 
             write!(out, "{keycode:<0$}", Self::LAYOUT_ARRAY_COLUMN_WIDTH)
                 .expect("format should never fail");
         }
-        out += &self
-            .keys
-            .last()
-            .expect("there is always at least one key")
-            .to_keycode();
+        if let Some(key) = &self.keys[last_index] {
+            out += &key.to_keycode();
+        }
 
         out
     }
@@ -244,13 +264,21 @@ impl HalfRow {
 
 // A single key.
 struct Key {
-    id: &'static str,
+    id: String,
 }
 
 impl Key {
     // Converts a key to its comment representation.
-    pub fn to_comment(&self) -> &'static str {
-        match self.id {
+    pub fn to_comment(&self) -> String {
+        let id = self.id.replace("KC_", "").replace("QK_", "");
+
+        // Show the argument of functions like layer switches or mod-taps
+        let id = match id.split_once('(') {
+            Some((_, argument)) => argument.trim_end_matches(')'),
+            None => &id,
+        };
+
+        match id {
             "BSPC" => "BSP",
             "CAPS" => "CPS",
             "SCLN" => ";",
@@ -262,92 +290,144 @@ impl Key {
             "LCTL" | "RCTL" => "CTL",
             "LGUI" | "RGUI" => "GUI",
             "LALT" | "RALT" => "ALT",
-            "NO" => "",
+            "NO" | "TRNS" => "",
             key => key,
         }
+        .chars()
+        .take(3)
+        .collect()
     }
 
     /// Returns the keycode of the key.
+    ///
+    /// Basic keycodes are given without the `KC_` prefix, all others are used as is.
     pub fn to_keycode(&self) -> String {
-        format!("KC_{id}", id = self.id)
+        if self
+            .id
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric())
+        {
+            format!("KC_{id}", id = self.id)
+        } else {
+            self.id.clone()
+        }
     }
 }
 
-impl From<&'static str> for Key {
-    fn from(id: &'static str) -> Self {
-        Self { id }
+impl From<&str> for Key {
+    fn from(id: &str) -> Self {
+        Self { id: id.to_owned() }
     }
 }
 
-// Returns the top border of a half row with the given number of keys.
-fn comment_top_border(keys: usize) -> String {
-    once("┌")
-        .chain(repeat_n("┬", keys - 1))
-        .chain(once("┐"))
-        .join("───")
+/// A canvas for drawing boxes using box-drawing characters.
+#[derive(Default)]
+struct Canvas {
+    lines: Vec<Vec<Cell>>,
 }
 
-// Returns the center border of a half row with the given number of keys.
-fn comment_center_border(keys: usize) -> String {
-    once("├")
-        .chain(repeat_n("┼", keys - 1))
-        .chain(once("┤"))
-        .join("───")
-}
+impl Canvas {
+    /// Draws a box of the given height with the given label in its last line, starting at the
+    /// given position.
+    ///
+    /// Borders of adjacent boxes are merged.
+    pub fn draw_box(&mut self, x: usize, y: usize, height: usize, label: &str) {
+        let width = HalfRow::COMMENT_COLUMN_WIDTH;
 
-// Returns the bottom border of a half row with the given number of keys.
-fn comment_bottom_border(keys: usize) -> String {
-    once("└")
-        .chain(repeat_n("┴", keys - 1))
-        .chain(once("┘"))
-        .join("───")
-}
+        for offset in 0..=width {
+            let left = if offset > 0 { Cell::LEFT } else { 0 };
+            let right = if offset < width { Cell::RIGHT } else { 0 };
+
+            self.add_lines(x + offset, y, left | right);
+            self.add_lines(x + offset, y + height, left | right);
+        }
+        for offset in 0..=height {
+            let up = if offset > 0 { Cell::UP } else { 0 };
+            let down = if offset < height { Cell::DOWN } else { 0 };
+
+            self.add_lines(x, y + offset, up | down);
+            self.add_lines(x + width, y + offset, up | down);
+        }
+        for (offset, character) in label.chars().enumerate() {
+            *self.cell(x + 1 + offset, y + height - 1) = Cell::Character(character);
+        }
+    }
+
+    /// Returns the lines of the canvas without trailing whitespace.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .map(|line| {
+                let line: String = line.iter().map(|cell| cell.to_char()).collect();
+
+                line.trim_end().to_owned()
+            })
+            .collect()
+    }
+
+    /// Adds the given lines leaving the cell at the given position.
+    fn add_lines(&mut self, x: usize, y: usize, lines: u8) {
+        let cell = self.cell(x, y);
+
+        *cell = match *cell {
+            Cell::Lines(existing) => Cell::Lines(existing | lines),
+            _ => Cell::Lines(lines),
+        };
+    }
 
-// Returns the center border of a thumb cluster with the given number of keys.
-fn comment_thumb_keys_border(keys: usize) -> String {
-    repeat_n("│", keys + 1).join("   ")
+    /// Returns the cell at the given position, growing the canvas if necessary.
+    fn cell(&mut self, x: usize, y: usize) -> &mut Cell {
+        if self.lines.len() <= y {
+            self.lines.resize_with(y + 1, Vec::new);
+        }
+
+        let line = &mut self.lines[y];
+        if line.len() <= x {
+            line.resize(x + 1, Cell::Empty);
+        }
+
+        &mut line[x]
+    }
 }
 
-// Returns the left and right borders between finger and thumb clusters.
-fn comment_cluster_borders(columns: usize, thumb_keys: usize) -> (String, String) {
-    if columns >= thumb_keys {
-        let left = once("└")
-            .chain(
-                repeat_n("─", columns - thumb_keys)
-                    .chain(repeat_n("┬", thumb_keys))
-                    .interleave(repeat_n("┴", columns)),
-            )
-            .chain(once("┐"))
-            .join("─");
-
-        let right =
-            once("┌")
-                .chain(repeat_n("┴", columns).interleave(
-                    repeat_n("┬", thumb_keys).chain(repeat_n("─", columns - thumb_keys)),
-                ))
-                .chain(once("┘"))
-                .join("─");
-
-        (left, right)
-    } else {
-        let left = once("┌")
-            .chain(
-                repeat_n("─", thumb_keys - columns - 1)
-                    .chain(repeat_n("┴", columns + 1))
-                    .interleave(repeat_n("┬", thumb_keys - 1)),
-            )
-            .chain(once("┐"))
-            .join("─");
-
-        let right = once("┌")
-            .chain(
-                repeat_n("┴", columns + 1)
-                    .chain(repeat_n("─", thumb_keys - columns - 1))
-                    .interleave(repeat_n("┬", thumb_keys - 1)),
-            )
-            .chain(once("┐"))
-            .join("─");
+/// A cell of a canvas.
+#[derive(Clone, Copy)]
+enum Cell {
+    /// An empty cell.
+    Empty,
+    /// A cell containing a character.
+    Character(char),
+    /// A cell containing lines leaving it in the directions given by a combination of the flags.
+    Lines(u8),
+}
 
-        (left, right)
+impl Cell {
+    const UP: u8 = 1;
+    const DOWN: u8 = 2;
+    const LEFT: u8 = 4;
+    const RIGHT: u8 = 8;
+
+    /// Returns the character depicting the cell.
+    fn to_char(self) -> char {
+        const UP_DOWN: u8 = Cell::UP | Cell::DOWN;
+        const LEFT_RIGHT: u8 = Cell::LEFT | Cell::RIGHT;
+
+        match self {
+            Self::Empty => ' ',
+            Self::Character(character) => character,
+            Self::Lines(lines) => match (lines & UP_DOWN, lines & LEFT_RIGHT) {
+                (0, _) => '─',
+                (_, 0) => '│',
+                (Self::DOWN, Self::RIGHT) => '┌',
+                (Self::DOWN, Self::LEFT) => '┐',
+                (Self::UP, Self::RIGHT) => '└',
+                (Self::UP, Self::LEFT) => '┘',
+                (UP_DOWN, Self::RIGHT) => '├',
+                (UP_DOWN, Self::LEFT) => '┤',
+                (Self::DOWN, _) => '┬',
+                (Self::UP, _) => '┴',
+                _ => '┼',
+            },
+        }
     }
 }
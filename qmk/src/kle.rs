@@ -0,0 +1,229 @@
+use std::fmt::Write;
+
+use config::{Keymap, KeymapSize};
+use glam::{dvec2, DVec2};
+use serde_json::{Map, Value};
+
+use crate::{
+    keyboard::{round, Key, KeymapPosition},
+    replace_indented::ReplaceIndented,
+};
+
+/// The number of legend positions on a keyboard-layout-editor key.
+const LEGEND_POSITIONS: usize = 12;
+
+/// An error type for errors which can occur when importing a keyboard-layout-editor layout.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Failed to parse the JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The JSON is not a keyboard-layout-editor layout.
+    #[error("file is not a keyboard-layout-editor layout")]
+    InvalidLayout,
+    /// A key of the layout has more legends than there are legend positions.
+    #[error("key has more than {LEGEND_POSITIONS} legends")]
+    TooManyLegends,
+    /// No key of the layout matches a key of the keyboard.
+    #[error("no key of the layout matches a key of the keyboard")]
+    NoMatchingKeys,
+}
+
+/// Returns the content of the keyboard-layout-editor JSON file for the given keys.
+///
+/// The layout is named after the given name and the keys are labeled with their keycodes in the
+/// layers of the given keymap, using one legend position per layer.
+pub fn to_file(name: &str, keys: &[Key], keymap: &Keymap, size: KeymapSize) -> String {
+    let layers = keymap.layer_names().len().min(LEGEND_POSITIONS);
+    let rows = rows(keys, |key| {
+        (0..layers)
+            .map(|layer| match key.keymap_position {
+                KeymapPosition::Finger { row, column } => {
+                    keymap.finger_keycode(layer, size, row, column)
+                }
+                KeymapPosition::Thumb(index) => keymap.thumb_keycode(layer, size, index),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    include_str!("kle.json")
+        .replace_indented("$rows", rows)
+        .replace("$name", &Value::from(name).to_string())
+}
+
+/// Returns the rows of a keyboard-layout-editor layout containing the given keys.
+///
+/// Each key is put in a separate row with its own rotation origin, which keeps the positions
+/// absolute. The keys are labeled using the given function.
+pub fn rows(keys: &[Key], legend: impl Fn(&Key) -> String) -> Vec<String> {
+    keys.iter()
+        .map(|key| {
+            let (rotation_x, rotation_y) = key.center();
+            let mut properties = format!(
+                "\"r\": {}, \"rx\": {rotation_x}, \"ry\": {rotation_y}, \"x\": {}, \"y\": {}",
+                key.rotation,
+                round(key.x - rotation_x),
+                round(key.y - rotation_y),
+            );
+            if let Some(height) = key.height {
+                write!(properties, ", \"h\": {height}").expect("format should never fail");
+            }
+
+            format!("[{{{properties}}}, {}]", Value::from(legend(key)))
+        })
+        .collect()
+}
+
+/// Returns the given keymap with the keycodes taken from the legends of the given
+/// keyboard-layout-editor JSON file.
+///
+/// Each legend position of a key corresponds to the keymap layer with the same index, empty
+/// legends keep the keycode of the given keymap. Each key of the file is matched with the closest
+/// of the given keys, keys of the file without a close match are ignored.
+pub fn import(keys: &[Key], keymap: &Keymap, json: &str) -> Result<Keymap, Error> {
+    /// The maximum distance between the centers of matching keys in units.
+    const MAX_DISTANCE: f64 = 0.5;
+
+    let legends = legends(&serde_json::from_str(json)?)?;
+    let mut keymap = keymap.clone();
+    let mut matched = false;
+
+    for (center, labels) in legends {
+        let closest_key = keys
+            .iter()
+            .map(|key| {
+                let (x, y) = key.center();
+
+                (key, center.distance(dvec2(x, y)))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((key, _)) = closest_key.filter(|(_, distance)| *distance < MAX_DISTANCE) {
+            let layers = labels
+                .into_iter()
+                .enumerate()
+                .filter(|(_, label)| !label.is_empty());
+
+            for (layer, label) in layers {
+                match key.keymap_position {
+                    KeymapPosition::Finger { row, column } => {
+                        keymap.set_finger_keycode(layer, row, column, label);
+                    }
+                    KeymapPosition::Thumb(index) => keymap.set_thumb_keycode(layer, index, label),
+                }
+            }
+            matched = true;
+        }
+    }
+
+    if matched {
+        Ok(keymap)
+    } else {
+        Err(Error::NoMatchingKeys)
+    }
+}
+
+/// Returns the centers and the labels per legend position of the keys in the given
+/// keyboard-layout-editor layout.
+fn legends(layout: &Value) -> Result<Vec<(DVec2, Vec<String>)>, Error> {
+    let rows = layout.as_array().ok_or(Error::InvalidLayout)?;
+    let mut cursor = Cursor::default();
+    let mut legends = Vec::new();
+
+    // Objects outside of rows contain metadata about the whole layout
+    for row in rows.iter().filter_map(Value::as_array) {
+        for item in row {
+            match item {
+                Value::Object(properties) => cursor.apply(properties),
+                Value::String(labels) => {
+                    let labels: Vec<_> = labels
+                        .split('\n')
+                        .map(|label| label.trim().to_owned())
+                        .collect();
+                    if labels.len() > LEGEND_POSITIONS {
+                        return Err(Error::TooManyLegends);
+                    }
+
+                    legends.push((cursor.key_center(), labels));
+                    cursor.next_key();
+                }
+                _ => return Err(Error::InvalidLayout),
+            }
+        }
+
+        cursor.next_row();
+    }
+
+    Ok(legends)
+}
+
+/// The current position and properties while reading a keyboard-layout-editor layout.
+struct Cursor {
+    position: DVec2,
+    size: DVec2,
+    rotation: f64,
+    rotation_origin: DVec2,
+}
+
+impl Cursor {
+    /// Applies the given key properties.
+    fn apply(&mut self, properties: &Map<String, Value>) {
+        let property = |name| properties.get(name).and_then(Value::as_f64);
+
+        if let Some(rotation) = property("r") {
+            self.rotation = rotation;
+        }
+        // Changing the rotation origin also moves the position to it
+        if let Some(x) = property("rx") {
+            self.rotation_origin.x = x;
+            self.position = self.rotation_origin;
+        }
+        if let Some(y) = property("ry") {
+            self.rotation_origin.y = y;
+            self.position = self.rotation_origin;
+        }
+        if let Some(x) = property("x") {
+            self.position.x += x;
+        }
+        if let Some(y) = property("y") {
+            self.position.y += y;
+        }
+        if let Some(width) = property("w") {
+            self.size.x = width;
+        }
+        if let Some(height) = property("h") {
+            self.size.y = height;
+        }
+    }
+
+    /// Returns the center of the current key after rotating it.
+    fn key_center(&self) -> DVec2 {
+        let center = self.position + self.size / 2.0;
+
+        DVec2::from_angle(self.rotation.to_radians()).rotate(center - self.rotation_origin)
+            + self.rotation_origin
+    }
+
+    /// Moves to the next key in the same row.
+    fn next_key(&mut self) {
+        self.position.x += self.size.x;
+        self.size = DVec2::ONE;
+    }
+
+    /// Moves to the beginning of the next row.
+    fn next_row(&mut self) {
+        self.position = dvec2(self.rotation_origin.x, self.position.y + 1.0);
+    }
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self {
+            position: DVec2::ZERO,
+            size: DVec2::ONE,
+            rotation: 0.0,
+            rotation_origin: DVec2::ZERO,
+        }
+    }
+}
@@ -1,41 +1,121 @@
-//! The `qmk` crate implements the generation of QMK configuration files.
+//! The `qmk` crate implements the generation of QMK configuration files as well as the export and
+//! import of keyboard-layout-editor layouts.
 
+mod board;
 mod keyboard;
 mod keymap;
+mod kle;
 mod replace_indented;
+mod via;
 
 use config::Config;
 
-use crate::{keyboard::Keyboard, keymap::Keymap};
+use crate::{board::Board, keyboard::Keyboard, keymap::Keymap, via::Definitions};
+
+pub use kle::Error as KleError;
 
 /// A set of QMK configuration files.
+///
+/// Apart from `via.json`, the files belong into the QMK keyboard directory with the given name.
 pub struct Files {
+    /// The name of the QMK keyboard directory.
+    pub directory: String,
+    /// The content of the `readme.md` file.
+    pub readme_md: String,
+    /// The content of the `rules.mk` file.
+    pub rules_mk: &'static str,
     /// The content of the `config.h` file.
-    pub config_h: &'static str,
+    pub config_h: String,
     /// The content of the `keyboard.json` file.
     pub keyboard_json: String,
     /// The content of the `keymap.c` file.
     pub keymap_c: String,
+    /// The content of the `via.json` file for loading the keyboard in VIA.
+    pub via_json: String,
+    /// The content of the `rules.mk` file of the VIA keymap.
+    pub via_rules_mk: &'static str,
+    /// The content of the `vial.json` file of the Vial keymap.
+    pub vial_json: String,
+    /// The content of the `rules.mk` file of the Vial keymap.
+    pub vial_rules_mk: &'static str,
+    /// The content of the `config.h` file of the Vial keymap.
+    pub vial_config_h: String,
 }
 
 impl Files {
     /// Creates a set of QMK files from the given configuration.
+    ///
+    /// Returns `None` if the controller is not supported by QMK.
     #[must_use]
-    pub fn from_config(config: &Config) -> Self {
-        let columns = config.finger_cluster.columns.len();
-        #[allow(clippy::cast_sign_loss)]
-        let rows = i8::from(config.finger_cluster.rows) as usize;
-        #[allow(clippy::cast_sign_loss)]
-        let thumb_keys = i8::from(config.thumb_cluster.keys) as usize;
-
-        let config_h = include_str!("config.h");
-        let keyboard_json = Keyboard::new(columns, rows, thumb_keys).to_file();
-        let keymap_c = Keymap::new(columns, rows, thumb_keys).to_file();
-
-        Self {
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let board = Board::from_controller(config.keyboard.controller)?;
+
+        let firmware = &config.firmware;
+        let directory = firmware.directory_name();
+        let readme_md = include_str!("readme.md")
+            .replace("$name", &firmware.name)
+            .replace("$maintainer", &firmware.maintainer)
+            .replace("$directory", &directory)
+            .replace("$controller", board.name())
+            .replace("$physical_reset", board.reset_instructions());
+
+        let config_h = board.config_h();
+        let keyboard = Keyboard::new(config);
+        let keyboard_json = keyboard.to_file(board, firmware);
+        let keymap_c =
+            Keymap::new(&config.keymap, config.keymap_size(), keyboard.matrix()).to_file();
+
+        let Definitions {
+            via_json,
+            vial_json,
+            vial_config_h,
+        } = Definitions::new(&keyboard, firmware);
+
+        Some(Self {
+            directory,
+            readme_md,
+            rules_mk: include_str!("rules.mk"),
             config_h,
             keyboard_json,
             keymap_c,
-        }
+            via_json,
+            via_rules_mk: include_str!("via_rules.mk"),
+            vial_json,
+            vial_rules_mk: include_str!("vial_rules.mk"),
+            vial_config_h,
+        })
     }
 }
+
+/// Returns the content of the keyboard-layout-editor JSON file for the given configuration.
+///
+/// The keys are placed like in the QMK layout and labeled with the layers of the keymap, using one
+/// legend position per layer.
+#[must_use]
+pub fn kle_json(config: &Config) -> String {
+    let keyboard = Keyboard::new(config);
+
+    kle::to_file(
+        &config.firmware.name,
+        &keyboard.layout(),
+        &config.keymap,
+        config.keymap_size(),
+    )
+}
+
+/// Returns the keymap of the given configuration with the keycodes taken from the legends of the
+/// given keyboard-layout-editor JSON file.
+///
+/// Each legend position is imported as the keymap layer with the same index, adding layers as
+/// required. The keys of the file are matched with the keys of the keyboard by their position,
+/// such that layouts exported using [`kle_json`] can be edited and imported again.
+///
+/// # Errors
+///
+/// Returns a [`KleError`] if the JSON is not a valid layout, a key has more legends than there are
+/// legend positions or none of its keys matches a key of the keyboard.
+pub fn import_kle(config: &Config, json: &str) -> Result<config::Keymap, KleError> {
+    let keyboard = Keyboard::new(config);
+
+    kle::import(&keyboard.layout(), &config.keymap, json)
+}
@@ -0,0 +1 @@
+# All settings are contained in keyboard.json and config.h
@@ -0,0 +1,89 @@
+use std::hash::Hasher;
+
+use config::{Firmware, StableHasher};
+use itertools::Itertools;
+use serde_json::Value;
+
+use crate::{
+    keyboard::{Key, Keyboard},
+    kle,
+    replace_indented::ReplaceIndented,
+};
+
+/// The VIA and Vial definitions of a keyboard.
+pub struct Definitions {
+    /// The content of the `via.json` file.
+    pub via_json: String,
+    /// The content of the `vial.json` file.
+    pub vial_json: String,
+    /// The content of the `config.h` file of the Vial keymap.
+    pub vial_config_h: String,
+}
+
+impl Definitions {
+    /// Creates the VIA and Vial definitions for the given keyboard and firmware metadata.
+    pub fn new(keyboard: &Keyboard, firmware: &Firmware) -> Self {
+        let (rows, columns) = keyboard.matrix_size();
+        let keys = keyboard.layout();
+        // The keys are labeled with their matrix position as required by VIA and Vial
+        let keymap = kle::rows(&keys, |key| {
+            let (matrix_row, matrix_column) = key.matrix_position;
+
+            format!("{matrix_row},{matrix_column}")
+        });
+
+        let fill = |template: &str| {
+            template
+                .replace_indented("$keymap", &keymap)
+                .replace("$name", &Value::from(firmware.name.as_str()).to_string())
+                .replace("$vendor_id", &firmware.vendor_id.to_string())
+                .replace("$product_id", &firmware.product_id.to_string())
+                .replace("$rows", &rows.to_string())
+                .replace("$columns", &columns.to_string())
+        };
+        let vial_definition = fill(include_str!("vial.json"));
+
+        // Derive the UID from the definition to keep it stable across exports
+        let mut hasher = StableHasher::default();
+        hasher.write(vial_definition.as_bytes());
+        let uid = hasher
+            .finish()
+            .to_le_bytes()
+            .iter()
+            .map(|byte| format!("0x{byte:02X}"))
+            .join(", ");
+        let [left_key, right_key] = unlock_combo(&keys);
+        let vial_config_h = include_str!("vial_config.h")
+            .replace("$uid", &uid)
+            .replace(
+                "$unlock_combo_rows",
+                &format!("{}, {}", left_key.0, right_key.0),
+            )
+            .replace(
+                "$unlock_combo_columns",
+                &format!("{}, {}", left_key.1, right_key.1),
+            );
+
+        Self {
+            via_json: fill(include_str!("via.json")),
+            vial_json: vial_definition,
+            vial_config_h,
+        }
+    }
+}
+
+/// Returns the matrix positions of the keys used for unlocking Vial.
+///
+/// These are the outermost keys on both halves.
+fn unlock_combo(keys: &[Key]) -> [(usize, usize); 2] {
+    let outermost_keys = keys
+        .iter()
+        .minmax_by(|a, b| a.x.total_cmp(&b.x))
+        .into_option()
+        .expect("there is always at least one key");
+
+    [
+        outermost_keys.0.matrix_position,
+        outermost_keys.1.matrix_position,
+    ]
+}
@@ -0,0 +1 @@
+VIA_ENABLE = yes
@@ -0,0 +1,8 @@
+#pragma once
+
+// unique identifier of the keyboard used by Vial
+#define VIAL_KEYBOARD_UID {$uid}
+
+// keys to hold for unlocking security-sensitive features in Vial
+#define VIAL_UNLOCK_COMBO_ROWS {$unlock_combo_rows}
+#define VIAL_UNLOCK_COMBO_COLS {$unlock_combo_columns}
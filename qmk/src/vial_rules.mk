@@ -0,0 +1,2 @@
+VIA_ENABLE = yes
+VIAL_ENABLE = yes
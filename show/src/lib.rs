@@ -3,7 +3,7 @@
 use std::num::NonZeroU8;
 
 use hex_color::HexColor;
-use three_d::egui::{Align, Checkbox, CollapsingHeader, DragValue, Layout, RichText, Ui};
+use three_d::egui::{Align, Checkbox, CollapsingHeader, DragValue, Layout, RichText, TextEdit, Ui};
 
 pub use three_d::egui;
 
@@ -71,6 +71,15 @@ impl Show for bool {
     }
 }
 
+impl Show for String {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        const WIDTH: f32 = 120.0;
+
+        ui.add(TextEdit::singleline(self).desired_width(WIDTH))
+            .changed()
+    }
+}
+
 impl Show for u8 {
     fn show(&mut self, ui: &mut Ui) -> bool {
         ui.add(DragValue::new(self)).changed()
@@ -90,3 +99,47 @@ impl Show for NonZeroU8 {
         changed
     }
 }
+
+impl<T: Show + Default> Show for Option<T> {
+    fn show(&mut self, ui: &mut Ui) -> bool {
+        let mut active = self.is_some();
+        let mut changed = ui.add(Checkbox::without_text(&mut active)).changed();
+
+        if changed {
+            *self = active.then(T::default);
+        }
+        if let Some(value) = self {
+            changed |= value.show(ui);
+        }
+
+        changed
+    }
+
+    fn show_with_name_and_description(
+        &mut self,
+        ui: &mut Ui,
+        label: &str,
+        description: &str,
+    ) -> bool {
+        let mut active = self.is_some();
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label(label).on_hover_text(description);
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                changed = ui.add(Checkbox::without_text(&mut active)).changed();
+            });
+        });
+
+        if changed {
+            *self = active.then(T::default);
+        }
+        if let Some(value) = self {
+            ui.indent(label, |ui| {
+                changed |= value.show(ui);
+            });
+        }
+
+        changed
+    }
+}
@@ -1,4 +1,4 @@
-use config::KeySize;
+use config::{KeySize, SwitchType};
 use gui::DisplaySettings;
 use three_d::{Camera, Context, Light, Mat4, RenderTarget};
 
@@ -21,24 +21,38 @@ impl Keys {
         context: &Context,
         assets: &Assets,
         display_settings: &DisplaySettings,
-        switch_positions: Vec<Mat4>,
+        switch_type: SwitchType,
         finger_key_positions: Vec<Mat4>,
         thumb_key_positions: Vec<Mat4>,
         thumb_key_size: KeySize,
     ) -> Self {
         let colors = &display_settings.colors;
 
-        let switches =
-            InstancedObject::new(context, &assets.switch, colors.switch, switch_positions);
+        let (switch_asset, keycap_1u_asset, keycap_1_5u_asset) = if switch_type.is_low_profile() {
+            (
+                &assets.switch_low_profile,
+                &assets.keycap_low_profile_1u,
+                &assets.keycap_low_profile_1_5u,
+            )
+        } else {
+            (&assets.switch, &assets.keycap_1u, &assets.keycap_1_5u)
+        };
+
+        let switch_positions = finger_key_positions
+            .iter()
+            .chain(&thumb_key_positions)
+            .copied()
+            .collect();
+        let switches = InstancedObject::new(context, switch_asset, colors.switch, switch_positions);
         let finger_keycaps = InstancedObject::new(
             context,
-            &assets.keycap_1u,
+            keycap_1u_asset,
             colors.keycap,
             finger_key_positions,
         );
         let thumb_keycap_asset = match thumb_key_size {
-            KeySize::U1_5 => &assets.keycap_1_5u,
-            KeySize::U1 => &assets.keycap_1u,
+            KeySize::U1_5 => keycap_1_5u_asset,
+            KeySize::U1 => keycap_1u_asset,
         };
         let thumb_keycaps = InstancedObject::new(
             context,
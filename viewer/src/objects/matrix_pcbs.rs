@@ -1,7 +1,7 @@
 use std::iter::once;
 
-use gui::DisplaySettings;
-use three_d::{Camera, Context, Light, Mat4, RenderTarget};
+use gui::{DisplaySettings, MatrixPcbSettings};
+use three_d::{Camera, Context, Light, RenderTarget};
 
 use crate::{
     assets::Assets,
@@ -20,11 +20,14 @@ impl MatrixPcbs {
         context: &Context,
         assets: &Assets,
         display_settings: &DisplaySettings,
-        meshes: Vec<gui::InstancedMesh>,
-        switch_positions: Vec<Mat4>,
-        ffc_pad_positions: Vec<Mat4>,
+        settings: MatrixPcbSettings,
     ) -> Self {
         let color = display_settings.colors.matrix_pcb;
+        let MatrixPcbSettings {
+            meshes,
+            pad_positions,
+            ffc_pad_positions,
+        } = settings;
 
         let objects = meshes
             .into_iter()
@@ -33,7 +36,7 @@ impl MatrixPcbs {
                 context,
                 &assets.matrix_pcb_pad,
                 color,
-                switch_positions,
+                pad_positions,
             )))
             .chain(once(InstancedObject::new(
                 context,
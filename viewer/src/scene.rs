@@ -25,19 +25,13 @@ pub struct Scene {
 impl Scene {
     /// Creates a scene from the given model settings using the given assets.
     pub fn from_settings(context: &Context, settings: Settings, assets: &Assets) -> Scene {
-        let switch_positions: Vec<_> = settings
-            .finger_key_positions
-            .iter()
-            .chain(&settings.thumb_key_settings.thumb_key_positions)
-            .copied()
-            .collect();
         let display_settings = &settings.display_settings;
 
         let keys = Keys::new(
             context,
             assets,
             display_settings,
-            switch_positions.clone(),
+            settings.switch_type,
             settings.finger_key_positions,
             settings.thumb_key_settings.thumb_key_positions,
             settings.thumb_key_settings.key_size,
@@ -52,9 +46,7 @@ impl Scene {
             context,
             assets,
             display_settings,
-            settings.matrix_pcb_meshes,
-            switch_positions,
-            settings.ffc_pad_positions,
+            settings.matrix_pcb_settings,
         );
 
         let ambient = AmbientLight::new(context, 0.05, Srgba::WHITE);
@@ -0,0 +1,132 @@
+use std::fmt::Write;
+
+use config::{Keymap, KeymapSize};
+use matrix::{Matrix, Side};
+
+use crate::layout::Layout;
+
+/// Returns the content of the `.keymap` file for the given keymap.
+pub fn to_file(matrix: &Matrix, keymap: &Keymap, size: KeymapSize) -> String {
+    const INDENTATION: usize = 16;
+
+    let layer_names = keymap.layer_names();
+    let mut defines = String::new();
+    let mut layers = String::new();
+
+    for (layer, name) in layer_names.iter().enumerate() {
+        writeln!(defines, "#define {name} {layer}").expect("format should never fail");
+
+        let bindings = Layout::new(
+            matrix,
+            |side, column, row| {
+                let row = size.rows - 1 - row;
+                let column = match side {
+                    Side::Left => size.columns - 1 - column,
+                    Side::Right => size.columns + column,
+                };
+
+                binding(keymap.finger_keycode(layer, size, row, column))
+            },
+            |side, index| {
+                let index = match side {
+                    Side::Left => size.thumb_keys - 1 - index,
+                    Side::Right => size.thumb_keys + index,
+                };
+
+                binding(keymap.thumb_keycode(layer, size, index))
+            },
+        );
+
+        write!(
+            layers,
+            "\n        {node}_layer {{\n            \
+            display-name = \"{name}\";\n            \
+            bindings = <\n{bindings}            >;\n        \
+            }};\n",
+            node = name.to_lowercase(),
+            bindings = bindings.to_grid(INDENTATION),
+        )
+        .expect("format should never fail");
+    }
+
+    include_str!("concavum.keymap")
+        .replace("$defines", &defines)
+        .replace("$layers", &layers)
+}
+
+/// Returns the binding of the given QMK keycode.
+///
+/// Keycodes without a ZMK equivalent bind nothing.
+fn binding(keycode: &str) -> String {
+    let binding = if let Some((function, arguments)) = keycode
+        .strip_suffix(')')
+        .and_then(|keycode| keycode.split_once('('))
+    {
+        let arguments: Vec<_> = arguments.split(',').map(str::trim).collect();
+
+        match (function, arguments.as_slice()) {
+            ("MO", [layer]) => Some(format!("&mo {layer}")),
+            ("TG", [layer]) => Some(format!("&tog {layer}")),
+            ("TO" | "DF", [layer]) => Some(format!("&to {layer}")),
+            ("OSL", [layer]) => Some(format!("&sl {layer}")),
+            ("LT", [layer, keycode]) => key(keycode).map(|key| format!("&lt {layer} {key}")),
+            (modifier_tap, [keycode]) => modifier_tap
+                .strip_suffix("_T")
+                .and_then(key)
+                .zip(key(keycode))
+                .map(|(modifier, key)| format!("&mt {modifier} {key}")),
+            _ => None,
+        }
+    } else {
+        match keycode {
+            "NO" | "KC_NO" | "XXXXXXX" => Some("&none".to_owned()),
+            "TRNS" | "KC_TRNS" | "_______" => Some("&trans".to_owned()),
+            "QK_BOOT" => Some("&bootloader".to_owned()),
+            "QK_RBT" => Some("&sys_reset".to_owned()),
+            keycode => key(keycode).map(|key| format!("&kp {key}")),
+        }
+    };
+
+    binding.unwrap_or_else(|| "&none".to_owned())
+}
+
+/// Returns the ZMK key corresponding to the given basic QMK keycode.
+fn key(keycode: &str) -> Option<String> {
+    let key = match keycode.strip_prefix("KC_").unwrap_or(keycode) {
+        key if key.len() == 1 && key.starts_with(|character: char| character.is_ascii_digit()) => {
+            return Some(format!("N{key}"));
+        }
+        "ENT" => "RET",
+        "SPC" => "SPACE",
+        "MINS" => "MINUS",
+        "EQL" => "EQUAL",
+        "LBRC" => "LBKT",
+        "RBRC" => "RBKT",
+        "BSLS" => "BSLH",
+        "SCLN" => "SEMI",
+        "QUOT" => "SQT",
+        "GRV" => "GRAVE",
+        "COMM" => "COMMA",
+        "SLSH" => "FSLH",
+        "RGHT" => "RIGHT",
+        "PGUP" => "PG_UP",
+        "PGDN" => "PG_DN",
+        "LSFT" => "LSHFT",
+        "RSFT" => "RSHFT",
+        "LCTL" => "LCTRL",
+        "RCTL" => "RCTRL",
+        "LOPT" => "LALT",
+        "ROPT" => "RALT",
+        "LCMD" => "LGUI",
+        "RCMD" => "RGUI",
+        key if key
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric()) =>
+        {
+            key
+        }
+        _ => return None,
+    };
+
+    Some(key.to_owned())
+}
@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use matrix::{Matrix, Side};
+
+/// The number of spaces between the two halves of a formatted layout.
+const CENTER_PADDING: usize = 8;
+
+/// Keys of both halves arranged like on the keyboard.
+///
+/// The keys are ordered as in the matrix transform, i.e. row by row from the top, with the
+/// left keys preceding the right ones.
+pub struct Layout<T> {
+    rows: Vec<Row<T>>,
+    thumb_keys: Row<T>,
+    columns: usize,
+    thumb_key_count: usize,
+}
+
+impl<T> Layout<T> {
+    /// Creates a new layout for the given matrix using the given functions for creating the keys.
+    ///
+    /// The functions are called with the side and the indices of the key, with the columns and
+    /// thumb keys being indexed from the inside out and the rows from the bottom up.
+    pub fn new(
+        matrix: &Matrix,
+        finger_key: impl Fn(Side, usize, usize) -> T,
+        thumb_key: impl Fn(Side, usize) -> T,
+    ) -> Self {
+        let columns = matrix.columns();
+        let thumb_key_count = matrix.thumb_keys();
+
+        let rows = (0..matrix.rows())
+            .rev()
+            .map(|row| {
+                Row::new(columns, |side, column| {
+                    matrix
+                        .has_key(column, row)
+                        .then(|| finger_key(side, column, row))
+                })
+            })
+            .collect();
+        let thumb_keys = Row::new(thumb_key_count, |side, index| Some(thumb_key(side, index)));
+
+        Self {
+            rows,
+            thumb_keys,
+            columns,
+            thumb_key_count,
+        }
+    }
+}
+
+impl<T: AsRef<str>> Layout<T> {
+    /// Formats the keys as a grid with the given indentation.
+    pub fn to_grid(&self, indentation: usize) -> String {
+        let column_width = self
+            .rows
+            .iter()
+            .chain([&self.thumb_keys])
+            .flat_map(|row| row.left.iter().chain(&row.right).flatten())
+            .map(|key| key.as_ref().len() + 2)
+            .max()
+            .unwrap_or_default();
+        let row_padding =
+            indentation + column_width * self.thumb_key_count.saturating_sub(self.columns);
+        let thumb_key_padding =
+            indentation + column_width * self.columns.saturating_sub(self.thumb_key_count);
+
+        let mut out = String::new();
+
+        for (row, padding) in self
+            .rows
+            .iter()
+            .map(|row| (row, row_padding))
+            .chain([(&self.thumb_keys, thumb_key_padding)])
+        {
+            let mut line = " ".repeat(padding);
+
+            for (index, key) in row.left.iter().chain(&row.right).enumerate() {
+                if index == row.left.len() {
+                    line += &" ".repeat(CENTER_PADDING);
+                }
+
+                let key = key.as_ref().map(AsRef::as_ref).unwrap_or_default();
+                write!(line, "{key:column_width$}").expect("format should never fail");
+            }
+
+            writeln!(out, "{}", line.trim_end()).expect("format should never fail");
+        }
+
+        out
+    }
+}
+
+/// A row consisting of a left and right half row, with missing keys being `None`.
+struct Row<T> {
+    left: Vec<Option<T>>,
+    right: Vec<Option<T>>,
+}
+
+impl<T> Row<T> {
+    /// Creates a new row with the given number of keys per half using the given function.
+    ///
+    /// The function is called with the side and the index of the key from the inside out.
+    fn new(keys: usize, key: impl Fn(Side, usize) -> Option<T>) -> Self {
+        Self {
+            left: (0..keys)
+                .rev()
+                .map(|index| key(Side::Left, index))
+                .collect(),
+            right: (0..keys).map(|index| key(Side::Right, index)).collect(),
+        }
+    }
+}
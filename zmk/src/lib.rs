@@ -0,0 +1,130 @@
+//! The `zmk` crate implements the generation of a ZMK shield for wireless builds.
+
+mod keymap;
+mod layout;
+
+use std::fmt::Write;
+
+use config::Config;
+use matrix::{Matrix, Side};
+
+use crate::layout::Layout;
+
+/// A set of ZMK configuration files forming a shield for the nice!nano.
+pub struct Files {
+    /// The content of the `Kconfig.shield` file.
+    pub kconfig_shield: &'static str,
+    /// The content of the `Kconfig.defconfig` file.
+    pub kconfig_defconfig: String,
+    /// The content of the `concavum.dtsi` file containing the matrix transform.
+    pub dtsi: String,
+    /// The content of the `concavum_left.overlay` file.
+    pub left_overlay: String,
+    /// The content of the `concavum_right.overlay` file.
+    pub right_overlay: String,
+    /// The content of the `concavum.keymap` file.
+    pub keymap: String,
+    /// The content of the `concavum.conf` file.
+    pub conf: &'static str,
+    /// The content of the `build.yaml` file.
+    pub build_yaml: &'static str,
+    /// The content of the `west.yml` file.
+    pub west_yml: &'static str,
+}
+
+impl Files {
+    /// Creates a set of ZMK files from the given configuration.
+    ///
+    /// Returns `None` if the controller is not a wireless one.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.keyboard.controller.is_wireless() {
+            return None;
+        }
+
+        let matrix = Matrix::from_config(config);
+
+        Some(Self {
+            kconfig_shield: include_str!("Kconfig.shield"),
+            kconfig_defconfig: kconfig_defconfig(&config.firmware.name),
+            dtsi: dtsi(&matrix),
+            left_overlay: overlay(&matrix, Side::Left),
+            right_overlay: overlay(&matrix, Side::Right),
+            keymap: keymap::to_file(&matrix, &config.keymap, config.keymap_size()),
+            conf: include_str!("concavum.conf"),
+            build_yaml: include_str!("build.yaml"),
+            west_yml: include_str!("west.yml"),
+        })
+    }
+}
+
+/// Returns the content of the `Kconfig.defconfig` file for a keyboard with the given name.
+///
+/// The name is truncated to the maximum length of a ZMK keyboard name and quoted as a Kconfig
+/// string.
+fn kconfig_defconfig(name: &str) -> String {
+    const MAX_NAME_LENGTH: usize = 16;
+
+    let mut quoted_name = String::from('"');
+    for character in name.trim().chars().take(MAX_NAME_LENGTH) {
+        if matches!(character, '"' | '\\') {
+            quoted_name.push('\\');
+        }
+        quoted_name.push(character);
+    }
+    quoted_name.push('"');
+
+    include_str!("Kconfig.defconfig").replace("$name", &quoted_name)
+}
+
+/// Returns the content of the `.dtsi` file shared by both halves.
+fn dtsi(matrix: &Matrix) -> String {
+    const INDENTATION: usize = 12;
+
+    // Both halves share the same matrix transform, with the columns of the right half following
+    // the ones of the left half
+    let key = |side, (row, column)| match side {
+        Side::Left => format!("RC({row},{column})"),
+        Side::Right => format!("RC({row},{})", matrix.column_count() + column),
+    };
+    let map = Layout::new(
+        matrix,
+        |side, column, row| key(side, matrix.finger_key_position(side, column, row)),
+        |side, index| key(side, matrix.thumb_key_position(side, index)),
+    );
+
+    include_str!("concavum.dtsi")
+        .replace("$columns", &(2 * matrix.column_count()).to_string())
+        .replace("$rows", &matrix.row_count().to_string())
+        .replace("$map\n", &map.to_grid(INDENTATION))
+}
+
+/// Returns the content of the `.overlay` file of the given side.
+fn overlay(matrix: &Matrix, side: Side) -> String {
+    let (column_pins, row_pins) = matrix.pins(side);
+    let template = match side {
+        Side::Left => include_str!("concavum_left.overlay"),
+        Side::Right => include_str!("concavum_right.overlay"),
+    };
+
+    template
+        .replace("$column_offset", &matrix.column_count().to_string())
+        .replace("$column_gpios\n", &gpios(&column_pins, "GPIO_ACTIVE_HIGH"))
+        .replace(
+            "$row_gpios\n",
+            &gpios(&row_pins, "(GPIO_ACTIVE_HIGH | GPIO_PULL_DOWN)"),
+        )
+}
+
+/// Returns a GPIO list of the given Pro Micro pins with the given flags.
+fn gpios(pins: &[u8], flags: &str) -> String {
+    let mut out = String::new();
+
+    for (index, pin) in pins.iter().enumerate() {
+        let separator = if index == 0 { '=' } else { ',' };
+        writeln!(out, "        {separator} <&pro_micro {pin} {flags}>")
+            .expect("format should never fail");
+    }
+
+    out
+}